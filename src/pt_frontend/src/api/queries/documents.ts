@@ -29,7 +29,7 @@ export const getDocumentOptions = (id: DocumentId) => {
   return createQueryOptions({
     queryFn: async () =>
       getSingleApiResult(
-        () => api.tenant.list_documents({ pagination }),
+        () => api.tenant.list_documents({ folder_id: [], pagination, recursive: [] }),
         'Document not found',
       ),
     queryKey: ['document', { id }],
@@ -52,7 +52,7 @@ export const listDocumentsByProjectIdOptions = (projectId: ProjectId) => {
   });
 
   return createQueryOptions({
-    queryFn: () => api.tenant.list_documents({ pagination }),
+    queryFn: () => api.tenant.list_documents({ folder_id: [], pagination, recursive: [] }),
     queryKey: ['documents_by_project_id', { projectId, pagination }],
   });
 };
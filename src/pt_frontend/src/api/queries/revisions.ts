@@ -80,6 +80,7 @@ export const downloadRevisionContentOptions = (
       api.tenant.download_revision_content({
         content_id: contentId,
         chunk_id: chunkId !== undefined ? [chunkId] : [],
+        share_token: [],
       }),
     queryKey: ['download_revision_content', { contentId, chunkId }],
     enabled: false, // Only enable when manually triggered
@@ -19,6 +19,11 @@ import {
   calculateUploadMetrics,
   separateContentBySize,
 } from '@/utils/chunked-revision-upload';
+import {
+  contentTypeForMime,
+  isFileContent,
+  mimeTypeForFile,
+} from '@/utils/content-type';
 import { createZodFieldValidator } from '@/utils/create-zod-field-validator';
 
 import { Loading } from '@/components/loading';
@@ -53,7 +58,6 @@ export type ContentFormProps = {
   isSubmitting: boolean;
   onSubmit: (
     contents: Array<RevisionContent>,
-    largeContents: Array<RevisionContent>,
   ) => Promise<{ revisionId: bigint } | null>;
   onSubmitComplete: () => void;
   defaultMarkdownContent?: string;
@@ -136,6 +140,10 @@ export const ContentForm: FC<ContentFormProps> = ({
             file_name: originalMarkdownContent.file_name,
             content_data: [], // Empty array represents None - reference existing content
             content_type: originalMarkdownContent.content_type,
+            mime_type: originalMarkdownContent.mime_type,
+            encryption: originalMarkdownContent.encryption,
+            purged: [],
+            sha256: [],
           });
         } else {
           // Content has changed - send new content
@@ -147,6 +155,10 @@ export const ContentForm: FC<ContentFormProps> = ({
             file_name: [],
             content_data: [{ Direct: { bytes: Array.from(markdownBytes) } }], // Wrap in array (Some)
             content_type: { Markdown: null },
+            mime_type: ['text/markdown'],
+            encryption: [],
+            purged: [],
+            sha256: [],
           });
         }
       }
@@ -157,7 +169,7 @@ export const ContentForm: FC<ContentFormProps> = ({
           // Check if this file exists in original contents and is unchanged
           const originalFileContent = originalRevisionContents?.find(
             (content) =>
-              'Upload' in content.content_type &&
+              isFileContent(content.content_type) &&
               content.file_name.length > 0 &&
               content.file_name[0] === file.name,
           );
@@ -198,6 +210,10 @@ export const ContentForm: FC<ContentFormProps> = ({
               file_name: originalFileContent.file_name,
               content_data: [], // Empty array represents None - reference existing content
               content_type: originalFileContent.content_type,
+              mime_type: originalFileContent.mime_type,
+              encryption: originalFileContent.encryption,
+              purged: [],
+              sha256: [],
             });
 
             // Mark as completed immediately since we're not uploading
@@ -215,11 +231,16 @@ export const ContentForm: FC<ContentFormProps> = ({
             });
 
             const fileBytes = new Uint8Array(await file.arrayBuffer());
+            const mimeType = mimeTypeForFile(file);
             contents.push({
               id: 0n, // Will be assigned by backend
               file_name: [file.name],
               content_data: [{ Direct: { bytes: Array.from(fileBytes) } }], // Wrap in array (Some)
-              content_type: { Upload: null },
+              content_type: contentTypeForMime(mimeType),
+              mime_type: [mimeType],
+              encryption: [],
+              purged: [],
+              sha256: [],
             });
 
             // Update progress: file processed (for small files this completes them)
@@ -237,14 +258,8 @@ export const ContentForm: FC<ContentFormProps> = ({
       // Separate content by size for different upload strategies
       const { smallContent, largeContent } = separateContentBySize(contents);
 
-      // Call parent to create revision with small content
-      const result = await onSubmit(smallContent, largeContent);
-
-      if (!result) {
-        return; // Error occurred in parent
-      }
-
-      // Handle large content upload with progress tracking
+      // Upload large content first so the revision is created complete
+      const uploadedContent: Array<RevisionContent> = [];
       if (largeContent.length > 0) {
         const metrics = calculateUploadMetrics(largeContent);
         console.log(
@@ -263,12 +278,20 @@ export const ContentForm: FC<ContentFormProps> = ({
           fileSize: file.size,
         }));
 
-        await uploadProgress.uploadWithProgress({
-          revisionId: result.revisionId,
-          contents: largeContent,
-          startingContentIndex,
-          fileMapping,
-        });
+        uploadedContent.push(
+          ...(await uploadProgress.uploadWithProgress({
+            contents: largeContent,
+            startingContentIndex,
+            fileMapping,
+          })),
+        );
+      }
+
+      // Call parent to create revision with small content and uploaded references
+      const result = await onSubmit([...smallContent, ...uploadedContent]);
+
+      if (!result) {
+        return; // Error occurred in parent
       }
 
       // Call completion callback to navigate
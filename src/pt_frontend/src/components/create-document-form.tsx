@@ -29,7 +29,6 @@ type CreateDocumentFormProps = {
   onSubmit: (
     title: string,
     contents: Array<RevisionContent>,
-    largeContents: Array<RevisionContent>,
   ) => Promise<{ revisionId: bigint } | null>;
   onSubmitComplete: () => void;
 };
@@ -45,10 +44,7 @@ export const CreateDocumentForm: FC<CreateDocumentFormProps> = ({
     },
   });
 
-  const handleContentSubmit = async (
-    contents: Array<RevisionContent>,
-    largeContents: Array<RevisionContent>,
-  ) => {
+  const handleContentSubmit = async (contents: Array<RevisionContent>) => {
     const title = titleForm.state.values.title;
 
     // Validate title
@@ -58,7 +54,7 @@ export const CreateDocumentForm: FC<CreateDocumentFormProps> = ({
       return null;
     }
 
-    return await onSubmit(title, contents, largeContents);
+    return await onSubmit(title, contents);
   };
 
   return (
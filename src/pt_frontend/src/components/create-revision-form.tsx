@@ -2,6 +2,7 @@ import type { FC } from 'react';
 import { useEffect, useState } from 'react';
 
 import { downloadChunkedContent } from '@/utils/chunked-revision-download';
+import { isFileContent } from '@/utils/content-type';
 
 import { ContentForm } from '@/components/content-form';
 import { Card, CardContent, CardHeader, CardTitle } from '@/components/ui/card';
@@ -13,7 +14,6 @@ type CreateRevisionFormProps = {
   isSubmitting: boolean;
   onSubmit: (
     contents: Array<RevisionContent>,
-    largeContents: Array<RevisionContent>,
   ) => Promise<{ revisionId: bigint } | null>;
   onSubmitComplete: () => void;
   revisionContents: Array<RevisionContent> | undefined;
@@ -63,9 +63,10 @@ export const CreateRevisionForm: FC<CreateRevisionFormProps> = ({
           ? content.file_name[0]
           : `content-${content.id}`;
       const mimeType =
-        'Upload' in content.content_type
+        content.mime_type[0] ??
+        (isFileContent(content.content_type)
           ? 'application/octet-stream'
-          : 'text/markdown'; // oxlint-disable-line
+          : 'text/markdown'); // oxlint-disable-line
 
       const arrayBuffer = new ArrayBuffer(bytes.length);
       const view = new Uint8Array(arrayBuffer);
@@ -124,7 +125,7 @@ export const CreateRevisionForm: FC<CreateRevisionFormProps> = ({
     if (!revisionContents) return [];
 
     const uploadContents = revisionContents.filter(
-      (content) => 'Upload' in content.content_type,
+      (content) => isFileContent(content.content_type),
     );
 
     const files: Array<File> = [];
@@ -49,7 +49,7 @@ export function useChunkedUpload() {
       }));
 
       try {
-        await uploadRevisionContentInChunks({
+        const uploadedContents = await uploadRevisionContentInChunks({
           ...options,
           onProgress: (progress) => {
             const percentComplete = Math.round(
@@ -107,6 +107,8 @@ export function useChunkedUpload() {
           error: null,
           fileProgress: prev.fileProgress,
         }));
+
+        return uploadedContents;
       } catch (error) {
         setState((prev) => ({
           isUploading: false,
@@ -41,6 +41,9 @@ function CreateDocument() {
     // Step 1: Create the document
     const documentResult = await tryCatch(
       createDocument({
+        document_type: [],
+        encrypted: [],
+        folder_id: [],
         project_id: projectId,
         title,
       }),
@@ -53,7 +56,7 @@ function CreateDocument() {
 
     createdDocumentId = documentResult.data;
 
-    // Step 2: Create revision with small content
+    // Step 2: Create revision; large content is referenced by its uploaded ID
     const revisionResult = await tryCatch(
       createRevision({
         project_id: projectId,
@@ -67,7 +70,6 @@ function CreateDocument() {
       return null;
     }
 
-    // Return the revision ID for large content uploads (if needed)
     return { revisionId: revisionResult.data };
   }
 
@@ -83,7 +83,11 @@ export const Route = createFileRoute(
     });
 
     const [documents, paginationMetaData] = await context.query.ensureQueryData(
-      listDocumentsOptions({ pagination: defaultPagination }),
+      listDocumentsOptions({
+        folder_id: [],
+        pagination: defaultPagination,
+        recursive: [],
+      }),
     );
 
     return {
@@ -37,7 +37,7 @@ export const Route = createFileRoute(
   loader: async ({ context, deps }) => {
     const pagination = processPaginationInput(deps.pagination);
     const [documents, paginationMetaData] = await context.query.ensureQueryData(
-      listDocumentsOptions({ pagination }),
+      listDocumentsOptions({ folder_id: [], pagination, recursive: [] }),
     );
 
     return {
@@ -43,7 +43,7 @@ export const Route = createFileRoute(
     );
 
     const [documents, paginationMetaData] = await context.query.ensureQueryData(
-      listDocumentsOptions({ pagination }),
+      listDocumentsOptions({ folder_id: [], pagination, recursive: [] }),
     );
     return {
       context,
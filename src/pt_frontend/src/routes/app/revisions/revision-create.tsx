@@ -49,7 +49,7 @@ function RevisionsCreate() {
     const projectId = projectIdSchema.parse(params.projectId);
     const documentId = documentIdSchema.parse(params.documentId);
 
-    // Create revision with small content first
+    // Large content has already been uploaded and is referenced by ID
     const result = await tryCatch(
       createRevision({
         contents: contents,
@@ -65,7 +65,6 @@ function RevisionsCreate() {
 
     const revisionId = result.data;
 
-    // Return revision ID so form can handle large content upload
     return { revisionId };
   }
 
@@ -11,6 +11,7 @@ import {
   downloadChunkedContent,
   triggerDownload,
 } from '@/utils/chunked-revision-download';
+import { isFileContent } from '@/utils/content-type';
 
 import { Badge } from '@/components/ui/badge';
 import { Button } from '@/components/ui/button';
@@ -136,7 +137,7 @@ function RevisionDetails() {
     if (content.file_name[0] && content.file_name.length > 0) {
       return content.file_name[0];
     }
-    if ('Upload' in content.content_type) {
+    if (isFileContent(content.content_type)) {
       return `upload-${index + 1}`;
     }
     return `content-${index + 1}`;
@@ -283,7 +284,7 @@ function RevisionDetails() {
                 );
               }
 
-              if ('Upload' in content.content_type) {
+              if (isFileContent(content.content_type)) {
                 return (
                   <div
                     key={content.id}
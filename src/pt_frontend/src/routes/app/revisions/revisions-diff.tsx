@@ -40,13 +40,13 @@ export const Route = createFileRoute(
   validateSearch: zodSearchValidator(revisionSearchSchema),
   loaderDeps: ({ search: { current, theirs } }) => ({ current, theirs }),
   loader: async ({ context, deps }) => {
-    const revisions = await context.query.ensureQueryData(
+    const diff = await context.query.ensureQueryData(
       getDiffRevisionsOptions({
         original: BigInt(deps.current),
         updated: BigInt(deps.theirs),
       }),
     );
-    return { revisions };
+    return { diff, revisions: [diff.original, diff.updated] };
   },
   component: RevisionDiff,
   errorComponent: ({ error }) => {
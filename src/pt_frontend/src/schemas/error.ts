@@ -12,6 +12,7 @@ export const AppErrorSchema = z.union([
   z.object({ InvalidPageNumber: z.string() }),
   z.object({ InvalidPageSize: z.string() }),
   z.object({ InvalidStateTransition: z.string() }),
+  z.object({ QuotaExceeded: z.string() }),
   z.object({ SpawnCanister: z.string() }),
   z.object({ Unauthorized: z.null() }),
   z.object({ ValidationError: z.string() }),
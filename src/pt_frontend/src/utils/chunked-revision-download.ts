@@ -34,6 +34,7 @@ export async function downloadChunkedContent({
         const result = await api.tenant.download_revision_content({
           content_id: contentId,
           chunk_id: [chunkId],
+          share_token: [],
         });
 
         const chunkData = new Uint8Array(result);
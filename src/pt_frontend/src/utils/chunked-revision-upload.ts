@@ -1,7 +1,4 @@
-import type {
-  RevisionContent,
-  RevisionContentChunk,
-} from '@/declarations/tenant_canister/tenant_canister.did';
+import type { RevisionContent } from '@/declarations/tenant_canister/tenant_canister.did';
 
 import { api } from '@/api';
 
@@ -17,22 +14,28 @@ export type ChunkedUploadProgress = {
 };
 
 export type ChunkedRevisionUploadOptions = {
-  revisionId: bigint;
   contents: Array<RevisionContent>;
   startingContentIndex?: number;
   onProgress?: (progress: ChunkedUploadProgress) => void;
 };
 
+async function sha256Hex(bytes: Uint8Array): Promise<string> {
+  const digest = await crypto.subtle.digest('SHA-256', bytes);
+  return Array.from(new Uint8Array(digest))
+    .map((byte) => byte.toString(16).padStart(2, '0'))
+    .join('');
+}
+
 /**
- * Upload revision content using chunking for large files
- * Following the same pattern as the bash WASM upload script
+ * Upload large revision content in chunks using upload sessions, before the
+ * revision is created. Returns the contents with every uploaded item replaced
+ * by a reference to its content ID, ready to be passed to create_revision.
  */
 export async function uploadRevisionContentInChunks({
-  revisionId,
   contents,
   startingContentIndex = 0,
   onProgress,
-}: ChunkedRevisionUploadOptions): Promise<void> {
+}: ChunkedRevisionUploadOptions): Promise<Array<RevisionContent>> {
   const totalContentSize = contents.reduce((sum, content) => {
     if (content.content_data.length === 0) {
       // Reference to existing content, no size to add
@@ -45,61 +48,44 @@ export async function uploadRevisionContentInChunks({
     return sum + Number(contentData.Chunked.total_size);
   }, 0);
   let uploadedBytes = 0;
+  const uploadedContents: Array<RevisionContent> = [];
 
   for (let i = 0; i < contents.length; i++) {
     const contentIndex = startingContentIndex + i;
     const content = contents[i];
 
-    // Skip content that's a reference to existing content
-    if (content.content_data.length === 0) {
-      continue;
-    }
-
     const contentData = content.content_data[0];
-    // Only process Direct content for chunking (Chunked content is already processed)
-    if (!('Direct' in contentData)) {
-      continue; // Skip already chunked content
-    }
-
-    const contentBytes = new Uint8Array(contentData.Direct.bytes);
-
-    // Skip small content that can be uploaded directly
-    if (contentBytes.length <= MAX_DIRECT_UPLOAD_SIZE) {
-      uploadedBytes += contentBytes.length;
-      onProgress?.({
-        contentIndex,
-        chunkId: 0,
-        totalChunks: 1,
-        totalContentSize,
-        uploadedBytes,
-      });
+    // Only Direct content is uploaded; references are passed through as-is
+    if (!contentData || !('Direct' in contentData)) {
+      uploadedContents.push(content);
       continue;
     }
 
-    // Calculate total chunks for this content
+    const contentBytes = new Uint8Array(contentData.Direct.bytes);
     const totalChunks = Math.ceil(contentBytes.length / CHUNK_SIZE);
 
+    // The API wrapper will throw an error if the result is Err, so we can just await
+    const uploadId = await api.tenant.begin_upload({
+      content_type: content.content_type,
+      file_name:
+        content.file_name.length > 0 ? [content.file_name[0] ?? ''] : [],
+      total_size: BigInt(contentBytes.length),
+      total_chunks: totalChunks,
+      sha256: await sha256Hex(contentBytes),
+      mime_type: content.mime_type[0] ?? 'application/octet-stream',
+      encryption: content.encryption,
+    });
+
     for (let chunkId = 0; chunkId < totalChunks; chunkId++) {
       const start = chunkId * CHUNK_SIZE;
       const end = Math.min(start + CHUNK_SIZE, contentBytes.length);
       const chunkData = contentBytes.slice(start, end);
 
-      const chunk: RevisionContentChunk = {
+      await api.tenant.upload_chunk({
+        upload_id: uploadId,
         chunk_id: chunkId,
-        total_chunks: totalChunks,
         data: Array.from(chunkData),
-        checksum: [], // TODO: Implement checksum calculation
-      };
-
-      // Upload chunk using the same pattern as WASM upload
-      // The API wrapper will throw an error if the result is Err, so we can just await
-      await api.tenant.store_revision_content_chunk({
-        revision_id: revisionId,
-        content_index: contentIndex,
-        chunk,
-        content_type: content.content_type,
-        file_name:
-          content.file_name.length > 0 ? [content.file_name[0] ?? ''] : [],
+        checksum: [await sha256Hex(chunkData)],
       });
 
       uploadedBytes += chunkData.length;
@@ -112,13 +98,21 @@ export async function uploadRevisionContentInChunks({
       });
     }
 
-    // Finish upload for this content (assembles chunks)
-    // The API wrapper will throw an error if the result is Err, so we can just await
-    await api.tenant.finish_revision_content_upload({
-      revision_id: revisionId,
-      content_index: contentIndex,
+    // Verifies size and checksum and returns the ID to reference the content by
+    const contentId = await api.tenant.finish_upload({ upload_id: uploadId });
+    uploadedContents.push({
+      id: contentId,
+      file_name: content.file_name,
+      content_type: content.content_type,
+      mime_type: content.mime_type,
+      encryption: content.encryption,
+      content_data: [], // Empty array represents None - reference uploaded content
+      purged: [],
+      sha256: [],
     });
   }
+
+  return uploadedContents;
 }
 
 /**
@@ -0,0 +1,37 @@
+import type { RevisionContentType } from '@/declarations/tenant_canister/tenant_canister.did';
+
+const SPREADSHEET_MIME_TYPES = [
+  'text/csv',
+  'application/vnd.ms-excel',
+  'application/vnd.openxmlformats-officedocument.spreadsheetml.sheet',
+  'application/vnd.oasis.opendocument.spreadsheet',
+];
+
+export const STRUCTURED_FORM_MIME_TYPE = 'application/vnd.permatrust.form+json';
+
+/**
+ * MIME type to declare for a file, falling back to a generic binary type
+ * when the browser cannot determine one
+ */
+export function mimeTypeForFile(file: File): string {
+  return file.type || 'application/octet-stream';
+}
+
+/**
+ * Content kind matching a MIME type, mirroring the backend classification
+ */
+export function contentTypeForMime(mimeType: string): RevisionContentType {
+  if (mimeType === 'text/markdown') return { Markdown: null };
+  if (mimeType === 'application/pdf') return { Pdf: null };
+  if (SPREADSHEET_MIME_TYPES.includes(mimeType)) return { Spreadsheet: null };
+  if (mimeType === STRUCTURED_FORM_MIME_TYPE) return { StructuredForm: null };
+  if (mimeType.startsWith('image/')) return { Image: null };
+  return { Upload: null };
+}
+
+/**
+ * Whether content is a file attachment rather than the document's markdown body
+ */
+export function isFileContent(contentType: RevisionContentType): boolean {
+  return !('Markdown' in contentType);
+}
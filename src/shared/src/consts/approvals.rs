@@ -0,0 +1,5 @@
+// Maximum length of a signature reason in chars
+pub const MAX_SIGNATURE_REASON_LENGTH: usize = 1_000;
+
+// Time a signing challenge stays valid in nanoseconds (5 minutes)
+pub const SIGNING_CHALLENGE_TTL: u64 = 5 * 60 * 1_000_000_000;
@@ -0,0 +1,2 @@
+// Maximum length of a comment body in chars
+pub const MAX_COMMENT_LENGTH: usize = 10_000;
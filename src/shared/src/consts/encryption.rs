@@ -0,0 +1,2 @@
+// Domain separator of document keys, also the vetKD context
+pub const DOCUMENT_KEY_CONTEXT: &[u8] = b"permatrust_document_key";
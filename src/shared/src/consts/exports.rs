@@ -0,0 +1,9 @@
+// Size of the archive chunks returned by get_export_chunk (2MB), below the query response limit
+pub const EXPORT_CHUNK_SIZE: u64 = 2_097_152;
+
+// Time after which prepared exports are removed (24 hours, in nanoseconds)
+pub const EXPORT_TTL: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+// Identifies the manifest format of export archives
+pub const EXPORT_FORMAT: &str = "permatrust-export";
+pub const EXPORT_FORMAT_VERSION: u32 = 1;
@@ -0,0 +1,8 @@
+// Maximum body size of a single HTTP response or streaming callback (2MB), below the query response limit
+pub const MAX_HTTP_BODY_SIZE: u64 = 2_097_152;
+
+// Path prefix content is served under, followed by the content ID
+pub const CONTENT_HTTP_PATH: &str = "/contents/";
+
+// Appended to a content path to get the HTML preview of Markdown content
+pub const CONTENT_PREVIEW_HTTP_SUFFIX: &str = "/preview";
@@ -14,10 +14,46 @@ pub mod tenant_canister {
     pub const USER_ROLES_MEMORY_ID: u8 = 7;
     pub const INVITES_MEMORY_ID: u8 = 8;
     pub const LOGS_STORAGE_MEMORY_ID: u8 = 9;
-    pub const REVISION_CONTENT_CHUNKS_MEMORY_ID: u8 = 10;
-    pub const REVISION_CONTENT_METADATA_MEMORY_ID: u8 = 11;
+    pub const LEGACY_REVISION_CONTENT_CHUNKS_MEMORY_ID: u8 = 10; // string keyed, migrated on upgrade
+    pub const LEGACY_REVISION_CONTENT_METADATA_MEMORY_ID: u8 = 11; // string keyed, migrated on upgrade
     pub const REVISION_CONTENT_MEMORY_ID: u8 = 12;
-    pub const REVISION_CHUNK_REFS_MEMORY_ID: u8 = 13;
+    pub const LEGACY_REVISION_CHUNK_REFS_MEMORY_ID: u8 = 13; // string keyed, migrated on upgrade
+    pub const DOCUMENT_LINKS_MEMORY_ID: u8 = 14;
+    pub const FOLDERS_MEMORY_ID: u8 = 15;
+    pub const SEARCH_INDEX_MEMORY_ID: u8 = 16;
+    pub const SEARCH_REVISIONS_MEMORY_ID: u8 = 17;
+    pub const COMMENTS_MEMORY_ID: u8 = 18;
+    pub const SHARE_LINKS_MEMORY_ID: u8 = 19;
+    pub const APPROVAL_POLICIES_MEMORY_ID: u8 = 20;
+    pub const REVISION_SIGNATURES_MEMORY_ID: u8 = 21;
+    pub const UPLOAD_SESSIONS_MEMORY_ID: u8 = 22;
+    pub const UPLOAD_CHUNK_REFS_MEMORY_ID: u8 = 23;
+    pub const CHUNK_USAGE_MEMORY_ID: u8 = 24;
+    pub const UPLOAD_SETTINGS_MEMORY_ID: u8 = 25;
+    pub const REVISION_CONTENT_CHUNKS_MEMORY_ID: u8 = 26;
+    pub const REVISION_CONTENT_METADATA_MEMORY_ID: u8 = 27;
+    pub const REVISION_CHUNK_REFS_MEMORY_ID: u8 = 28;
+    pub const REVISION_CONTENT_INDEX_MEMORY_ID: u8 = 29;
+    pub const CONTENT_TYPE_POLICY_MEMORY_ID: u8 = 30;
+    pub const STORAGE_QUOTA_MEMORY_ID: u8 = 31;
+    pub const PROJECT_STORAGE_USAGE_MEMORY_ID: u8 = 32;
+    pub const DOCUMENT_KEYS_MEMORY_ID: u8 = 33;
+    pub const CONTENT_PREVIEWS_MEMORY_ID: u8 = 34;
+    pub const EXPORTS_MEMORY_ID: u8 = 35;
+    pub const REVIEW_SCHEDULES_MEMORY_ID: u8 = 36;
+    pub const RETENTION_POLICY_MEMORY_ID: u8 = 37;
+    pub const DOCUMENT_LEGAL_HOLDS_MEMORY_ID: u8 = 38;
+    pub const PROJECT_LEGAL_HOLDS_MEMORY_ID: u8 = 39;
+    pub const PURGE_RECORDS_MEMORY_ID: u8 = 40;
+    pub const TRAINING_POLICIES_MEMORY_ID: u8 = 41;
+    pub const TRAINING_ASSIGNMENTS_MEMORY_ID: u8 = 42;
+    pub const SUBSCRIPTIONS_MEMORY_ID: u8 = 43;
+    pub const NOTIFICATIONS_MEMORY_ID: u8 = 44;
+    pub const STORAGE_MIGRATION_MEMORY_ID: u8 = 45;
+    pub const UPLOAD_CONTENT_INDEX_MEMORY_ID: u8 = 46;
+    pub const CONTENT_REVISION_INDEX_MEMORY_ID: u8 = 47;
+    pub const SEARCH_REVISION_TERMS_MEMORY_ID: u8 = 48;
+    pub const SUBSCRIBERS_MEMORY_ID: u8 = 49;
 }
 
 pub mod upgrade_canister {
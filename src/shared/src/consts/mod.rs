@@ -1,2 +1,15 @@
+pub mod approvals;
+pub mod comments;
+pub mod encryption;
+pub mod exports;
+pub mod http;
 pub mod memory_ids;
+pub mod notifications;
+pub mod quotas;
+pub mod retention;
+pub mod reviews;
 pub mod revisions;
+pub mod search;
+pub mod share_links;
+pub mod training;
+pub mod uploads;
@@ -0,0 +1,11 @@
+// Notifications kept per user, the oldest are dropped beyond this
+pub const MAX_NOTIFICATIONS_PER_USER: usize = 500;
+
+// Subscriptions a user can hold
+pub const MAX_SUBSCRIPTIONS_PER_USER: usize = 500;
+
+// Notifications listed when the request does not set a limit
+pub const DEFAULT_NOTIFICATION_LIMIT: u32 = 50;
+
+// Maximum number of notifications listed per request
+pub const MAX_NOTIFICATION_LIMIT: u32 = 200;
@@ -0,0 +1,2 @@
+// Storage quota of a tenant until one is configured (10GB)
+pub const DEFAULT_STORAGE_QUOTA: u64 = 10 * 1024 * 1024 * 1024;
@@ -0,0 +1,17 @@
+// How often the retention job looks for revisions to purge (1 hour)
+pub const RETENTION_CHECK_INTERVAL_SECONDS: u64 = 60 * 60;
+
+// Revisions purged per run of the retention job, keeps a run within the instruction limit
+pub const MAX_PURGES_PER_RUN: usize = 50;
+
+// Longest retention period that can be configured
+pub const MAX_RETENTION_YEARS: u32 = 100;
+
+// Maximum number of per document type retention rules
+pub const MAX_RETENTION_RULES: usize = 100;
+
+// Maximum length of a document type in chars
+pub const MAX_DOCUMENT_TYPE_LENGTH: usize = 64;
+
+// Maximum length of a legal hold reason in chars
+pub const MAX_LEGAL_HOLD_REASON_LENGTH: usize = 1_000;
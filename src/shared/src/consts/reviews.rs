@@ -0,0 +1,5 @@
+// How often the review timer flags overdue documents (1 hour)
+pub const REVIEW_CHECK_INTERVAL_SECONDS: u64 = 60 * 60;
+
+// Longest review interval that can be configured (10 years)
+pub const MAX_REVIEW_INTERVAL_MONTHS: u32 = 120;
@@ -1,2 +1,49 @@
 // Size in bytes (2MB - small buffer for overhead)
 pub const MAX_DOCUMENT_SIZE: u32 = 2_097_152;
+
+// Upper bound on the comparison table of a diff (lines x lines, or words x words).
+// Larger inputs are reported as a full replacement of the differing region.
+pub const MAX_DIFF_CELLS: usize = 4_000_000;
+
+// Largest Markdown content given a line diff (1MB); larger content is only compared by checksum
+pub const MAX_DIFF_CONTENT_SIZE: u64 = 1_048_576;
+
+// Chunks rehashed per content verification call (32MB at the 2MB chunk size)
+pub const MAX_VERIFY_CHUNKS: u32 = 16;
+
+// Maximum length of a rollback reason in chars
+pub const MAX_ROLLBACK_REASON_LENGTH: usize = 1_000;
+
+// Largest Markdown content rendered to an HTML preview (512KB)
+pub const MAX_MARKDOWN_PREVIEW_SIZE: u64 = 524_288;
+
+// MIME type of structured form content (JSON described by a form schema)
+pub const STRUCTURED_FORM_MIME_TYPE: &str = "application/vnd.permatrust.form+json";
+
+// MIME types tenants accept until they configure their own policy. `type/*` matches a whole type.
+pub const DEFAULT_ALLOWED_MIME_TYPES: &[&str] = &[
+    "text/*",
+    "image/*",
+    "application/pdf",
+    "application/json",
+    "application/zip",
+    "application/octet-stream",
+    "application/msword",
+    "application/vnd.ms-excel",
+    "application/vnd.ms-powerpoint",
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+    "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+    "application/vnd.oasis.opendocument.text",
+    "application/vnd.oasis.opendocument.spreadsheet",
+    STRUCTURED_FORM_MIME_TYPE,
+];
+
+// Maximum number of entries in a content type policy
+pub const MAX_ALLOWED_MIME_TYPES: usize = 100;
+
+// Legacy entries moved per batch of the storage migration
+pub const MIGRATION_BATCH_SIZE: usize = 500;
+
+// Chunk data moved or hashed per batch of the storage migration (32MB)
+pub const MIGRATION_BATCH_BYTES: u64 = 33_554_432;
@@ -0,0 +1,12 @@
+// Tokens outside this length range (in chars) are not indexed
+pub const MIN_TOKEN_LENGTH: usize = 2;
+pub const MAX_TOKEN_LENGTH: usize = 64;
+
+pub const DEFAULT_SEARCH_LIMIT: u32 = 20;
+pub const MAX_SEARCH_LIMIT: u32 = 100;
+
+// Markdown text indexed per batch of the storage migration (4MB)
+pub const SEARCH_MIGRATION_BATCH_BYTES: u64 = 4_194_304;
+
+// Characters of context shown around the first match
+pub const SNIPPET_CONTEXT_LENGTH: usize = 80;
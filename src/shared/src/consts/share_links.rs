@@ -0,0 +1,2 @@
+// Share links cannot be valid for longer than 90 days (in nanoseconds)
+pub const MAX_SHARE_LINK_TTL: u64 = 90 * 24 * 60 * 60 * 1_000_000_000;
@@ -0,0 +1,5 @@
+// Longest time users can be given to acknowledge a released revision
+pub const MAX_TRAINING_DUE_DAYS: u32 = 365;
+
+// Reason recorded on the signature of a training acknowledgement
+pub const TRAINING_ACKNOWLEDGEMENT_REASON: &str = "Read and understood";
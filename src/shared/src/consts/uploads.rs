@@ -0,0 +1,16 @@
+// Maximum declared size of a single upload in bytes (512MB)
+pub const MAX_UPLOAD_SIZE: u64 = 536_870_912;
+
+// Chunks hashed by a single upload call while checking an upload against its declared
+// checksum (16MB at the 2MB chunk size)
+pub const MAX_HASHED_CHUNKS_PER_CALL: u32 = 8;
+
+// Default time after which unattached upload sessions expire (24 hours, in nanoseconds)
+pub const UPLOAD_SESSION_TTL: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+// Bounds for the configurable upload session TTL (1 hour to 30 days, in nanoseconds)
+pub const MIN_UPLOAD_SESSION_TTL: u64 = 60 * 60 * 1_000_000_000;
+pub const MAX_UPLOAD_SESSION_TTL: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+
+// Interval of the timer that removes expired uploads and orphaned chunks (in seconds)
+pub const STORAGE_CLEANUP_INTERVAL_SECONDS: u64 = 60 * 60;
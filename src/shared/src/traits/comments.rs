@@ -0,0 +1,18 @@
+use crate::types::comments::Comment;
+use candid::{Decode, Encode};
+use ic_stable_structures::storable::Bound;
+use ic_stable_structures::Storable;
+use std::borrow::Cow;
+
+impl Storable for Comment {
+    fn to_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        Cow::Owned(Encode!(self).unwrap())
+    }
+
+    fn from_bytes(bytes: std::borrow::Cow<[u8]>) -> Self {
+        Decode!(bytes.as_ref(), Self).unwrap()
+    }
+
+    // Grows with the edit history
+    const BOUND: Bound = Bound::Unbounded;
+}
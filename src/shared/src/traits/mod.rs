@@ -1,10 +1,23 @@
 pub mod access_control;
+pub mod approvals;
+pub mod comments;
+pub mod document_links;
 pub mod documents;
+pub mod encryption;
+pub mod exports;
+pub mod folders;
 pub mod invites;
 pub mod logs;
+pub mod notifications;
 pub mod organization;
 pub mod pagination;
 pub mod projects;
+pub mod retention;
+pub mod reviews;
 pub mod revisions;
+pub mod search;
+pub mod share_links;
+pub mod training;
+pub mod uploads;
 pub mod users;
 pub mod workflows;
@@ -1,18 +1,20 @@
 use std::cmp::Ordering;
 
+use crate::types::comments::{Comment, CommentId, CommentStatus};
 use crate::types::documents::{Document, DocumentId};
 use crate::types::invites::{Invite, InviteId};
 use crate::types::logs::{CanisterOrigin, LogEntry, LogLevel};
 use crate::types::organization::Organization;
 use crate::types::pagination::{
-    DocumentFilterField, InviteFilterField, LogFilterField, OrganizationFilterField,
-    ProjectFilterField, RevisionFilterField, UserFilterField, WorkflowFilterField,
+    CommentFilterField, DocumentFilterField, InviteFilterField, LogFilterField,
+    OrganizationFilterField, ProjectFilterField, RevisionFilterField, UserFilterField,
+    WorkflowFilterField,
 };
 use crate::types::pagination::{
     FilterCriteria, FilterField, FilterOperator, SortCriteria, SortOrder,
 };
 use crate::types::projects::{Project, ProjectId};
-use crate::types::revisions::{Revision, RevisionId};
+use crate::types::revisions::{Revision, RevisionId, RevisionStatus};
 use crate::types::users::{User, UserId};
 use crate::types::workflows::{Workflow, WorkflowId};
 
@@ -160,6 +162,23 @@ impl Filterable for Revision {
                     _ => false,
                 }
             }
+            FilterField::Revision(RevisionFilterField::Status) => {
+                let criteria_status = match criteria.value.to_lowercase().as_str() {
+                    "draft" => Some(RevisionStatus::Draft),
+                    "inreview" | "in_review" => Some(RevisionStatus::InReview),
+                    "approved" => Some(RevisionStatus::Approved),
+                    "rejected" => Some(RevisionStatus::Rejected),
+                    "released" => Some(RevisionStatus::Released),
+                    "superseded" => Some(RevisionStatus::Superseded),
+                    "obsolete" => Some(RevisionStatus::Obsolete),
+                    _ => None,
+                };
+                let status = self.status.clone().unwrap_or(RevisionStatus::Draft);
+                match (criteria.operator.clone(), criteria_status) {
+                    (FilterOperator::Equals, Some(criteria_status)) => status == criteria_status,
+                    _ => false,
+                }
+            }
             _ => false,
         }
     }
@@ -493,3 +512,100 @@ impl Sortable for LogEntry {
         }
     }
 }
+
+impl Filterable for Comment {
+    fn matches(&self, criteria: &FilterCriteria) -> bool {
+        match &criteria.field {
+            FilterField::Comment(CommentFilterField::Id) => {
+                let criteria_value = criteria.value.parse::<CommentId>().unwrap_or(0);
+                match criteria.operator {
+                    FilterOperator::Equals => self.id == criteria_value,
+                    _ => false,
+                }
+            }
+            FilterField::Comment(CommentFilterField::DocumentId) => {
+                let criteria_value = criteria.value.parse::<DocumentId>().unwrap_or(0);
+                match criteria.operator {
+                    FilterOperator::Equals => self.document_id == criteria_value,
+                    _ => false,
+                }
+            }
+            FilterField::Comment(CommentFilterField::RevisionId) => {
+                let criteria_value = criteria.value.parse::<RevisionId>().unwrap_or(0);
+                match criteria.operator {
+                    FilterOperator::Equals => self.revision_id == criteria_value,
+                    _ => false,
+                }
+            }
+            FilterField::Comment(CommentFilterField::ParentId) => {
+                // An empty value matches thread roots
+                let criteria_value = criteria.value.parse::<CommentId>().ok();
+                match criteria.operator {
+                    FilterOperator::Equals => self.parent_id == criteria_value,
+                    _ => false,
+                }
+            }
+            FilterField::Comment(CommentFilterField::Status) => {
+                let criteria_status = match criteria.value.to_lowercase().as_str() {
+                    "open" => Some(CommentStatus::Open),
+                    "resolved" => Some(CommentStatus::Resolved),
+                    _ => None,
+                };
+                match (criteria.operator.clone(), criteria_status) {
+                    (FilterOperator::Equals, Some(status)) => self.status == status,
+                    _ => false,
+                }
+            }
+            FilterField::Comment(CommentFilterField::CreatedBy) => {
+                let parsed_value = criteria.value.parse::<UserId>().ok();
+                match (criteria.operator.clone(), parsed_value) {
+                    (FilterOperator::Equals, Some(value)) => self.created_by == value,
+                    _ => false,
+                }
+            }
+            FilterField::Comment(CommentFilterField::CreatedAt) => {
+                let criteria_value = criteria.value.parse::<u64>().unwrap_or(0);
+                match criteria.operator {
+                    FilterOperator::GreaterThan => self.created_at > criteria_value,
+                    FilterOperator::LessThan => self.created_at < criteria_value,
+                    FilterOperator::Equals => self.created_at == criteria_value,
+                    _ => false,
+                }
+            }
+            FilterField::Comment(CommentFilterField::Mentions) => {
+                let parsed_value = criteria.value.parse::<UserId>().ok();
+                match (criteria.operator.clone(), parsed_value) {
+                    (FilterOperator::Contains, Some(value))
+                    | (FilterOperator::Equals, Some(value)) => self.mentions.contains(&value),
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Sortable for Comment {
+    fn compare(&self, other: &Self, criteria: &SortCriteria) -> Ordering {
+        let ordering = match &criteria.field {
+            FilterField::Comment(CommentFilterField::Id) => self.id.cmp(&other.id),
+            FilterField::Comment(CommentFilterField::DocumentId) => {
+                self.document_id.cmp(&other.document_id)
+            }
+            FilterField::Comment(CommentFilterField::RevisionId) => {
+                self.revision_id.cmp(&other.revision_id)
+            }
+            FilterField::Comment(CommentFilterField::CreatedBy) => {
+                self.created_by.cmp(&other.created_by)
+            }
+            FilterField::Comment(CommentFilterField::CreatedAt) => {
+                self.created_at.cmp(&other.created_at)
+            }
+            _ => Ordering::Equal,
+        };
+        match criteria.order {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        }
+    }
+}
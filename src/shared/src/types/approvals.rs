@@ -0,0 +1,192 @@
+use candid::{CandidType, Principal};
+use serde::Deserialize;
+
+use crate::types::access_control::RoleId;
+use crate::types::errors::AppError;
+use crate::types::projects::ProjectId;
+use crate::types::revisions::{RevisionId, RevisionStatus};
+use crate::types::users::UserId;
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum SignatureMeaning {
+    Authored,
+    Reviewed,
+    Approved,
+    Rejected,
+    /// Read and understood, signed when acknowledging training on a released revision
+    Acknowledged,
+}
+
+/// An electronic signature on a revision. Signatures are append-only and keyed by
+/// revision and sequence number.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct RevisionSignature {
+    pub revision_id: RevisionId,
+    pub sequence: u32,
+    pub signer: UserId,
+    /// Full name of the signer at the time of signing
+    pub signer_name: String,
+    pub principal: Principal,
+    /// Roles the signer held in the project at the time of signing
+    pub role_ids: Vec<RoleId>,
+    pub meaning: SignatureMeaning,
+    pub reason: String,
+    /// SHA-256 (hex) over the revision's content checksums that was signed
+    pub content_hash: String,
+    /// When the signing challenge used for this signature was issued
+    pub challenge_issued_at: u64,
+    pub signed_at: u64,
+    /// Status of the revision after the signature was applied
+    pub resulting_status: RevisionStatus,
+}
+
+/// A single-use nonce the signer must present, together with the content hash, in
+/// the call that records the signature.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SigningChallenge {
+    pub nonce: String,
+    pub revision_id: RevisionId,
+    pub principal: Principal,
+    pub meaning: SignatureMeaning,
+    pub content_hash: String,
+    pub issued_at: u64,
+    pub expires_at: u64,
+}
+
+/// Proof of a fresh signing call: the challenge nonce and the content hash the
+/// signer agreed to.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SignatureAttestation {
+    pub nonce: String,
+    pub content_hash: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct VerifiedSignature {
+    pub signature: RevisionSignature,
+    /// Whether the signed hash matches the revision's current content
+    pub content_hash_matches: bool,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SignatureVerification {
+    pub revision_id: RevisionId,
+    pub content_hash: String,
+    pub signatures: Vec<VerifiedSignature>,
+    /// True when every signature matches the content and the sequence has no gaps
+    pub is_valid: bool,
+}
+
+/// Approvals needed before a revision of the project becomes `Approved`: at least
+/// `quorum` distinct approvers, and one approver holding each of `required_roles`.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ApprovalPolicy {
+    pub project_id: ProjectId,
+    pub required_roles: Vec<RoleId>,
+    pub quorum: u32,
+    /// None while the project uses the default policy of a single approval
+    pub updated_at: Option<u64>,
+    pub updated_by: Option<UserId>,
+}
+
+// Inputs
+
+#[derive(CandidType, Deserialize)]
+pub struct SubmitRevisionForReviewInput {
+    pub revision_id: RevisionId,
+    pub reason: String,
+    pub attestation: SignatureAttestation,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ApproveRevisionInput {
+    pub revision_id: RevisionId,
+    /// `Reviewed` or `Approved`; only `Approved` signatures count towards the policy
+    pub meaning: SignatureMeaning,
+    pub reason: String,
+    pub attestation: SignatureAttestation,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct RejectRevisionInput {
+    pub revision_id: RevisionId,
+    pub reason: String,
+    pub attestation: SignatureAttestation,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ReleaseRevisionInput {
+    pub revision_id: RevisionId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ObsoleteRevisionInput {
+    pub revision_id: RevisionId,
+    pub reason: String,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct SetApprovalPolicyInput {
+    pub project_id: ProjectId,
+    pub required_roles: Vec<RoleId>,
+    pub quorum: u32,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct GetApprovalPolicyInput {
+    pub project_id: ProjectId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ListRevisionSignaturesInput {
+    pub revision_id: RevisionId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct RequestSigningChallengeInput {
+    pub revision_id: RevisionId,
+    pub meaning: SignatureMeaning,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct VerifySignaturesInput {
+    pub revision_id: RevisionId,
+}
+
+// Results
+
+#[derive(CandidType, Deserialize)]
+pub enum RevisionStatusResult {
+    Ok(RevisionStatus),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum SetApprovalPolicyResult {
+    Ok,
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum GetApprovalPolicyResult {
+    Ok(ApprovalPolicy),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum ListRevisionSignaturesResult {
+    Ok(Vec<RevisionSignature>),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum RequestSigningChallengeResult {
+    Ok(SigningChallenge),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum VerifySignaturesResult {
+    Ok(SignatureVerification),
+    Err(AppError),
+}
@@ -0,0 +1,120 @@
+use candid::CandidType;
+use serde::Deserialize;
+
+use crate::types::documents::DocumentId;
+use crate::types::errors::AppError;
+use crate::types::pagination::{PaginationInput, PaginationMetadata};
+use crate::types::projects::ProjectId;
+use crate::types::revisions::{RevisionContentId, RevisionId};
+use crate::types::users::UserId;
+
+pub type CommentId = u64;
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum CommentStatus {
+    Open,
+    Resolved,
+}
+
+/// Character offsets within the text of the anchored content item, `end` exclusive.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct CommentTextRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct CommentAnchor {
+    pub content_id: RevisionContentId,
+    pub range: Option<CommentTextRange>,
+}
+
+/// A previous version of a comment body, kept whenever the comment is edited.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct CommentEdit {
+    pub body: String,
+    pub mentions: Vec<UserId>,
+    pub edited_at: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct Comment {
+    pub id: CommentId,
+    pub document_id: DocumentId,
+    pub revision_id: RevisionId,
+    pub project_id: ProjectId,
+    /// None for the comment starting a thread, otherwise the thread's root comment
+    pub parent_id: Option<CommentId>,
+    pub anchor: Option<CommentAnchor>,
+    pub body: String,
+    pub mentions: Vec<UserId>,
+    /// Only meaningful on thread roots; replies follow the status of their thread
+    pub status: CommentStatus,
+    pub created_at: u64,
+    pub created_by: UserId,
+    pub updated_at: Option<u64>,
+    pub resolved_at: Option<u64>,
+    pub resolved_by: Option<UserId>,
+    pub edit_history: Vec<CommentEdit>,
+}
+
+// Inputs
+
+#[derive(CandidType, Deserialize)]
+pub struct CreateCommentInput {
+    pub revision_id: RevisionId,
+    /// Reply to an existing comment; replies are attached to the root of its thread
+    pub parent_id: Option<CommentId>,
+    pub anchor: Option<CommentAnchor>,
+    pub body: String,
+    pub mentions: Vec<UserId>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct UpdateCommentInput {
+    pub id: CommentId,
+    pub body: String,
+    pub mentions: Vec<UserId>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct CommentIdInput {
+    pub id: CommentId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ListCommentsInput {
+    pub pagination: PaginationInput,
+}
+
+// Results
+
+#[derive(CandidType, Deserialize)]
+pub enum CreateCommentResult {
+    Ok(CommentId),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum UpdateCommentResult {
+    Ok,
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum ResolveCommentThreadResult {
+    Ok,
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum ReopenCommentThreadResult {
+    Ok,
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum ListCommentsResult {
+    Ok((Vec<Comment>, PaginationMetadata)),
+    Err(AppError),
+}
@@ -0,0 +1,106 @@
+use candid::CandidType;
+use serde::Deserialize;
+
+use crate::types::documents::{Document, DocumentId};
+use crate::types::errors::AppError;
+use crate::types::projects::ProjectId;
+use crate::types::revisions::RevisionId;
+use crate::types::users::UserId;
+
+pub type DocumentLinkId = u64;
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum DocumentLinkType {
+    References,
+    Supersedes,
+    Implements,
+    DerivedFrom,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum DocumentLinkDirection {
+    Outgoing,
+    Incoming,
+    Both,
+}
+
+/// A typed, directed link from `source_document_id` to `target_document_id`,
+/// e.g. an SOP (source) that `References` a form (target).
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DocumentLink {
+    pub id: DocumentLinkId,
+    pub source_document_id: DocumentId,
+    pub target_document_id: DocumentId,
+    pub link_type: DocumentLinkType,
+    pub project_id: ProjectId,
+    pub created_at: u64,
+    pub created_by: UserId,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DocumentImpactItem {
+    pub document: Document,
+    pub link: DocumentLink,
+}
+
+/// Documents linking to `document_id`, which need to be revisited when a new
+/// revision of it is released.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DocumentImpactReport {
+    pub document_id: DocumentId,
+    pub version: u8,
+    pub latest_revision_id: Option<RevisionId>,
+    pub impacted: Vec<DocumentImpactItem>,
+}
+
+// Inputs
+
+#[derive(CandidType, Deserialize)]
+pub struct CreateDocumentLinkInput {
+    pub source_document_id: DocumentId,
+    pub target_document_id: DocumentId,
+    pub link_type: DocumentLinkType,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct DeleteDocumentLinkInput {
+    pub id: DocumentLinkId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ListDocumentLinksInput {
+    pub document_id: DocumentId,
+    pub direction: DocumentLinkDirection,
+    pub link_type: Option<DocumentLinkType>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct GetDocumentImpactInput {
+    pub document_id: DocumentId,
+}
+
+// Results
+
+#[derive(CandidType, Deserialize)]
+pub enum CreateDocumentLinkResult {
+    Ok(DocumentLinkId),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum DeleteDocumentLinkResult {
+    Ok,
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum ListDocumentLinksResult {
+    Ok(Vec<DocumentLink>),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum GetDocumentImpactResult {
+    Ok(DocumentImpactReport),
+    Err(AppError),
+}
@@ -1,4 +1,6 @@
 use crate::types::errors::AppError;
+use crate::types::folders::FolderId;
+use crate::types::imports::ImportOrigin;
 use candid::CandidType;
 use serde::Deserialize;
 
@@ -19,6 +21,14 @@ pub struct Document {
     pub created_by: UserId,
     pub version: u8,
     pub project_id: ProjectId,
+    pub folder_id: Option<FolderId>,
+    /// Content is encrypted by clients with per-document vetKD keys. None for documents
+    /// created before encryption was available, treated as false.
+    pub encrypted: Option<bool>,
+    /// Set for documents created by an import
+    pub imported: Option<ImportOrigin>,
+    /// Selects the retention rule that applies to the document's revisions
+    pub document_type: Option<String>,
 }
 
 // Inputs
@@ -27,11 +37,19 @@ pub struct Document {
 pub struct CreateDocumentInput {
     pub project_id: ProjectId,
     pub title: String,
+    pub folder_id: Option<FolderId>,
+    /// Create an encrypted document; cannot be changed later
+    pub encrypted: Option<bool>,
+    pub document_type: Option<String>,
 }
 
 #[derive(CandidType, Deserialize)]
 pub struct ListDocumentsInput {
     pub pagination: PaginationInput,
+    /// Only list documents in this folder
+    pub folder_id: Option<FolderId>,
+    /// Also include documents in subfolders of `folder_id`
+    pub recursive: Option<bool>,
 }
 
 #[derive(CandidType, Deserialize)]
@@ -39,6 +57,13 @@ pub struct DocumentIdInput {
     pub id: DocumentId,
 }
 
+#[derive(CandidType, Deserialize)]
+pub struct SetDocumentTypeInput {
+    pub document_id: DocumentId,
+    /// None clears the type
+    pub document_type: Option<String>,
+}
+
 // Results
 
 #[derive(CandidType, Deserialize)]
@@ -79,3 +104,9 @@ pub enum ListDocumentsResult {
     Ok((Vec<Document>, PaginationMetadata)),
     Err(AppError),
 }
+
+#[derive(CandidType, Deserialize)]
+pub enum SetDocumentTypeResult {
+    Ok,
+    Err(AppError),
+}
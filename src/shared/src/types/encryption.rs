@@ -0,0 +1,69 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+use crate::types::documents::DocumentId;
+use crate::types::errors::AppError;
+use crate::types::users::UserId;
+
+/// Marks content as encrypted by the client with the key of an encrypted document.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ContentEncryption {
+    /// Epoch of the document key the content was encrypted with
+    pub key_epoch: u32,
+}
+
+/// Key state of an encrypted document. Keys are derived with vetKD from the document ID
+/// and the epoch; rotating bumps the epoch so new content uses a key former readers
+/// never received.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DocumentKeyState {
+    pub document_id: DocumentId,
+    pub epoch: u32,
+    pub rotated_at: Option<u64>,
+    /// Users a key of the current epoch has been derived for
+    pub key_holders: Vec<UserId>,
+}
+
+/// A document key encrypted for the caller's transport key.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DerivedDocumentKey {
+    pub document_id: DocumentId,
+    pub key_epoch: u32,
+    pub encrypted_key: Vec<u8>,
+}
+
+// Inputs
+
+#[derive(CandidType, Deserialize)]
+pub struct DeriveDocumentKeyInput {
+    pub document_id: DocumentId,
+    /// Defaults to the current epoch, older epochs decrypt earlier content
+    pub key_epoch: Option<u32>,
+    /// Public key of the caller's ephemeral transport key pair
+    pub transport_public_key: Vec<u8>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct RotateDocumentKeyInput {
+    pub document_id: DocumentId,
+}
+
+// Results
+
+#[derive(CandidType, Deserialize)]
+pub enum GetEncryptionPublicKeyResult {
+    Ok(Vec<u8>),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum DeriveDocumentKeyResult {
+    Ok(DerivedDocumentKey),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum RotateDocumentKeyResult {
+    Ok(u32),
+    Err(AppError),
+}
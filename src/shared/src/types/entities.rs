@@ -11,4 +11,5 @@ pub enum Entity {
     Project,
     Workflow,
     LogEntry,
+    Comment,
 }
@@ -31,6 +31,7 @@ pub enum AppError {
     InvalidPageNumber(String),
     InvalidPageSize(String),
     InvalidStateTransition(String),
+    QuotaExceeded(String),
     SpawnCanister(String),
     CanisterUpgradeFailed(String),
     StoreWasmModuleFailed(String),
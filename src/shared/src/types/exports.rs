@@ -0,0 +1,89 @@
+use candid::CandidType;
+use serde::Deserialize;
+
+use crate::types::documents::DocumentId;
+use crate::types::errors::AppError;
+use crate::types::projects::ProjectId;
+use crate::types::revisions::RevisionContentId;
+use crate::types::users::UserId;
+
+pub type ExportId = u64;
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum ExportScope {
+    Document(DocumentId),
+    /// Every document of the project the caller may export
+    Project(ProjectId),
+}
+
+/// A file of an export archive.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ExportEntry {
+    pub path: String,
+    /// None for the manifest
+    pub content_id: Option<RevisionContentId>,
+    /// Path of an earlier entry with the same content, stored as a hard link
+    pub link_target: Option<String>,
+    pub size: u64,
+    /// Modification time in seconds
+    pub mtime: u64,
+    /// Offset of the entry's header in the archive
+    pub offset: u64,
+}
+
+/// A tar archive laid out by `begin_export`. The archive itself is not stored, chunks
+/// are assembled from the entries when they are read.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ExportSession {
+    pub id: ExportId,
+    pub scope: ExportScope,
+    pub created_by: UserId,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub entries: Vec<ExportEntry>,
+    /// JSON manifest, the first entry of the archive
+    pub manifest: Vec<u8>,
+    pub total_size: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ExportSummary {
+    pub export_id: ExportId,
+    pub scope: ExportScope,
+    pub document_count: u32,
+    pub file_count: u32,
+    /// Size of the tar archive in bytes
+    pub total_size: u64,
+    pub chunk_size: u64,
+    pub total_chunks: u32,
+    /// SHA-256 (hex) of `manifest.json`, which lists the SHA-256 of every file
+    pub manifest_sha256: String,
+    pub expires_at: u64,
+}
+
+// Inputs
+
+#[derive(CandidType, Deserialize)]
+pub struct BeginExportInput {
+    pub scope: ExportScope,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct GetExportChunkInput {
+    pub export_id: ExportId,
+    pub chunk_id: u32,
+}
+
+// Results
+
+#[derive(CandidType, Deserialize)]
+pub enum BeginExportResult {
+    Ok(ExportSummary),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum GetExportChunkResult {
+    Ok(Vec<u8>),
+    Err(AppError),
+}
@@ -0,0 +1,121 @@
+use candid::CandidType;
+use serde::Deserialize;
+
+use crate::types::access_control::{Permission, RoleId};
+use crate::types::documents::DocumentId;
+use crate::types::errors::AppError;
+use crate::types::projects::ProjectId;
+use crate::types::users::UserId;
+
+pub type FolderId = u64;
+
+/// Extra permissions granted to holders of `role_id` on everything inside a folder.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct FolderRoleGrant {
+    pub role_id: RoleId,
+    pub permissions: Vec<Permission>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct Folder {
+    pub id: FolderId,
+    pub project_id: ProjectId,
+    /// None for folders at the root of the project
+    pub parent_id: Option<FolderId>,
+    pub name: String,
+    pub created_at: u64,
+    pub created_by: UserId,
+    pub updated_at: Option<u64>,
+    /// When true, grants of the parent folders also apply to this folder
+    pub inherit_permissions: bool,
+    pub grants: Vec<FolderRoleGrant>,
+}
+
+// Inputs
+
+#[derive(CandidType, Deserialize)]
+pub struct CreateFolderInput {
+    pub project_id: ProjectId,
+    pub parent_id: Option<FolderId>,
+    pub name: String,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct RenameFolderInput {
+    pub id: FolderId,
+    pub name: String,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct MoveFolderInput {
+    pub id: FolderId,
+    pub parent_id: Option<FolderId>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct DeleteFolderInput {
+    pub id: FolderId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct SetFolderPermissionsInput {
+    pub id: FolderId,
+    pub inherit_permissions: bool,
+    pub grants: Vec<FolderRoleGrant>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ListFoldersInput {
+    pub project_id: ProjectId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct MoveDocumentInput {
+    pub document_id: DocumentId,
+    /// None moves the document to the root of its project
+    pub folder_id: Option<FolderId>,
+}
+
+// Results
+
+#[derive(CandidType, Deserialize)]
+pub enum CreateFolderResult {
+    Ok(FolderId),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum RenameFolderResult {
+    Ok,
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum MoveFolderResult {
+    Ok,
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum DeleteFolderResult {
+    Ok,
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum SetFolderPermissionsResult {
+    Ok,
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum ListFoldersResult {
+    Ok(Vec<Folder>),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum MoveDocumentResult {
+    Ok,
+    Err(AppError),
+}
@@ -0,0 +1,55 @@
+use candid::{define_function, CandidType};
+use serde::Deserialize;
+
+use crate::types::revisions::RevisionContentId;
+
+pub type HeaderField = (String, String);
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct HttpRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<HeaderField>,
+    #[serde(with = "serde_bytes")]
+    pub body: Vec<u8>,
+    pub certificate_version: Option<u16>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct HttpResponse {
+    pub status_code: u16,
+    pub headers: Vec<HeaderField>,
+    #[serde(with = "serde_bytes")]
+    pub body: Vec<u8>,
+    pub streaming_strategy: Option<StreamingStrategy>,
+    pub upgrade: Option<bool>,
+}
+
+/// Position of the next body part of a streamed content download.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StreamingCallbackToken {
+    pub content_id: RevisionContentId,
+    /// Share token the download was authorized with, if any
+    pub share_token: Option<String>,
+    /// Byte offset of the next body part
+    pub offset: u64,
+    /// Exclusive end of the requested byte range
+    pub end: u64,
+}
+
+define_function!(pub StreamingCallback : (StreamingCallbackToken) -> (StreamingCallbackHttpResponse) query);
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum StreamingStrategy {
+    Callback {
+        callback: StreamingCallback,
+        token: StreamingCallbackToken,
+    },
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StreamingCallbackHttpResponse {
+    #[serde(with = "serde_bytes")]
+    pub body: Vec<u8>,
+    pub token: Option<StreamingCallbackToken>,
+}
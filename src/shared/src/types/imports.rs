@@ -0,0 +1,129 @@
+use candid::CandidType;
+use serde::Deserialize;
+
+use crate::types::documents::DocumentId;
+use crate::types::errors::AppError;
+use crate::types::folders::FolderId;
+use crate::types::projects::ProjectId;
+use crate::types::revisions::{RevisionContentId, RevisionContentType, RevisionId};
+use crate::types::users::UserId;
+
+/// Where an imported document or revision came from. Authorship in the source system
+/// is kept as metadata, the importing user is recorded as the creator.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ImportOrigin {
+    /// Identifier in the source system
+    pub source_id: Option<String>,
+    pub original_created_at: Option<u64>,
+    pub original_created_by: Option<String>,
+    pub imported_at: u64,
+    pub imported_by: UserId,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum ImportContent {
+    Direct {
+        file_name: Option<String>,
+        content_type: RevisionContentType,
+        mime_type: String,
+        bytes: Vec<u8>,
+    },
+    /// A finished upload, referenced by the content ID `finish_upload` returned. The
+    /// same upload may be referenced by several revisions.
+    Upload {
+        content_id: RevisionContentId,
+        /// Checked against the checksum the upload was declared with
+        sha256: Option<String>,
+    },
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ImportRevision {
+    pub source_id: Option<String>,
+    pub original_created_at: Option<u64>,
+    pub original_created_by: Option<String>,
+    pub contents: Vec<ImportContent>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ImportDocument {
+    pub source_id: Option<String>,
+    pub title: String,
+    /// Defaults to the folder of the import
+    pub folder_id: Option<FolderId>,
+    pub document_type: Option<String>,
+    pub original_created_at: Option<u64>,
+    pub original_created_by: Option<String>,
+    /// Imported in order, oldest first
+    pub revisions: Vec<ImportRevision>,
+}
+
+/// A file of an export archive, uploaded with an upload session.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ImportFile {
+    /// Path of the file in the archive
+    pub path: String,
+    pub content_id: RevisionContentId,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum ImportSource {
+    Documents(Vec<ImportDocument>),
+    /// `manifest.json` of an export archive. Files with the same content in the archive
+    /// (hard links) only need to be uploaded once.
+    ExportManifest {
+        manifest: String,
+        files: Vec<ImportFile>,
+    },
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct ImportError {
+    pub revision_index: Option<u32>,
+    pub content_index: Option<u32>,
+    pub error: AppError,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct ImportDocumentReport {
+    pub index: u32,
+    pub source_id: Option<String>,
+    pub title: String,
+    /// Set once the document has been created
+    pub document_id: Option<DocumentId>,
+    pub revision_ids: Vec<RevisionId>,
+    pub errors: Vec<ImportError>,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    /// False when validation failed; nothing is imported unless every item is valid
+    pub imported: bool,
+    pub document_count: u32,
+    pub revision_count: u32,
+    pub content_count: u32,
+    /// Bytes of direct content stored by the import, uploads are accounted for already
+    pub direct_bytes: u64,
+    pub documents: Vec<ImportDocumentReport>,
+}
+
+// Inputs
+
+#[derive(CandidType, Deserialize)]
+pub struct ImportDocumentsInput {
+    pub project_id: ProjectId,
+    /// Folder for documents that do not name one
+    pub folder_id: Option<FolderId>,
+    pub source: ImportSource,
+    /// Only validate and report, without creating anything
+    pub dry_run: bool,
+}
+
+// Results
+
+#[derive(CandidType, Deserialize)]
+pub enum ImportDocumentsResult {
+    Ok(ImportReport),
+    Err(AppError),
+}
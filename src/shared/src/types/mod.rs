@@ -1,13 +1,29 @@
 pub mod access_control;
+pub mod approvals;
+pub mod comments;
+pub mod document_links;
 pub mod documents;
+pub mod encryption;
 pub mod entities;
 pub mod errors;
+pub mod exports;
+pub mod folders;
+pub mod http;
+pub mod imports;
 pub mod invites;
 pub mod logs;
 pub mod management;
+pub mod notifications;
 pub mod organization;
 pub mod pagination;
 pub mod projects;
+pub mod quotas;
+pub mod retention;
+pub mod reviews;
 pub mod revisions;
+pub mod search;
+pub mod share_links;
+pub mod training;
+pub mod uploads;
 pub mod users;
 pub mod workflows;
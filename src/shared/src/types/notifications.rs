@@ -0,0 +1,132 @@
+use candid::CandidType;
+use serde::Deserialize;
+
+use crate::types::comments::CommentId;
+use crate::types::documents::DocumentId;
+use crate::types::errors::AppError;
+use crate::types::projects::ProjectId;
+use crate::types::revisions::RevisionId;
+use crate::types::users::UserId;
+use crate::types::workflows::{EventId, StateId, WorkflowId};
+
+/// Per-user, increasing with every notification delivered to the user
+pub type NotificationId = u64;
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum SubscriptionTarget {
+    Document(DocumentId),
+    /// Covers every document and workflow of the project
+    Project(ProjectId),
+    Workflow(WorkflowId),
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct Subscription {
+    pub user_id: UserId,
+    pub target: SubscriptionTarget,
+    pub created_at: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum NotificationEvent {
+    RevisionCreated {
+        document_id: DocumentId,
+        revision_id: RevisionId,
+        version: u8,
+    },
+    ReviewRequested {
+        document_id: DocumentId,
+        revision_id: RevisionId,
+        version: u8,
+    },
+    WorkflowTransitioned {
+        workflow_id: WorkflowId,
+        event_id: EventId,
+        from_state: StateId,
+        to_state: StateId,
+    },
+    CommentAdded {
+        document_id: DocumentId,
+        revision_id: RevisionId,
+        comment_id: CommentId,
+        /// The user was mentioned in the comment
+        mentioned: bool,
+    },
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct Notification {
+    pub id: NotificationId,
+    pub user_id: UserId,
+    pub project_id: ProjectId,
+    pub event: NotificationEvent,
+    /// User whose action raised the notification
+    pub actor: Option<UserId>,
+    pub created_at: u64,
+    pub read_at: Option<u64>,
+}
+
+// Inputs
+
+#[derive(CandidType, Deserialize)]
+pub struct SubscribeInput {
+    pub target: SubscriptionTarget,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct UnsubscribeInput {
+    pub target: SubscriptionTarget,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ListNotificationsInput {
+    pub unread_only: bool,
+    /// Only list notifications older than this one
+    pub before: Option<NotificationId>,
+    pub limit: Option<u32>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct MarkNotificationsReadInput {
+    /// No ids marks every notification of the user as read
+    pub ids: Option<Vec<NotificationId>>,
+}
+
+// Results
+
+#[derive(CandidType, Deserialize)]
+pub enum SubscribeResult {
+    Ok,
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum UnsubscribeResult {
+    Ok,
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum ListSubscriptionsResult {
+    Ok(Vec<Subscription>),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum ListNotificationsResult {
+    Ok(Vec<Notification>),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum MarkNotificationsReadResult {
+    /// Number of notifications newly marked as read
+    Ok(u32),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum GetUnreadNotificationCountResult {
+    Ok(u32),
+    Err(AppError),
+}
@@ -28,6 +28,7 @@ pub enum RevisionFilterField {
     Version,
     DocumentId,
     CreatedAt,
+    Status,
 }
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
@@ -78,6 +79,18 @@ pub enum LogFilterField {
     Message,
 }
 
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub enum CommentFilterField {
+    Id,
+    DocumentId,
+    RevisionId,
+    ParentId,
+    Status,
+    CreatedBy,
+    CreatedAt,
+    Mentions,
+}
+
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub enum FilterField {
     User(UserFilterField),
@@ -89,6 +102,7 @@ pub enum FilterField {
     Workflow(WorkflowFilterField),
     Role(RoleFilterField),
     LogEntry(LogFilterField),
+    Comment(CommentFilterField),
 }
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
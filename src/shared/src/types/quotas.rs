@@ -0,0 +1,46 @@
+use candid::CandidType;
+use serde::Deserialize;
+
+use crate::types::errors::AppError;
+use crate::types::projects::ProjectId;
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ProjectStorageUsage {
+    pub project_id: ProjectId,
+    /// Bytes of content first stored in a revision of the project
+    pub bytes: u64,
+}
+
+/// Storage used by a tenant against its quota. Content referenced by several revisions
+/// is accounted once, to the project it was first stored in.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StorageUsage {
+    pub quota: u64,
+    pub used_bytes: u64,
+    /// Declared size of uploads that have not been attached to a revision yet
+    pub pending_upload_bytes: u64,
+    pub available_bytes: u64,
+    pub projects: Vec<ProjectStorageUsage>,
+}
+
+// Inputs
+
+#[derive(CandidType, Deserialize)]
+pub struct SetStorageQuotaInput {
+    /// Quota in bytes
+    pub quota: u64,
+}
+
+// Results
+
+#[derive(CandidType, Deserialize)]
+pub enum SetStorageQuotaResult {
+    Ok,
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum GetStorageUsageResult {
+    Ok(StorageUsage),
+    Err(AppError),
+}
@@ -0,0 +1,132 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+use crate::types::documents::DocumentId;
+use crate::types::errors::AppError;
+use crate::types::projects::ProjectId;
+use crate::types::revisions::{RevisionContentId, RevisionId};
+use crate::types::users::UserId;
+
+pub type PurgeRecordId = u64;
+
+/// How long revisions of a document type are kept after they were superseded or made
+/// obsolete.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct RetentionRule {
+    pub document_type: String,
+    pub retention_years: u32,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct RetentionPolicy {
+    pub rules: Vec<RetentionRule>,
+    /// Applies to documents without a matching rule; None keeps them indefinitely
+    pub default_retention_years: Option<u32>,
+    pub updated_at: Option<u64>,
+    pub updated_by: Option<UserId>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum LegalHoldTarget {
+    Document(DocumentId),
+    /// Every document of the project
+    Project(ProjectId),
+}
+
+/// Blocks deletion and purging of the documents it covers while it is in place.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct LegalHold {
+    pub target: LegalHoldTarget,
+    pub reason: String,
+    pub placed_at: u64,
+    pub placed_by: UserId,
+}
+
+/// Set on content whose data was purged; checksum and size of the data are kept.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ContentPurge {
+    pub purged_at: u64,
+    pub sha256: String,
+    pub size: u64,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct PurgedContent {
+    pub content_id: RevisionContentId,
+    pub sha256: String,
+    pub size: u64,
+    /// False when the data was purged before, or is kept because a retained revision
+    /// still contains it
+    pub removed: bool,
+}
+
+/// Audit record of a revision whose content was purged under the retention policy.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct PurgeRecord {
+    pub id: PurgeRecordId,
+    pub document_id: DocumentId,
+    pub project_id: ProjectId,
+    pub revision_id: RevisionId,
+    pub version: u8,
+    pub document_type: Option<String>,
+    pub retention_years: u32,
+    pub retired_at: u64,
+    pub purged_at: u64,
+    /// Revision hash over the checksums of its contents, as before the purge
+    pub revision_hash: String,
+    pub contents: Vec<PurgedContent>,
+    pub bytes_removed: u64,
+}
+
+// Inputs
+
+#[derive(CandidType, Deserialize)]
+pub struct SetRetentionPolicyInput {
+    pub rules: Vec<RetentionRule>,
+    pub default_retention_years: Option<u32>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct SetLegalHoldInput {
+    pub target: LegalHoldTarget,
+    /// False releases the hold
+    pub hold: bool,
+    pub reason: String,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ListPurgeRecordsInput {
+    pub document_id: Option<DocumentId>,
+}
+
+// Results
+
+#[derive(CandidType, Deserialize)]
+pub enum GetRetentionPolicyResult {
+    Ok(RetentionPolicy),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum SetRetentionPolicyResult {
+    Ok(RetentionPolicy),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum SetLegalHoldResult {
+    Ok,
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum ListLegalHoldsResult {
+    Ok(Vec<LegalHold>),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum ListPurgeRecordsResult {
+    Ok(Vec<PurgeRecord>),
+    Err(AppError),
+}
@@ -0,0 +1,81 @@
+use candid::CandidType;
+use serde::Deserialize;
+
+use crate::types::documents::DocumentId;
+use crate::types::errors::AppError;
+use crate::types::projects::ProjectId;
+use crate::types::revisions::RevisionId;
+use crate::types::users::UserId;
+
+/// Periodic review of a document, due a number of months after its released revision
+/// was released.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ReviewSchedule {
+    pub document_id: DocumentId,
+    pub interval_months: u32,
+    /// User responsible for the review
+    pub owner: UserId,
+    pub updated_at: u64,
+    pub updated_by: UserId,
+    /// Set by the review timer once the review is overdue, cleared when a new revision
+    /// is released
+    pub overdue_since: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DocumentReview {
+    pub document_id: DocumentId,
+    pub project_id: ProjectId,
+    pub title: String,
+    pub interval_months: u32,
+    pub owner: UserId,
+    /// None while the document has no released revision, which needs no review
+    pub released_revision_id: Option<RevisionId>,
+    pub released_at: Option<u64>,
+    pub next_review_due: Option<u64>,
+    pub overdue_since: Option<u64>,
+}
+
+// Inputs
+
+#[derive(CandidType, Deserialize)]
+pub struct SetReviewScheduleInput {
+    pub document_id: DocumentId,
+    /// None removes the schedule
+    pub interval_months: Option<u32>,
+    /// Defaults to the current owner, or the document's creator for a new schedule
+    pub owner: Option<UserId>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct GetDocumentReviewInput {
+    pub document_id: DocumentId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ListDueReviewsInput {
+    pub project_id: Option<ProjectId>,
+    pub owner: Option<UserId>,
+    /// Also list reviews due within this many days; only overdue reviews when None
+    pub due_within_days: Option<u32>,
+}
+
+// Results
+
+#[derive(CandidType, Deserialize)]
+pub enum SetReviewScheduleResult {
+    Ok(Option<DocumentReview>),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum GetDocumentReviewResult {
+    Ok(Option<DocumentReview>),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum ListDueReviewsResult {
+    Ok(Vec<DocumentReview>),
+    Err(AppError),
+}
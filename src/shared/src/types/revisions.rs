@@ -2,13 +2,19 @@ use candid::CandidType;
 use serde::{Deserialize, Serialize};
 
 use crate::types::documents::DocumentId;
+use crate::types::encryption::ContentEncryption;
 use crate::types::errors::AppError;
+use crate::types::imports::ImportOrigin;
 use crate::types::pagination::{PaginationInput, PaginationMetadata};
 use crate::types::projects::ProjectId;
+use crate::types::retention::ContentPurge;
 use crate::types::users::UserId;
+use crate::utils::sha256::Sha256State;
 
 pub type RevisionId = u64;
 pub type RevisionContentId = u64;
+/// SHA-256 digest addressing a chunk in the deduplicated chunk store
+pub type ChunkDigest = [u8; 32];
 
 #[derive(CandidType, Deserialize, Clone, Debug)]
 pub struct Revision {
@@ -19,6 +25,37 @@ pub struct Revision {
     pub created_by: UserId,
     pub version: u8,
     pub project_id: ProjectId,
+    /// None for revisions created before the approval lifecycle, treated as Draft
+    pub status: Option<RevisionStatus>,
+    /// Set when the revision restores the contents of an earlier revision
+    pub rollback: Option<RevisionRollback>,
+    /// Set for revisions created by an import
+    pub imported: Option<ImportOrigin>,
+    /// Set when the revision is released. None for drafts and for revisions released
+    /// before release times were recorded.
+    pub released_at: Option<u64>,
+    /// Set when the revision is superseded or made obsolete, starts its retention period
+    pub retired_at: Option<u64>,
+    /// Set once the content of the revision has been purged under the retention policy
+    pub purged_at: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct RevisionRollback {
+    pub target_revision_id: RevisionId,
+    pub target_version: u8,
+    pub reason: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum RevisionStatus {
+    Draft,
+    InReview,
+    Approved,
+    Rejected,
+    Released,
+    Superseded,
+    Obsolete,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -26,9 +63,20 @@ pub struct RevisionContent {
     pub id: RevisionContentId,
     pub file_name: Option<String>,
     pub content_type: RevisionContentType,
+    /// MIME type declared by the client and verified against the content's leading bytes.
+    /// None for content stored before MIME types were recorded.
+    pub mime_type: Option<String>,
+    /// Set when the client encrypted the content with the document key, required for
+    /// content of encrypted documents
+    pub encryption: Option<ContentEncryption>,
     /// Content data - None means reference existing content by ID (optimization for unchanged content)
-    /// Security note: When None, the backend MUST validate that the ID references content the user has access to
+    /// Referenced content must belong to a revision of the same document or of a document the caller can read
     pub content_data: Option<RevisionContentData>,
+    /// Set once the data has been purged under the retention policy, ignored in inputs
+    pub purged: Option<ContentPurge>,
+    /// SHA-256 (hex) of the data, recorded when it is stored and ignored in inputs.
+    /// None for content stored before checksums were recorded, until it is backfilled.
+    pub sha256: Option<String>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -52,6 +100,32 @@ pub struct RevisionContentChunk {
     pub checksum: Option<String>, // SHA-256 checksum for integrity
 }
 
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ChunkVerification {
+    pub chunk_id: u32,
+    /// Checksum the chunk is stored under, empty if the chunk is missing
+    pub checksum: String,
+    pub size: u64,
+    /// Whether the stored data still hashes to `checksum`
+    pub is_valid: bool,
+}
+
+/// Result of rehashing stored content. Chunked content is checked a range of chunks
+/// per call; the content checksum was verified against the chunks when it was stored.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ContentVerification {
+    pub content_id: RevisionContentId,
+    /// SHA-256 (hex) recorded when the content was stored, None if not recorded yet
+    pub sha256: Option<String>,
+    pub size: u64,
+    /// Chunks checked by this call, empty for direct content
+    pub chunks: Vec<ChunkVerification>,
+    /// First chunk left to check, None once the last chunk was checked
+    pub next_chunk: Option<u32>,
+    /// Whether the checked data matches the checksums recorded when it was stored
+    pub is_valid: bool,
+}
+
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct RevisionContentMetadata {
     pub revision_id: RevisionId,
@@ -63,10 +137,139 @@ pub struct RevisionContentMetadata {
     pub is_complete: bool,
 }
 
+/// Bookkeeping for a deduplicated chunk: how many chunk references (revision content
+/// or pending uploads) point at it and the size of its data.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ChunkUsage {
+    pub references: u64,
+    pub size: u64,
+}
+
+/// Step of moving revision content stored by earlier versions to the current stores.
+/// Runs in batches on a timer after an upgrade, in this order.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum StorageMigrationPhase {
+    Chunks,
+    ChunkReferences,
+    Metadata,
+    ContentIndex,
+    Checksums,
+    SearchIndex,
+    Done,
+}
+
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct StorageMigration {
+    pub phase: StorageMigrationPhase,
+    /// Next revision (content index phase) or content (checksum phase) to process
+    pub cursor: u64,
+    /// Checksum of the content at `cursor` while its chunks are hashed over several batches
+    pub hash: Option<Sha256State>,
+    pub hashed_chunks: u32,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub enum RevisionContentType {
     Markdown,
+    /// Any other file
     Upload,
+    Pdf,
+    Image,
+    Spreadsheet,
+    /// JSON data captured with a structured form
+    StructuredForm,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq)]
+pub enum DiffChangeKind {
+    Unchanged,
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A run of words within a changed line. Only `Unchanged`, `Added` and `Removed` occur.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DiffSegment {
+    pub kind: DiffChangeKind,
+    pub text: String,
+}
+
+/// One row of a side-by-side diff. Line numbers are 1-based; a side is None when the
+/// line only exists in the other revision.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct DiffLine {
+    pub kind: DiffChangeKind,
+    pub original_line: Option<u32>,
+    pub updated_line: Option<u32>,
+    pub original_text: Option<String>,
+    pub updated_text: Option<String>,
+    /// Word-level changes, only for `Changed` lines
+    pub original_segments: Vec<DiffSegment>,
+    pub updated_segments: Vec<DiffSegment>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ContentDiffSide {
+    pub content_id: RevisionContentId,
+    pub checksum: String,
+    pub size: u64,
+}
+
+/// Comparison of a content item present in either revision, matched by file name or,
+/// for unnamed content, by position among the unnamed items of the same type.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ContentDiff {
+    pub file_name: Option<String>,
+    pub content_type: RevisionContentType,
+    pub kind: DiffChangeKind,
+    pub original: Option<ContentDiffSide>,
+    pub updated: Option<ContentDiffSide>,
+    /// Line diff of Markdown content; empty for uploads
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct RevisionDiff {
+    pub document_id: DocumentId,
+    pub original: Revision,
+    pub updated: Revision,
+    /// Revisions from `original` up to and including `updated`
+    pub revisions: Vec<Revision>,
+    pub contents: Vec<ContentDiff>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ContentHeading {
+    /// 1 to 6
+    pub level: u8,
+    pub text: String,
+    /// `id` of the heading element in the rendered HTML
+    pub anchor: String,
+}
+
+/// Title and headings of Markdown content, in document order.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ContentOutline {
+    pub content_id: RevisionContentId,
+    /// First level 1 heading, or the first heading if there is none
+    pub title: Option<String>,
+    pub headings: Vec<ContentHeading>,
+}
+
+/// Markdown content rendered to HTML. Raw HTML in the source is escaped and links
+/// are limited to http(s), mailto and relative targets.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ContentPreview {
+    pub content_id: RevisionContentId,
+    pub html: String,
+    pub outline: ContentOutline,
+}
+
+/// MIME types a tenant accepts for new content. Entries are exact types or `type/*`.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ContentTypePolicy {
+    pub allowed_mime_types: Vec<String>,
 }
 
 // Inputs
@@ -78,15 +281,6 @@ pub struct CreateRevisionInput {
     pub contents: Vec<RevisionContent>,
 }
 
-#[derive(CandidType, Deserialize)]
-pub struct StoreRevisionContentChunkInput {
-    pub revision_id: RevisionId,
-    pub content_index: u32,
-    pub chunk: RevisionContentChunk,
-    pub content_type: RevisionContentType,
-    pub file_name: Option<String>,
-}
-
 #[derive(CandidType, Deserialize)]
 pub struct GetRevisionContentChunkInput {
     pub revision_id: RevisionId,
@@ -94,12 +288,6 @@ pub struct GetRevisionContentChunkInput {
     pub chunk_id: u32,
 }
 
-#[derive(CandidType, Deserialize)]
-pub struct FinishRevisionContentUploadInput {
-    pub revision_id: RevisionId,
-    pub content_index: u32,
-}
-
 #[derive(CandidType, Deserialize)]
 pub struct RevisionIdInput {
     pub id: RevisionId,
@@ -110,6 +298,13 @@ pub struct ListRevisionsInput {
     pub pagination: PaginationInput,
 }
 
+#[derive(CandidType, Deserialize)]
+pub struct RollbackDocumentInput {
+    pub document_id: DocumentId,
+    pub target_revision_id: RevisionId,
+    pub reason: String,
+}
+
 #[derive(CandidType, Deserialize)]
 pub struct DiffRevisionsInput {
     pub original: RevisionId,
@@ -126,10 +321,40 @@ pub struct ListRevisionContentsInput {
     pub revision_id: RevisionId,
 }
 
+#[derive(CandidType, Deserialize)]
+pub struct VerifyContentInput {
+    pub content_id: RevisionContentId,
+    /// First chunk to check, defaults to 0
+    pub start_chunk: Option<u32>,
+    /// Number of chunks to check, at most `MAX_VERIFY_CHUNKS`
+    pub chunk_count: Option<u32>,
+}
+
 #[derive(CandidType, Deserialize)]
 pub struct DownloadRevisionContentInput {
     pub content_id: RevisionContentId,
     pub chunk_id: Option<u32>, // None for direct content, Some(chunk_id) for chunked content
+    /// Token of a share link, for callers without a user record
+    pub share_token: Option<String>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct GetContentPreviewInput {
+    pub content_id: RevisionContentId,
+    /// Token of a share link, for callers without a user record
+    pub share_token: Option<String>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct GetContentOutlineInput {
+    pub content_id: RevisionContentId,
+    /// Token of a share link, for callers without a user record
+    pub share_token: Option<String>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct SetContentTypePolicyInput {
+    pub allowed_mime_types: Vec<String>,
 }
 
 // Results
@@ -162,8 +387,8 @@ pub enum CreateRevisionResult {
 }
 
 #[derive(CandidType, Deserialize)]
-pub enum StoreRevisionContentChunkResult {
-    Ok(()),
+pub enum RollbackDocumentResult {
+    Ok(RevisionId),
     Err(AppError),
 }
 
@@ -173,12 +398,6 @@ pub enum GetRevisionContentChunkResult {
     Err(AppError),
 }
 
-#[derive(CandidType, Deserialize)]
-pub enum FinishRevisionContentUploadResult {
-    Ok(()),
-    Err(AppError),
-}
-
 #[derive(CandidType, Deserialize)]
 pub enum ListRevisionsResult {
     Ok((Vec<Revision>, PaginationMetadata)),
@@ -187,7 +406,7 @@ pub enum ListRevisionsResult {
 
 #[derive(CandidType, Deserialize)]
 pub enum DiffRevisionsResult {
-    Ok(Vec<Revision>),
+    Ok(Box<RevisionDiff>),
     Err(AppError),
 }
 
@@ -203,8 +422,38 @@ pub enum ListRevisionContentsResult {
     Err(AppError),
 }
 
+#[derive(CandidType, Deserialize)]
+pub enum VerifyContentResult {
+    Ok(ContentVerification),
+    Err(AppError),
+}
+
 #[derive(CandidType, Deserialize)]
 pub enum DownloadRevisionContentResult {
     Ok(Vec<u8>), // Raw bytes for either direct content or a single chunk
     Err(AppError),
 }
+
+#[derive(CandidType, Deserialize)]
+pub enum SetContentTypePolicyResult {
+    Ok,
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum GetContentTypePolicyResult {
+    Ok(ContentTypePolicy),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum GetContentPreviewResult {
+    Ok(ContentPreview),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum GetContentOutlineResult {
+    Ok(ContentOutline),
+    Err(AppError),
+}
@@ -0,0 +1,61 @@
+use candid::CandidType;
+use serde::Deserialize;
+
+use crate::types::documents::DocumentId;
+use crate::types::errors::AppError;
+use crate::types::projects::ProjectId;
+use crate::types::revisions::RevisionId;
+
+/// Key of the inverted index: one entry per distinct term in a revision's Markdown content.
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SearchIndexKey {
+    pub term: String,
+    pub revision_id: RevisionId,
+}
+
+/// Key of the forward index, listing the terms of a revision so that it can be removed
+/// from the inverted index without scanning it.
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SearchRevisionTermKey {
+    pub revision_id: RevisionId,
+    pub term: String,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SearchIndexedRevision {
+    pub revision_id: RevisionId,
+    pub document_id: DocumentId,
+    pub project_id: ProjectId,
+    /// Total number of indexed tokens, used to normalize term frequencies
+    pub token_count: u32,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SearchHit {
+    pub document_id: DocumentId,
+    pub document_title: String,
+    pub revision_id: RevisionId,
+    pub version: u8,
+    pub project_id: ProjectId,
+    pub score: f64,
+    pub snippet: String,
+}
+
+// Inputs
+
+#[derive(CandidType, Deserialize)]
+pub struct SearchDocumentsInput {
+    pub project_id: ProjectId,
+    pub query: String,
+    pub limit: Option<u32>,
+    /// Also return matches in revisions that are no longer the latest of their document
+    pub include_all_revisions: Option<bool>,
+}
+
+// Results
+
+#[derive(CandidType, Deserialize)]
+pub enum SearchDocumentsResult {
+    Ok(Vec<SearchHit>),
+    Err(AppError),
+}
@@ -0,0 +1,85 @@
+use candid::CandidType;
+use serde::Deserialize;
+
+use crate::types::documents::DocumentId;
+use crate::types::errors::AppError;
+use crate::types::projects::ProjectId;
+use crate::types::revisions::{Revision, RevisionContent, RevisionId};
+use crate::types::users::UserId;
+
+pub type ShareLinkId = u64;
+
+/// Read-only access to a single revision for holders of `token`, who do not
+/// need a user record in the tenant.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ShareLink {
+    pub id: ShareLinkId,
+    pub token: String,
+    pub document_id: DocumentId,
+    pub revision_id: RevisionId,
+    pub project_id: ProjectId,
+    pub created_at: u64,
+    pub created_by: UserId,
+    pub expires_at: u64,
+    pub revoked_at: Option<u64>,
+    pub revoked_by: Option<UserId>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct SharedRevision {
+    pub document_id: DocumentId,
+    pub document_title: String,
+    pub revision: Revision,
+    pub contents: Vec<RevisionContent>,
+    pub expires_at: u64,
+}
+
+// Inputs
+
+#[derive(CandidType, Deserialize)]
+pub struct CreateShareLinkInput {
+    pub revision_id: RevisionId,
+    /// Expiry as a timestamp in nanoseconds
+    pub expires_at: u64,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct RevokeShareLinkInput {
+    pub id: ShareLinkId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ListShareLinksInput {
+    pub document_id: DocumentId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct GetSharedRevisionInput {
+    pub token: String,
+}
+
+// Results
+
+#[derive(CandidType, Deserialize)]
+pub enum CreateShareLinkResult {
+    Ok(ShareLink),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum RevokeShareLinkResult {
+    Ok,
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum ListShareLinksResult {
+    Ok(Vec<ShareLink>),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum GetSharedRevisionResult {
+    Ok(Box<SharedRevision>),
+    Err(AppError),
+}
@@ -0,0 +1,130 @@
+use candid::CandidType;
+use serde::Deserialize;
+
+use crate::types::access_control::RoleId;
+use crate::types::approvals::SignatureAttestation;
+use crate::types::documents::DocumentId;
+use crate::types::errors::AppError;
+use crate::types::projects::ProjectId;
+use crate::types::revisions::RevisionId;
+use crate::types::users::UserId;
+
+/// Users holding any of `role_ids` in the project are assigned training on every
+/// revision released in it, to be acknowledged within `due_days`.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct TrainingPolicy {
+    pub project_id: ProjectId,
+    pub role_ids: Vec<RoleId>,
+    pub due_days: u32,
+    pub updated_at: u64,
+    pub updated_by: UserId,
+}
+
+/// A user's assignment to read and acknowledge a released revision.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct TrainingAssignment {
+    pub revision_id: RevisionId,
+    pub user_id: UserId,
+    pub document_id: DocumentId,
+    pub project_id: ProjectId,
+    pub version: u8,
+    /// Roles of the policy the user held when the revision was released
+    pub role_ids: Vec<RoleId>,
+    pub assigned_at: u64,
+    pub due_at: u64,
+    pub acknowledged_at: Option<u64>,
+    /// Sequence of the `Acknowledged` signature on the revision, when signed
+    pub signature_sequence: Option<u32>,
+    /// Set when a newer revision was released before this one was acknowledged; the
+    /// assignment on the newer revision replaces it
+    pub superseded_at: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct TrainingReportEntry {
+    pub assignment: TrainingAssignment,
+    pub user_name: String,
+    pub document_title: String,
+    pub overdue: bool,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct TrainingReport {
+    pub project_id: ProjectId,
+    pub generated_at: u64,
+    /// Open assignments, superseded ones excluded
+    pub assigned: u32,
+    pub acknowledged: u32,
+    pub overdue: u32,
+    pub entries: Vec<TrainingReportEntry>,
+}
+
+// Inputs
+
+#[derive(CandidType, Deserialize)]
+pub struct SetTrainingPolicyInput {
+    pub project_id: ProjectId,
+    /// No roles turns training assignments off for the project
+    pub role_ids: Vec<RoleId>,
+    pub due_days: u32,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct GetTrainingPolicyInput {
+    pub project_id: ProjectId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct AcknowledgeRevisionInput {
+    pub revision_id: RevisionId,
+    /// Signs the acknowledgement with a challenge requested for the `Acknowledged`
+    /// meaning
+    pub attestation: Option<SignatureAttestation>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct ListMyTrainingInput {
+    /// Also list acknowledged and superseded assignments
+    pub include_completed: bool,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct GetTrainingReportInput {
+    pub project_id: ProjectId,
+    pub document_id: Option<DocumentId>,
+    pub user_id: Option<UserId>,
+    /// Only list overdue assignments
+    pub overdue_only: bool,
+}
+
+// Results
+
+#[derive(CandidType, Deserialize)]
+pub enum SetTrainingPolicyResult {
+    Ok,
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum GetTrainingPolicyResult {
+    Ok(Option<TrainingPolicy>),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum AcknowledgeRevisionResult {
+    Ok(TrainingAssignment),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum ListMyTrainingResult {
+    Ok(Vec<TrainingAssignment>),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum GetTrainingReportResult {
+    Ok(TrainingReport),
+    Err(AppError),
+}
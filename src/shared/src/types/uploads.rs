@@ -0,0 +1,121 @@
+use candid::CandidType;
+use serde::Deserialize;
+
+use crate::types::encryption::ContentEncryption;
+use crate::types::errors::AppError;
+use crate::types::revisions::{RevisionContentId, RevisionContentType};
+use crate::types::users::UserId;
+use crate::utils::sha256::Sha256State;
+
+pub type UploadId = u64;
+
+/// Content uploaded in chunks before the revision it belongs to is created. A
+/// finished upload is identified by its reserved content ID, which is passed to
+/// `create_revision` to attach the content.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct UploadSession {
+    pub id: UploadId,
+    pub created_by: UserId,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub content_type: RevisionContentType,
+    pub file_name: Option<String>,
+    pub total_size: u64,
+    pub total_chunks: u32,
+    /// MIME type declared when the upload was started, verified against the first chunk
+    pub mime_type: Option<String>,
+    /// Set when the client encrypts the content with the document key
+    pub encryption: Option<ContentEncryption>,
+    /// SHA-256 (hex) of the complete content, verified when the upload is finished
+    pub expected_sha256: String,
+    /// Hash of the chunks uploaded so far, fed in chunk order as they arrive
+    pub hash: Sha256State,
+    pub hashed_chunks: u32,
+    /// Reserved once the upload is finished. The session is removed once the content
+    /// is attached to a revision.
+    pub content_id: Option<RevisionContentId>,
+}
+
+/// Usage of the deduplicated chunk store.
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct StorageReport {
+    /// Distinct chunks kept in the store
+    pub chunk_count: u64,
+    /// Bytes actually stored
+    pub bytes_stored: u64,
+    /// Bytes all chunk references would take without deduplication
+    pub bytes_referenced: u64,
+    pub bytes_saved_by_dedup: u64,
+    /// Chunks no revision content or upload refers to anymore
+    pub orphan_count: u64,
+    pub orphan_bytes: u64,
+    /// Upload sessions that have not been attached to a revision yet
+    pub pending_upload_count: u64,
+    pub upload_session_ttl: u64,
+}
+
+// Inputs
+
+#[derive(CandidType, Deserialize)]
+pub struct BeginUploadInput {
+    pub content_type: RevisionContentType,
+    pub file_name: Option<String>,
+    pub total_size: u64,
+    pub total_chunks: u32,
+    pub sha256: String,
+    pub mime_type: String,
+    /// Required for content of encrypted documents
+    pub encryption: Option<ContentEncryption>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct UploadChunkInput {
+    pub upload_id: UploadId,
+    pub chunk_id: u32,
+    pub data: Vec<u8>,
+    /// SHA-256 (hex) of `data` as computed by the client; rejected if it does not match
+    pub checksum: Option<String>,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct FinishUploadInput {
+    pub upload_id: UploadId,
+}
+
+#[derive(CandidType, Deserialize)]
+pub struct SetUploadSessionTtlInput {
+    /// Time in nanoseconds after which uploads that were not attached to a revision are removed
+    pub ttl: u64,
+}
+
+// Results
+
+#[derive(CandidType, Deserialize)]
+pub enum BeginUploadResult {
+    Ok(UploadId),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum UploadChunkResult {
+    Ok,
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum FinishUploadResult {
+    Ok(RevisionContentId),
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum SetUploadSessionTtlResult {
+    Ok,
+    Err(AppError),
+}
+
+#[derive(CandidType, Deserialize)]
+pub enum GetStorageReportResult {
+    Ok(StorageReport),
+    Err(AppError),
+}
@@ -0,0 +1,347 @@
+use crate::consts::revisions::MAX_DIFF_CELLS;
+use crate::types::revisions::{DiffChangeKind, DiffLine, DiffSegment};
+
+#[derive(Clone, Copy, PartialEq)]
+enum Edit {
+    Equal,
+    Insert,
+    Delete,
+}
+
+/// Longest-common-subsequence diff of two token sequences. Common prefix and suffix
+/// are stripped first so that typical edits only compare a small region.
+fn diff_sequences<T: PartialEq>(original: &[T], updated: &[T]) -> Vec<Edit> {
+    let prefix = original
+        .iter()
+        .zip(updated)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = original[prefix..]
+        .iter()
+        .rev()
+        .zip(updated[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let a = &original[prefix..original.len() - suffix];
+    let b = &updated[prefix..updated.len() - suffix];
+
+    let mut edits = vec![Edit::Equal; prefix];
+    if a.len().saturating_mul(b.len()) > MAX_DIFF_CELLS {
+        edits.extend(std::iter::repeat_n(Edit::Delete, a.len()));
+        edits.extend(std::iter::repeat_n(Edit::Insert, b.len()));
+    } else {
+        // lengths[i][j] = LCS length of a[i..] and b[j..]
+        let width = b.len() + 1;
+        let mut lengths = vec![0u32; (a.len() + 1) * width];
+        for i in (0..a.len()).rev() {
+            for j in (0..b.len()).rev() {
+                lengths[i * width + j] = if a[i] == b[j] {
+                    lengths[(i + 1) * width + j + 1] + 1
+                } else {
+                    lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            if a[i] == b[j] {
+                edits.push(Edit::Equal);
+                i += 1;
+                j += 1;
+            } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+                edits.push(Edit::Delete);
+                i += 1;
+            } else {
+                edits.push(Edit::Insert);
+                j += 1;
+            }
+        }
+        edits.extend(std::iter::repeat_n(Edit::Delete, a.len() - i));
+        edits.extend(std::iter::repeat_n(Edit::Insert, b.len() - j));
+    }
+    edits.extend(std::iter::repeat_n(Edit::Equal, suffix));
+    edits
+}
+
+/// Splits a line into words and the whitespace between them, so that joining the
+/// tokens reproduces the line.
+fn split_words(line: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = 0;
+    let mut in_whitespace = None;
+    for (index, c) in line.char_indices() {
+        let is_whitespace = c.is_whitespace();
+        if in_whitespace.is_some_and(|previous| previous != is_whitespace) {
+            words.push(&line[start..index]);
+            start = index;
+        }
+        in_whitespace = Some(is_whitespace);
+    }
+    if start < line.len() {
+        words.push(&line[start..]);
+    }
+    words
+}
+
+fn push_segment(segments: &mut Vec<DiffSegment>, kind: DiffChangeKind, text: &str) {
+    match segments.last_mut() {
+        Some(last) if last.kind == kind => last.text.push_str(text),
+        _ => segments.push(DiffSegment {
+            kind,
+            text: text.to_string(),
+        }),
+    }
+}
+
+/// Word-level segments of a changed line, for the original and the updated side.
+pub fn diff_words(original: &str, updated: &str) -> (Vec<DiffSegment>, Vec<DiffSegment>) {
+    let a = split_words(original);
+    let b = split_words(updated);
+    let (mut original_segments, mut updated_segments) = (Vec::new(), Vec::new());
+    let (mut i, mut j) = (0, 0);
+    for edit in diff_sequences(&a, &b) {
+        match edit {
+            Edit::Equal => {
+                push_segment(&mut original_segments, DiffChangeKind::Unchanged, a[i]);
+                push_segment(&mut updated_segments, DiffChangeKind::Unchanged, b[j]);
+                i += 1;
+                j += 1;
+            }
+            Edit::Delete => {
+                push_segment(&mut original_segments, DiffChangeKind::Removed, a[i]);
+                i += 1;
+            }
+            Edit::Insert => {
+                push_segment(&mut updated_segments, DiffChangeKind::Added, b[j]);
+                j += 1;
+            }
+        }
+    }
+    (original_segments, updated_segments)
+}
+
+/// Line-level diff for side-by-side rendering. Removed lines directly followed by
+/// added lines are paired up as `Changed` rows with word-level segments.
+pub fn diff_lines(original: &str, updated: &str) -> Vec<DiffLine> {
+    let a: Vec<&str> = original.lines().collect();
+    let b: Vec<&str> = updated.lines().collect();
+    let edits = diff_sequences(&a, &b);
+
+    let mut lines = Vec::new();
+    let (mut i, mut j, mut k) = (0, 0, 0);
+    while k < edits.len() {
+        if edits[k] == Edit::Equal {
+            lines.push(DiffLine {
+                kind: DiffChangeKind::Unchanged,
+                original_line: Some(i as u32 + 1),
+                updated_line: Some(j as u32 + 1),
+                original_text: Some(a[i].to_string()),
+                updated_text: Some(b[j].to_string()),
+                original_segments: vec![],
+                updated_segments: vec![],
+            });
+            i += 1;
+            j += 1;
+            k += 1;
+            continue;
+        }
+
+        // Collect the block of consecutive deletions and insertions
+        let (block_i, block_j) = (i, j);
+        while k < edits.len() && edits[k] != Edit::Equal {
+            match edits[k] {
+                Edit::Delete => i += 1,
+                Edit::Insert => j += 1,
+                Edit::Equal => unreachable!(),
+            }
+            k += 1;
+        }
+        let removed = i - block_i;
+        let added = j - block_j;
+        for row in 0..removed.max(added) {
+            let original_index = (row < removed).then_some(block_i + row);
+            let updated_index = (row < added).then_some(block_j + row);
+            let line = match (original_index, updated_index) {
+                (Some(oi), Some(ui)) => {
+                    let (original_segments, updated_segments) = diff_words(a[oi], b[ui]);
+                    DiffLine {
+                        kind: DiffChangeKind::Changed,
+                        original_line: Some(oi as u32 + 1),
+                        updated_line: Some(ui as u32 + 1),
+                        original_text: Some(a[oi].to_string()),
+                        updated_text: Some(b[ui].to_string()),
+                        original_segments,
+                        updated_segments,
+                    }
+                }
+                (Some(oi), None) => DiffLine {
+                    kind: DiffChangeKind::Removed,
+                    original_line: Some(oi as u32 + 1),
+                    updated_line: None,
+                    original_text: Some(a[oi].to_string()),
+                    updated_text: None,
+                    original_segments: vec![],
+                    updated_segments: vec![],
+                },
+                (None, Some(ui)) => DiffLine {
+                    kind: DiffChangeKind::Added,
+                    original_line: None,
+                    updated_line: Some(ui as u32 + 1),
+                    original_text: None,
+                    updated_text: Some(b[ui].to_string()),
+                    original_segments: vec![],
+                    updated_segments: vec![],
+                },
+                (None, None) => unreachable!(),
+            };
+            lines.push(line);
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(lines: &[DiffLine]) -> Vec<DiffChangeKind> {
+        lines.iter().map(|line| line.kind.clone()).collect()
+    }
+
+    fn segments(segments: &[DiffSegment]) -> Vec<(DiffChangeKind, &str)> {
+        segments
+            .iter()
+            .map(|segment| (segment.kind.clone(), segment.text.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn unchanged_text() {
+        let lines = diff_lines("a\nb", "a\nb");
+        assert_eq!(
+            kinds(&lines),
+            [DiffChangeKind::Unchanged, DiffChangeKind::Unchanged]
+        );
+        assert_eq!(lines[1].original_line, Some(2));
+        assert_eq!(lines[1].updated_line, Some(2));
+        assert!(diff_lines("", "").is_empty());
+    }
+
+    #[test]
+    fn inserted_lines() {
+        let lines = diff_lines("a\nc", "a\nb\nc");
+        assert_eq!(
+            kinds(&lines),
+            [
+                DiffChangeKind::Unchanged,
+                DiffChangeKind::Added,
+                DiffChangeKind::Unchanged
+            ]
+        );
+        assert_eq!(lines[1].original_line, None);
+        assert_eq!(lines[1].updated_line, Some(2));
+        assert_eq!(lines[1].updated_text.as_deref(), Some("b"));
+        assert_eq!(lines[2].original_line, Some(2));
+        assert_eq!(lines[2].updated_line, Some(3));
+    }
+
+    #[test]
+    fn deleted_lines() {
+        let lines = diff_lines("a\nb\nc", "a\nc");
+        assert_eq!(
+            kinds(&lines),
+            [
+                DiffChangeKind::Unchanged,
+                DiffChangeKind::Removed,
+                DiffChangeKind::Unchanged
+            ]
+        );
+        assert_eq!(lines[1].original_line, Some(2));
+        assert_eq!(lines[1].updated_line, None);
+        assert_eq!(lines[1].original_text.as_deref(), Some("b"));
+    }
+
+    #[test]
+    fn changed_lines_are_paired() {
+        let lines = diff_lines("a\nold one\nold two\nz", "a\nnew one\nz");
+        assert_eq!(
+            kinds(&lines),
+            [
+                DiffChangeKind::Unchanged,
+                DiffChangeKind::Changed,
+                DiffChangeKind::Removed,
+                DiffChangeKind::Unchanged
+            ]
+        );
+        assert_eq!(lines[1].original_text.as_deref(), Some("old one"));
+        assert_eq!(lines[1].updated_text.as_deref(), Some("new one"));
+        assert_eq!(lines[2].original_line, Some(3));
+        assert_eq!(lines[3].original_line, Some(4));
+        assert_eq!(lines[3].updated_line, Some(3));
+    }
+
+    #[test]
+    fn word_level_changes() {
+        let (original, updated) = diff_words("the quick brown fox", "the slow brown  fox");
+        assert_eq!(
+            segments(&original),
+            [
+                (DiffChangeKind::Unchanged, "the "),
+                (DiffChangeKind::Removed, "quick"),
+                (DiffChangeKind::Unchanged, " brown"),
+                (DiffChangeKind::Removed, " "),
+                (DiffChangeKind::Unchanged, "fox"),
+            ]
+        );
+        assert_eq!(
+            segments(&updated),
+            [
+                (DiffChangeKind::Unchanged, "the "),
+                (DiffChangeKind::Added, "slow"),
+                (DiffChangeKind::Unchanged, " brown"),
+                (DiffChangeKind::Added, "  "),
+                (DiffChangeKind::Unchanged, "fox"),
+            ]
+        );
+
+        let lines = diff_lines("status: draft", "status: released");
+        assert_eq!(
+            segments(&lines[0].updated_segments),
+            [
+                (DiffChangeKind::Unchanged, "status: "),
+                (DiffChangeKind::Added, "released"),
+            ]
+        );
+    }
+
+    #[test]
+    fn splits_words_and_whitespace() {
+        assert_eq!(
+            split_words("  a bc\td "),
+            ["  ", "a", " ", "bc", "\t", "d", " "]
+        );
+        assert!(split_words("").is_empty());
+    }
+
+    #[test]
+    fn large_regions_fall_back_to_replacement() {
+        let width = (MAX_DIFF_CELLS as f64).sqrt() as u32 + 1;
+        let original: Vec<u32> = (0..width).collect();
+        let updated: Vec<u32> = (1..=width).collect();
+        let edits = diff_sequences(&original, &updated);
+        assert_eq!(edits.len(), 2 * width as usize);
+        assert!(edits[..width as usize]
+            .iter()
+            .all(|edit| *edit == Edit::Delete));
+        assert!(edits[width as usize..]
+            .iter()
+            .all(|edit| *edit == Edit::Insert));
+
+        // Below the limit the common run is found
+        let edits = diff_sequences(&original[..100], &updated[..100]);
+        assert_eq!(
+            edits.iter().filter(|edit| **edit == Edit::Equal).count(),
+            99
+        );
+    }
+}
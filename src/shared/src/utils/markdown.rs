@@ -0,0 +1,278 @@
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag, TagEnd};
+use std::collections::HashSet;
+
+use crate::types::revisions::ContentHeading;
+
+const SAFE_URL_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+/// Markdown rendered to sanitized HTML, with its title and headings.
+pub struct RenderedMarkdown {
+    pub html: String,
+    pub title: Option<String>,
+    pub headings: Vec<ContentHeading>,
+}
+
+/// Whether a link target may be rendered: relative URLs, fragments and http(s) or
+/// mailto URLs. Anything else that looks like a scheme (`javascript:`, `data:`) is not.
+fn is_safe_url(url: &str) -> bool {
+    match url.trim().split_once(':') {
+        Some((scheme, _)) if !scheme.contains(['/', '?', '#']) => SAFE_URL_SCHEMES
+            .iter()
+            .any(|safe| scheme.eq_ignore_ascii_case(safe)),
+        _ => true,
+    }
+}
+
+/// Raw HTML is rendered as text and unsafe link targets are dropped, everything else
+/// is escaped by the HTML renderer.
+fn sanitize_event(event: Event) -> Event {
+    match event {
+        Event::Html(html) | Event::InlineHtml(html) => Event::Text(html),
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) if !is_safe_url(&dest_url) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: CowStr::Borrowed(""),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) if !is_safe_url(&dest_url) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: CowStr::Borrowed(""),
+            title,
+            id,
+        }),
+        event => event,
+    }
+}
+
+/// Lowercase, dash separated anchor of a heading, unique within the document.
+fn anchor_for(text: &str, used: &mut HashSet<String>) -> String {
+    let mut slug = String::new();
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if (c.is_whitespace() || c == '-' || c == '_') && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = match slug.trim_matches('-') {
+        "" => "section".to_string(),
+        slug => slug.to_string(),
+    };
+    let mut anchor = slug.clone();
+    let mut suffix = 0;
+    while used.contains(&anchor) {
+        suffix += 1;
+        anchor = format!("{}-{}", slug, suffix);
+    }
+    used.insert(anchor.clone());
+    anchor
+}
+
+/// Renders Markdown (CommonMark with tables, strikethrough, task lists and footnotes)
+/// to HTML. Headings get `id` anchors; the title is the first level 1 heading, or the
+/// first heading if there is none.
+pub fn render_markdown(source: &str) -> RenderedMarkdown {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_FOOTNOTES;
+    let mut events: Vec<Event> = Parser::new_ext(source, options)
+        .map(sanitize_event)
+        .collect();
+
+    let mut headings = Vec::new();
+    let mut used_anchors = HashSet::new();
+    let mut index = 0;
+    while index < events.len() {
+        if let Event::Start(Tag::Heading { level, .. }) = &events[index] {
+            let level = *level;
+            let mut text = String::new();
+            let mut end = index + 1;
+            while end < events.len() && !matches!(events[end], Event::End(TagEnd::Heading(_))) {
+                match &events[end] {
+                    Event::Text(part) | Event::Code(part) => text.push_str(part),
+                    Event::SoftBreak | Event::HardBreak => text.push(' '),
+                    _ => {}
+                }
+                end += 1;
+            }
+            let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            let anchor = anchor_for(&text, &mut used_anchors);
+            events[index] = Event::Start(Tag::Heading {
+                level,
+                id: Some(CowStr::from(anchor.clone())),
+                classes: vec![],
+                attrs: vec![],
+            });
+            headings.push(ContentHeading {
+                level: level as u8,
+                text,
+                anchor,
+            });
+            index = end;
+        }
+        index += 1;
+    }
+
+    let mut rendered = String::with_capacity(source.len() * 3 / 2);
+    html::push_html(&mut rendered, events.into_iter());
+
+    let title = headings
+        .iter()
+        .find(|heading| heading.level == 1)
+        .or(headings.first())
+        .map(|heading| heading.text.clone());
+    RenderedMarkdown {
+        html: rendered,
+        title,
+        headings,
+    }
+}
+
+/// Escapes text for HTML text content and attribute values.
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Wraps a rendered HTML fragment in a standalone document.
+pub fn html_document(title: Option<&str>, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title.unwrap_or_default()),
+        body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_html_is_rendered_as_text() {
+        let rendered = render_markdown("<script>alert(1)</script>\n\nHello <b>world</b>");
+        assert!(!rendered.html.contains("<script>"));
+        assert!(rendered
+            .html
+            .contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(rendered.html.contains("Hello &lt;b&gt;world&lt;/b&gt;"));
+    }
+
+    #[test]
+    fn safe_urls() {
+        for url in [
+            "https://example.com",
+            "HTTP://example.com",
+            "mailto:someone@example.com",
+            "/docs/intro",
+            "intro.md",
+            "#section",
+            "?page=2",
+            "docs/a:b",
+        ] {
+            assert!(is_safe_url(url), "{}", url);
+        }
+        for url in [
+            "javascript:alert(1)",
+            " JavaScript:alert(1)",
+            "data:text/html;base64,PHNjcmlwdD4=",
+            "vbscript:msgbox",
+        ] {
+            assert!(!is_safe_url(url), "{}", url);
+        }
+    }
+
+    #[test]
+    fn unsafe_link_and_image_targets_are_dropped() {
+        let rendered = render_markdown(
+            "[link](javascript:alert(1)) ![image](data:image/png;base64,AAAA) [ok](https://example.com)",
+        );
+        assert!(!rendered.html.contains("javascript:"));
+        assert!(!rendered.html.contains("data:"));
+        assert!(rendered.html.contains("<a href=\"\">link</a>"));
+        assert!(rendered.html.contains("<img src=\"\" alt=\"image\" />"));
+        assert!(rendered
+            .html
+            .contains("<a href=\"https://example.com\">ok</a>"));
+    }
+
+    #[test]
+    fn sanitize_event_keeps_safe_events() {
+        let event = sanitize_event(Event::Text(CowStr::Borrowed("text")));
+        assert_eq!(event, Event::Text(CowStr::Borrowed("text")));
+        let event = sanitize_event(Event::InlineHtml(CowStr::Borrowed("<br>")));
+        assert_eq!(event, Event::Text(CowStr::Borrowed("<br>")));
+    }
+
+    #[test]
+    fn heading_anchors_are_unique() {
+        let rendered = render_markdown("## Scope\n\n## Scope\n\n## Scope\n\n## Scope-1\n\n## !!!");
+        let anchors: Vec<&str> = rendered
+            .headings
+            .iter()
+            .map(|heading| heading.anchor.as_str())
+            .collect();
+        assert_eq!(
+            anchors,
+            ["scope", "scope-1", "scope-2", "scope-1-1", "section"]
+        );
+        assert!(rendered.html.contains("<h2 id=\"scope-2\">Scope</h2>"));
+    }
+
+    #[test]
+    fn title_and_headings() {
+        let rendered = render_markdown("## Intro\n\n# Quality *Manual*\n\n### Step `one`");
+        assert_eq!(rendered.title.as_deref(), Some("Quality Manual"));
+        let headings: Vec<(u8, &str, &str)> = rendered
+            .headings
+            .iter()
+            .map(|heading| {
+                (
+                    heading.level,
+                    heading.text.as_str(),
+                    heading.anchor.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            headings,
+            [
+                (2, "Intro", "intro"),
+                (1, "Quality Manual", "quality-manual"),
+                (3, "Step one", "step-one"),
+            ]
+        );
+
+        let rendered = render_markdown("## Intro\n\nText");
+        assert_eq!(rendered.title.as_deref(), Some("Intro"));
+        assert_eq!(render_markdown("Text").title, None);
+    }
+
+    #[test]
+    fn escapes_html() {
+        assert_eq!(
+            escape_html("<a href=\"x\">Tom & Jerry's</a>"),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+    }
+}
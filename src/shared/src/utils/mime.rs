@@ -0,0 +1,258 @@
+use crate::consts::revisions::STRUCTURED_FORM_MIME_TYPE;
+use crate::types::revisions::RevisionContentType;
+
+const ZIP: &str = "application/zip";
+const CFB: &str = "application/x-cfb";
+
+/// Lowercased MIME type without parameters, e.g. `text/plain; charset=utf-8` -> `text/plain`.
+pub fn normalize_mime(mime: &str) -> String {
+    mime.split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// Detects binary formats from their magic bytes. Zip based formats (OOXML, OpenDocument)
+/// are reported as `application/zip` and legacy Office formats as `application/x-cfb`.
+pub fn sniff_mime(bytes: &[u8]) -> Option<&'static str> {
+    const SIGNATURES: &[(&[u8], &str)] = &[
+        (b"%PDF-", "application/pdf"),
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"II*\0", "image/tiff"),
+        (b"MM\0*", "image/tiff"),
+        (b"PK\x03\x04", ZIP),
+        (b"PK\x05\x06", ZIP),
+        (b"\xd0\xcf\x11\xe0\xa1\xb1\x1a\xe1", CFB),
+    ];
+    if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        return Some("image/webp");
+    }
+    SIGNATURES
+        .iter()
+        .find(|(signature, _)| bytes.starts_with(signature))
+        .map(|(_, mime)| *mime)
+}
+
+/// Whether the bytes are UTF-8 text without NUL bytes. A multi-byte character cut off at
+/// the end (e.g. at a chunk boundary) is accepted.
+pub fn is_text(bytes: &[u8]) -> bool {
+    let valid = match std::str::from_utf8(bytes) {
+        Ok(_) => true,
+        Err(e) => e.error_len().is_none(),
+    };
+    valid && !bytes.contains(&0)
+}
+
+fn is_text_mime(mime: &str) -> bool {
+    mime.starts_with("text/")
+        || mime.ends_with("+json")
+        || mime.ends_with("+xml")
+        || matches!(mime, "application/json" | "application/xml")
+}
+
+/// Whether the leading bytes of a content item are consistent with its declared MIME type.
+pub fn matches_declared_mime(mime: &str, sample: &[u8]) -> bool {
+    let sniffed = sniff_mime(sample);
+    match mime {
+        "application/octet-stream" => true,
+        "application/pdf" | "image/png" | "image/jpeg" | "image/gif" | "image/webp"
+        | "image/tiff" => sniffed == Some(mime),
+        "application/zip" => sniffed == Some(ZIP),
+        "application/msword" | "application/vnd.ms-excel" | "application/vnd.ms-powerpoint" => {
+            sniffed == Some(CFB)
+        }
+        _ if mime.starts_with("application/vnd.openxmlformats-officedocument.")
+            || mime.starts_with("application/vnd.oasis.opendocument.") =>
+        {
+            sniffed == Some(ZIP)
+        }
+        _ if is_text_mime(mime) => sniffed.is_none() && is_text(sample),
+        // Formats without a known signature must at least not look like another format
+        _ => sniffed.is_none(),
+    }
+}
+
+/// The structured content kind a MIME type belongs to.
+pub fn content_kind(mime: &str) -> RevisionContentType {
+    match mime {
+        "text/markdown" => RevisionContentType::Markdown,
+        "application/pdf" => RevisionContentType::Pdf,
+        "text/csv"
+        | "application/vnd.ms-excel"
+        | "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+        | "application/vnd.oasis.opendocument.spreadsheet" => RevisionContentType::Spreadsheet,
+        _ if mime == STRUCTURED_FORM_MIME_TYPE => RevisionContentType::StructuredForm,
+        _ if mime.starts_with("image/") => RevisionContentType::Image,
+        _ => RevisionContentType::Upload,
+    }
+}
+
+/// Whether a declared content kind fits the MIME type. `Upload` is the generic kind and
+/// fits any type; Markdown may also be declared as plain text.
+pub fn is_kind_compatible(kind: &RevisionContentType, mime: &str) -> bool {
+    match kind {
+        RevisionContentType::Upload => true,
+        RevisionContentType::Markdown if mime == "text/plain" => true,
+        kind => *kind == content_kind(mime),
+    }
+}
+
+/// Whether a MIME type matches an allow-list of exact types and `type/*` wildcards.
+pub fn is_mime_allowed(mime: &str, allowed: &[String]) -> bool {
+    allowed
+        .iter()
+        .any(|pattern| match pattern.strip_suffix("/*") {
+            Some("*") => true,
+            Some(top_level) => mime
+                .split_once('/')
+                .is_some_and(|(mime_top_level, _)| mime_top_level == top_level),
+            None => pattern == mime,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allowed(patterns: &[&str]) -> Vec<String> {
+        patterns.iter().map(|pattern| pattern.to_string()).collect()
+    }
+
+    #[test]
+    fn normalizes() {
+        assert_eq!(normalize_mime(" Text/Plain; charset=utf-8"), "text/plain");
+        assert_eq!(normalize_mime("application/pdf"), "application/pdf");
+    }
+
+    #[test]
+    fn sniffs_signatures() {
+        let cases: &[(&[u8], &str)] = &[
+            (b"%PDF-1.7\n", "application/pdf"),
+            (b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR", "image/png"),
+            (b"\xff\xd8\xff\xe0\0\x10JFIF", "image/jpeg"),
+            (b"GIF87a\x01\0", "image/gif"),
+            (b"GIF89a\x01\0", "image/gif"),
+            (b"II*\0\x08\0\0\0", "image/tiff"),
+            (b"MM\0*\0\0\0\x08", "image/tiff"),
+            (b"RIFF\x24\0\0\0WEBPVP8 ", "image/webp"),
+            (b"PK\x03\x04\x14\0", ZIP),
+            (b"PK\x05\x06\0\0", ZIP),
+            (b"\xd0\xcf\x11\xe0\xa1\xb1\x1a\xe1\0\0", CFB),
+        ];
+        for (bytes, mime) in cases {
+            assert_eq!(sniff_mime(bytes), Some(*mime), "{}", mime);
+        }
+        assert_eq!(sniff_mime(b"RIFF\x24\0\0\0WAVEfmt "), None);
+        assert_eq!(sniff_mime(b"RIFF"), None);
+        assert_eq!(sniff_mime(b"%PD"), None);
+        assert_eq!(sniff_mime(b"plain text"), None);
+        assert_eq!(sniff_mime(b""), None);
+    }
+
+    #[test]
+    fn office_formats_map_to_containers() {
+        let zip = b"PK\x03\x04\x14\0";
+        let cfb = b"\xd0\xcf\x11\xe0\xa1\xb1\x1a\xe1";
+        for mime in [
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            "application/vnd.oasis.opendocument.text",
+            "application/zip",
+        ] {
+            assert!(matches_declared_mime(mime, zip), "{}", mime);
+            assert!(!matches_declared_mime(mime, cfb), "{}", mime);
+        }
+        for mime in [
+            "application/msword",
+            "application/vnd.ms-excel",
+            "application/vnd.ms-powerpoint",
+        ] {
+            assert!(matches_declared_mime(mime, cfb), "{}", mime);
+            assert!(!matches_declared_mime(mime, zip), "{}", mime);
+        }
+    }
+
+    #[test]
+    fn declared_binary_types() {
+        assert!(matches_declared_mime("application/pdf", b"%PDF-1.7"));
+        assert!(!matches_declared_mime(
+            "application/pdf",
+            b"\x89PNG\r\n\x1a\n"
+        ));
+        assert!(!matches_declared_mime("image/png", b"not an image"));
+        assert!(matches_declared_mime(
+            "application/octet-stream",
+            b"%PDF-1.7"
+        ));
+        // Unknown types only need to not look like a known format
+        assert!(matches_declared_mime("application/x-custom", b"\0\x01\x02"));
+        assert!(!matches_declared_mime("application/x-custom", b"%PDF-1.7"));
+    }
+
+    #[test]
+    fn text_versus_binary() {
+        assert!(is_text(b"plain text\n"));
+        assert!(is_text("Größe".as_bytes()));
+        assert!(is_text(b""));
+        assert!(!is_text(b"text\0with nul"));
+        assert!(!is_text(b"\xff\xfe invalid"));
+
+        assert!(matches_declared_mime("text/plain", b"hello"));
+        assert!(matches_declared_mime("application/json", b"{\"a\": 1}"));
+        assert!(matches_declared_mime(STRUCTURED_FORM_MIME_TYPE, b"{}"));
+        assert!(matches_declared_mime("image/svg+xml", b"<svg/>"));
+        assert!(!matches_declared_mime("text/plain", b"\x01\0\x02"));
+        assert!(!matches_declared_mime("text/csv", b"%PDF-1.7"));
+    }
+
+    #[test]
+    fn utf8_cut_at_chunk_boundary() {
+        let bytes = "ab€".as_bytes();
+        assert_eq!(bytes.len(), 5);
+        assert!(is_text(&bytes[..3]));
+        assert!(is_text(&bytes[..4]));
+        assert!(matches_declared_mime("text/plain", &bytes[..4]));
+        // An invalid byte before the end is not a cut-off character
+        assert!(!is_text(b"a\xe2\x82b"));
+    }
+
+    #[test]
+    fn content_kinds() {
+        assert_eq!(content_kind("text/markdown"), RevisionContentType::Markdown);
+        assert_eq!(content_kind("application/pdf"), RevisionContentType::Pdf);
+        assert_eq!(content_kind("text/csv"), RevisionContentType::Spreadsheet);
+        assert_eq!(
+            content_kind(STRUCTURED_FORM_MIME_TYPE),
+            RevisionContentType::StructuredForm
+        );
+        assert_eq!(content_kind("image/png"), RevisionContentType::Image);
+        assert_eq!(content_kind("application/zip"), RevisionContentType::Upload);
+
+        assert!(is_kind_compatible(
+            &RevisionContentType::Upload,
+            "image/png"
+        ));
+        assert!(is_kind_compatible(
+            &RevisionContentType::Markdown,
+            "text/plain"
+        ));
+        assert!(!is_kind_compatible(&RevisionContentType::Pdf, "image/png"));
+    }
+
+    #[test]
+    fn allow_list_patterns() {
+        let list = allowed(&["application/pdf", "image/*"]);
+        assert!(is_mime_allowed("application/pdf", &list));
+        assert!(is_mime_allowed("image/png", &list));
+        assert!(!is_mime_allowed("application/zip", &list));
+        assert!(!is_mime_allowed("imagex/png", &list));
+        assert!(!is_mime_allowed("image", &list));
+
+        assert!(is_mime_allowed("application/zip", &allowed(&["*/*"])));
+        assert!(!is_mime_allowed("application/pdf", &[]));
+    }
+}
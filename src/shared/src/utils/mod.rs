@@ -1,5 +1,11 @@
+pub mod diff;
 pub mod filter;
 pub mod logs;
+pub mod markdown;
+pub mod mime;
 pub mod pagination;
 pub mod random;
+pub mod sha256;
 pub mod sort;
+pub mod tar;
+pub mod time;
@@ -0,0 +1,148 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+const BLOCK_SIZE: usize = 64;
+
+const INITIAL_STATE: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// SHA-256 state that can be stored between messages, so that data too large to hash
+/// within one message's instruction limit is hashed a part at a time.
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Sha256State {
+    state: Vec<u32>,
+    // Bytes of the last incomplete block
+    buffer: Vec<u8>,
+    length: u64,
+}
+
+impl Default for Sha256State {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sha256State {
+    pub fn new() -> Self {
+        Self {
+            state: INITIAL_STATE.to_vec(),
+            buffer: Vec::new(),
+            length: 0,
+        }
+    }
+
+    /// Number of bytes hashed so far.
+    pub fn len(&self) -> u64 {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        let mut state = self.words();
+        let mut data = data;
+        self.length += data.len() as u64;
+
+        if !self.buffer.is_empty() {
+            let take = (BLOCK_SIZE - self.buffer.len()).min(data.len());
+            self.buffer.extend_from_slice(&data[..take]);
+            data = &data[take..];
+            if self.buffer.len() < BLOCK_SIZE {
+                return;
+            }
+            Self::compress(&mut state, &self.buffer);
+            self.buffer.clear();
+        }
+
+        let full_blocks = data.len() - data.len() % BLOCK_SIZE;
+        Self::compress(&mut state, &data[..full_blocks]);
+        self.buffer.extend_from_slice(&data[full_blocks..]);
+        self.state = state.to_vec();
+    }
+
+    /// SHA-256 digest of all data passed to `update`.
+    pub fn finalize(self) -> [u8; 32] {
+        let mut state = self.words();
+        let mut tail = self.buffer;
+        tail.push(0x80);
+        while tail.len() % BLOCK_SIZE != BLOCK_SIZE - 8 {
+            tail.push(0);
+        }
+        tail.extend_from_slice(&self.length.wrapping_mul(8).to_be_bytes());
+        Self::compress(&mut state, &tail);
+
+        let mut digest = [0u8; 32];
+        for (bytes, word) in digest.chunks_exact_mut(4).zip(state) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+
+    fn words(&self) -> [u32; 8] {
+        self.state.as_slice().try_into().unwrap_or(INITIAL_STATE)
+    }
+
+    // `data` is a whole number of blocks
+    fn compress(state: &mut [u32; 8], data: &[u8]) {
+        for block in data.chunks_exact(BLOCK_SIZE) {
+            let block: [u8; BLOCK_SIZE] = block.try_into().expect("block of BLOCK_SIZE bytes");
+            sha2::compress256(state, &[block.into()]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+
+    fn sample(length: usize) -> Vec<u8> {
+        (0..length).map(|i| (i * 31 % 251) as u8).collect()
+    }
+
+    #[test]
+    fn empty_input() {
+        let expected: [u8; 32] = Sha256::digest([]).into();
+        assert_eq!(Sha256State::new().finalize(), expected);
+    }
+
+    #[test]
+    fn matches_single_pass_digest() {
+        for length in [1, 55, 56, 63, 64, 65, 119, 120, 128, 1000] {
+            let data = sample(length);
+            let mut state = Sha256State::new();
+            state.update(&data);
+            let expected: [u8; 32] = Sha256::digest(&data).into();
+            assert_eq!(state.finalize(), expected, "length {}", length);
+        }
+    }
+
+    #[test]
+    fn split_updates() {
+        let data = sample(1000);
+        let expected: [u8; 32] = Sha256::digest(&data).into();
+        for part in [1, 7, 63, 64, 65, 333] {
+            let mut state = Sha256State::new();
+            for chunk in data.chunks(part) {
+                state.update(chunk);
+            }
+            assert_eq!(state.len(), 1000);
+            assert_eq!(state.finalize(), expected, "part {}", part);
+        }
+    }
+
+    #[test]
+    fn survives_candid_round_trip() {
+        let data = sample(300);
+        let mut state = Sha256State::new();
+        state.update(&data[..100]);
+        let encoded = candid::encode_one(&state).unwrap();
+        let mut state: Sha256State = candid::decode_one(&encoded).unwrap();
+        state.update(&data[100..]);
+        let expected: [u8; 32] = Sha256::digest(&data).into();
+        assert_eq!(state.finalize(), expected);
+    }
+}
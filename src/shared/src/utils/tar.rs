@@ -0,0 +1,209 @@
+pub const TAR_BLOCK_SIZE: u64 = 512;
+
+// Name and prefix field lengths of a ustar header
+const NAME_LENGTH: usize = 100;
+const PREFIX_LENGTH: usize = 155;
+
+/// Size of an entry's data rounded up to whole blocks.
+pub fn padded_size(size: u64) -> u64 {
+    size.div_ceil(TAR_BLOCK_SIZE) * TAR_BLOCK_SIZE
+}
+
+/// Size of the end-of-archive marker, two zero blocks.
+pub fn trailer_size() -> u64 {
+    2 * TAR_BLOCK_SIZE
+}
+
+fn write_octal(field: &mut [u8], value: u64) {
+    let digits = field.len() - 1;
+    let octal = format!("{:0width$o}", value, width = digits);
+    field[..digits].copy_from_slice(&octal.as_bytes()[octal.len() - digits..]);
+    field[digits] = 0;
+}
+
+/// Splits a path into the ustar prefix and name fields at a `/`.
+fn split_path(path: &str) -> Option<(&str, &str)> {
+    if path.len() <= NAME_LENGTH {
+        return Some(("", path));
+    }
+    path.match_indices('/')
+        .map(|(index, _)| (&path[..index], &path[index + 1..]))
+        .find(|(prefix, name)| {
+            prefix.len() <= PREFIX_LENGTH && name.len() <= NAME_LENGTH && !name.is_empty()
+        })
+}
+
+/// ustar header of a regular file, or of a hard link to an earlier entry when
+/// `link_target` is set. Fails if the path or link target do not fit the header.
+pub fn tar_header(
+    path: &str,
+    size: u64,
+    mtime: u64,
+    link_target: Option<&str>,
+) -> Result<[u8; TAR_BLOCK_SIZE as usize], String> {
+    let (prefix, name) =
+        split_path(path).ok_or_else(|| format!("Path '{}' is too long for a tar header", path))?;
+    if link_target.is_some_and(|target| target.len() > NAME_LENGTH) {
+        return Err(format!("Link target of '{}' is too long", path));
+    }
+    if size >= 1 << 33 {
+        return Err(format!("Entry '{}' is too large for a tar header", path));
+    }
+
+    let mut header = [0u8; TAR_BLOCK_SIZE as usize];
+    header[..name.len()].copy_from_slice(name.as_bytes());
+    write_octal(&mut header[100..108], 0o644);
+    write_octal(&mut header[108..116], 0);
+    write_octal(&mut header[116..124], 0);
+    write_octal(
+        &mut header[124..136],
+        if link_target.is_some() { 0 } else { size },
+    );
+    write_octal(&mut header[136..148], mtime);
+    header[156] = if link_target.is_some() { b'1' } else { b'0' };
+    if let Some(target) = link_target {
+        header[157..157 + target.len()].copy_from_slice(target.as_bytes());
+    }
+    header[257..263].copy_from_slice(b"ustar\0");
+    header[263..265].copy_from_slice(b"00");
+    header[345..345 + prefix.len()].copy_from_slice(prefix.as_bytes());
+
+    // The checksum is computed with its own field filled with spaces
+    header[148..156].fill(b' ');
+    let checksum: u64 = header.iter().map(|byte| *byte as u64).sum();
+    write_octal(&mut header[148..155], checksum);
+    header[155] = b' ';
+    Ok(header)
+}
+
+/// Replaces characters that are awkward in archive paths and shortens the name to
+/// `max_length` bytes, keeping a short extension.
+pub fn sanitize_file_name(name: &str, max_length: usize) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let sanitized = sanitized.trim_start_matches('.');
+    if sanitized.len() <= max_length {
+        return sanitized.to_string();
+    }
+    match sanitized.rsplit_once('.') {
+        Some((stem, extension)) if extension.len() < max_length / 2 => {
+            let stem_length = max_length - extension.len() - 1;
+            format!("{}.{}", &stem[..stem_length.min(stem.len())], extension)
+        }
+        _ => sanitized[..max_length].to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(header: &[u8], range: std::ops::Range<usize>) -> &str {
+        let bytes = &header[range];
+        let end = bytes
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(bytes.len());
+        std::str::from_utf8(&bytes[..end]).unwrap()
+    }
+
+    fn octal(header: &[u8], range: std::ops::Range<usize>) -> u64 {
+        u64::from_str_radix(field(header, range).trim(), 8).unwrap()
+    }
+
+    #[test]
+    fn sizes() {
+        assert_eq!(padded_size(0), 0);
+        assert_eq!(padded_size(1), 512);
+        assert_eq!(padded_size(512), 512);
+        assert_eq!(padded_size(513), 1024);
+        assert_eq!(trailer_size(), 1024);
+    }
+
+    #[test]
+    fn regular_file_header() {
+        let header = tar_header("docs/readme.md", 1234, 1_700_000_000, None).unwrap();
+        assert_eq!(field(&header, 0..100), "docs/readme.md");
+        assert_eq!(field(&header, 100..108), "0000644");
+        assert_eq!(octal(&header, 124..136), 1234);
+        assert_eq!(field(&header, 124..136), "00000002322");
+        assert_eq!(octal(&header, 136..148), 1_700_000_000);
+        assert_eq!(header[156], b'0');
+        assert_eq!(&header[257..265], b"ustar\x0000");
+        assert_eq!(field(&header, 345..500), "");
+
+        let mut unsummed = header;
+        unsummed[148..156].fill(b' ');
+        let expected: u64 = unsummed.iter().map(|byte| *byte as u64).sum();
+        assert_eq!(octal(&header, 148..155), expected);
+        assert_eq!(header[154], 0);
+        assert_eq!(header[155], b' ');
+    }
+
+    #[test]
+    fn long_paths_use_the_prefix() {
+        let directory = "d".repeat(80);
+        let file = "f".repeat(60);
+        let path = format!("{}/{}", directory, file);
+        let header = tar_header(&path, 0, 0, None).unwrap();
+        assert_eq!(field(&header, 0..100), file);
+        assert_eq!(field(&header, 345..500), directory);
+
+        assert_eq!(split_path("short/name"), Some(("", "short/name")));
+        let path = format!("{}/{}/{}", "a".repeat(60), "b".repeat(60), "c".repeat(10));
+        let (prefix, name) = split_path(&path).unwrap();
+        assert_eq!(prefix, &path[..60]);
+        assert_eq!(name, &path[61..]);
+    }
+
+    #[test]
+    fn paths_that_do_not_fit() {
+        assert!(split_path(&"x".repeat(101)).is_none());
+        assert!(split_path(&format!("{}/", "x".repeat(101))).is_none());
+        assert!(split_path(&format!("{}/file", "x".repeat(156))).is_none());
+        assert!(tar_header(&"x".repeat(101), 0, 0, None).is_err());
+        assert!(tar_header("file", 0, 0, Some(&"x".repeat(101))).is_err());
+        assert!(tar_header("file", 1 << 33, 0, None).is_err());
+        assert!(tar_header("file", (1 << 33) - 1, 0, None).is_ok());
+    }
+
+    #[test]
+    fn hard_link_header() {
+        let header = tar_header("copy.pdf", 4096, 0, Some("original.pdf")).unwrap();
+        assert_eq!(header[156], b'1');
+        assert_eq!(field(&header, 157..257), "original.pdf");
+        assert_eq!(octal(&header, 124..136), 0);
+    }
+
+    #[test]
+    fn sanitizes_file_names() {
+        assert_eq!(
+            sanitize_file_name("Quality Manual (v2).pdf", 64),
+            "Quality_Manual__v2_.pdf"
+        );
+        assert_eq!(sanitize_file_name("../../etc/passwd", 64), "_.._etc_passwd");
+        assert_eq!(sanitize_file_name(".hidden", 64), "hidden");
+        assert_eq!(sanitize_file_name("Größe.txt", 64), "Gr__e.txt");
+    }
+
+    #[test]
+    fn truncation_keeps_the_extension() {
+        let name = format!("{}.docx", "a".repeat(40));
+        assert_eq!(
+            sanitize_file_name(&name, 20),
+            format!("{}.docx", "a".repeat(15))
+        );
+        // Extensions too long to keep are truncated with the rest of the name
+        let name = format!("name.{}", "e".repeat(20));
+        assert_eq!(sanitize_file_name(&name, 10), "name.eeeee");
+        assert_eq!(sanitize_file_name(&"a".repeat(30), 10), "a".repeat(10));
+    }
+}
@@ -0,0 +1,143 @@
+pub const NANOS_PER_DAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+/// Civil date (year, month, day) of a day count since the Unix epoch.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Day count since the Unix epoch of a civil date.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let shifted_month = i64::from((month + 9) % 12);
+    let day_of_year = (153 * shifted_month + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Adds calendar months to a timestamp in nanoseconds (UTC), keeping the time of day.
+/// The day is clamped to the end of shorter months, so Jan 31 + 1 month is Feb 28/29.
+pub fn add_months(timestamp: u64, months: u32) -> u64 {
+    let days = (timestamp / NANOS_PER_DAY) as i64;
+    let time_of_day = timestamp % NANOS_PER_DAY;
+    let (year, month, day) = civil_from_days(days);
+
+    let month_index = (month - 1) as i64 + i64::from(months);
+    let year = year + month_index / 12;
+    let month = (month_index % 12) as u32 + 1;
+    let day = day.min(days_in_month(year, month));
+    days_from_civil(year, month, day) as u64 * NANOS_PER_DAY + time_of_day
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timestamp(year: i64, month: u32, day: u32) -> u64 {
+        days_from_civil(year, month, day) as u64 * NANOS_PER_DAY
+    }
+
+    #[test]
+    fn days_from_civil_known_dates() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(1969, 12, 31), -1);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(days_from_civil(2024, 2, 29), 19_782);
+    }
+
+    #[test]
+    fn civil_round_trip() {
+        for days in -1_000..200_000 {
+            let (year, month, day) = civil_from_days(days);
+            assert!((1..=12).contains(&month));
+            assert!(day >= 1 && day <= days_in_month(year, month));
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+    }
+
+    #[test]
+    fn days_in_month_leap_years() {
+        assert_eq!(days_in_month(2024, 2), 29);
+        assert_eq!(days_in_month(2023, 2), 28);
+        assert_eq!(days_in_month(2000, 2), 29);
+        assert_eq!(days_in_month(2100, 2), 28);
+    }
+
+    #[test]
+    fn add_months_clamps_to_month_end() {
+        assert_eq!(
+            add_months(timestamp(2023, 1, 31), 1),
+            timestamp(2023, 2, 28)
+        );
+        assert_eq!(
+            add_months(timestamp(2024, 1, 31), 1),
+            timestamp(2024, 2, 29)
+        );
+        assert_eq!(
+            add_months(timestamp(2024, 3, 31), 1),
+            timestamp(2024, 4, 30)
+        );
+    }
+
+    #[test]
+    fn add_months_leap_day() {
+        assert_eq!(
+            add_months(timestamp(2024, 2, 29), 12),
+            timestamp(2025, 2, 28)
+        );
+        assert_eq!(
+            add_months(timestamp(2024, 2, 29), 48),
+            timestamp(2028, 2, 29)
+        );
+    }
+
+    #[test]
+    fn add_months_year_rollover() {
+        assert_eq!(
+            add_months(timestamp(2024, 11, 15), 2),
+            timestamp(2025, 1, 15)
+        );
+        assert_eq!(add_months(timestamp(2024, 12, 1), 1), timestamp(2025, 1, 1));
+        assert_eq!(
+            add_months(timestamp(2024, 5, 10), 120),
+            timestamp(2034, 5, 10)
+        );
+    }
+
+    #[test]
+    fn add_months_keeps_time_of_day() {
+        let time_of_day = 13 * 60 * 60 * 1_000_000_000 + 123;
+        assert_eq!(
+            add_months(timestamp(2024, 1, 31) + time_of_day, 1),
+            timestamp(2024, 2, 29) + time_of_day
+        );
+        assert_eq!(
+            add_months(timestamp(2024, 6, 5) + time_of_day, 0),
+            timestamp(2024, 6, 5) + time_of_day
+        );
+    }
+}
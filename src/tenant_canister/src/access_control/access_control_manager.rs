@@ -8,12 +8,15 @@ use shared::types::access_control::{
     UpdateRolePermissionsInput, UpdateRolePermissionsResult, UserPermission, WorkflowPermission,
 };
 use shared::types::errors::AppError;
+use shared::types::projects::ProjectId;
 use shared::types::users::UserId;
 use shared::{log_debug, log_error, log_info, log_warn};
 use std::cell::RefCell;
 use std::sync::atomic::{AtomicU64, Ordering};
 use strum::IntoEnumIterator;
 
+use crate::encryption::EncryptionManager;
+
 type Memory = VirtualMemory<DefaultMemoryImpl>;
 
 thread_local! {
@@ -50,7 +53,7 @@ impl AccessControlManager {
         ROLES.with(|roles| roles.borrow().get(role_id))
     }
 
-    pub fn get_roles_by_project(project_id: u32) -> Vec<Role> {
+    pub fn get_roles_by_project(project_id: ProjectId) -> Vec<Role> {
         ROLES.with(|roles| {
             roles
                 .borrow()
@@ -65,6 +68,81 @@ impl AccessControlManager {
         USER_ROLES.with(|user_roles| user_roles.borrow_mut().insert(user, roles));
     }
 
+    pub fn get_user_role_ids(user: UserId) -> Vec<RoleId> {
+        USER_ROLES.with(|user_roles| {
+            user_roles
+                .borrow()
+                .get(&user)
+                .map(|roles| roles.0)
+                .unwrap_or_default()
+        })
+    }
+
+    /// Users holding any of `role_ids`, each with the ones they hold.
+    pub fn get_role_holders(role_ids: &[RoleId]) -> Vec<(UserId, Vec<RoleId>)> {
+        USER_ROLES.with(|user_roles| {
+            user_roles
+                .borrow()
+                .iter()
+                .filter_map(|(user, roles)| {
+                    let held: Vec<RoleId> = roles
+                        .0
+                        .into_iter()
+                        .filter(|role_id| role_ids.contains(role_id))
+                        .collect();
+                    (!held.is_empty()).then_some((user, held))
+                })
+                .collect()
+        })
+    }
+
+    pub fn get_user_roles(user: UserId) -> Vec<Role> {
+        Self::get_user_role_ids(user)
+            .iter()
+            .filter_map(Self::get_role)
+            .collect()
+    }
+
+    /// Whether any of the user's roles scoped to `project_id` grants `permission`.
+    pub fn has_project_permission(
+        user: UserId,
+        project_id: ProjectId,
+        permission: &Permission,
+    ) -> bool {
+        Self::get_user_roles(user)
+            .iter()
+            .filter(|role| role.project_id == project_id)
+            .any(|role| role.permissions.contains(permission))
+    }
+
+    /// Whether any of the user's roles, in any project, grants the organization-wide `permission`.
+    pub fn has_organization_permission(user: UserId, permission: &Permission) -> bool {
+        Self::get_user_roles(user)
+            .iter()
+            .any(|role| role.permissions.contains(permission))
+    }
+
+    /// Grants the project's default Admin role to a user, used for the tenant owner.
+    pub fn assign_admin_role(user: UserId, project_id: ProjectId) -> Result<RoleId, AppError> {
+        let admin_role = Self::get_roles_by_project(project_id)
+            .into_iter()
+            .find(|role| role.name == "Admin")
+            .ok_or_else(|| AppError::EntityNotFound("Admin role not found".to_string()))?;
+
+        let mut role_ids = Self::get_user_role_ids(user);
+        if !role_ids.contains(&admin_role.id) {
+            role_ids.push(admin_role.id);
+        }
+        Self::insert_user_roles(user, RoleIdVec(role_ids));
+        log_info!(
+            "role_assignment: Assigned admin role [user_id={}, project_id={}, role_id={}]",
+            user,
+            project_id,
+            admin_role.id
+        );
+        Ok(admin_role.id)
+    }
+
     pub fn update_role(role_id: RoleId, role: Role) {
         ROLES.with(|roles| roles.borrow_mut().insert(role_id, role));
     }
@@ -217,6 +295,8 @@ impl AccessControlManager {
             principal,
             input.user_ids.len()
         );
+        // Assigned roles replace the previous ones
+        EncryptionManager::rotate_revoked_keys();
         AssignRolesResult::Ok
     }
 
@@ -257,6 +337,7 @@ impl AccessControlManager {
         role.permissions = input.permissions;
         role.updated_at = Some(ic_cdk::api::time());
         Self::update_role(input.role_id, role.clone());
+        EncryptionManager::rotate_revoked_keys();
 
         log_info!("role_modification: Updated role permissions [principal={}, role_id={}, role_name='{}', old_permissions={}, new_permissions={}]",
                  principal, input.role_id, role.name, old_permissions_count, role.permissions.len());
@@ -0,0 +1,847 @@
+use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap};
+use shared::consts::approvals::{MAX_SIGNATURE_REASON_LENGTH, SIGNING_CHALLENGE_TTL};
+use shared::consts::memory_ids::tenant_canister::{
+    APPROVAL_POLICIES_MEMORY_ID, REVISION_SIGNATURES_MEMORY_ID,
+};
+use shared::consts::training::TRAINING_ACKNOWLEDGEMENT_REASON;
+use shared::types::access_control::{
+    DocumentPermission, Permission, ProjectPermission, RevisionPermission, RoleId,
+};
+use shared::types::approvals::{
+    ApprovalPolicy, ApproveRevisionInput, GetApprovalPolicyInput, GetApprovalPolicyResult,
+    ListRevisionSignaturesInput, ListRevisionSignaturesResult, ObsoleteRevisionInput,
+    RejectRevisionInput, ReleaseRevisionInput, RequestSigningChallengeInput,
+    RequestSigningChallengeResult, RevisionSignature, RevisionStatusResult, SetApprovalPolicyInput,
+    SetApprovalPolicyResult, SignatureAttestation, SignatureMeaning, SignatureVerification,
+    SigningChallenge, SubmitRevisionForReviewInput, VerifiedSignature, VerifySignaturesInput,
+    VerifySignaturesResult,
+};
+use shared::types::documents::Document;
+use shared::types::errors::AppError;
+use shared::types::notifications::NotificationEvent;
+use shared::types::projects::ProjectId;
+use shared::types::revisions::{Revision, RevisionId, RevisionStatus};
+use shared::types::users::{User, UserId};
+use shared::utils::random::random;
+use shared::{log_debug, log_info, log_warn};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+use crate::access_control::AccessControlManager;
+use crate::document_links::document_links_manager::DocumentLinksManager;
+use crate::documents::DocumentManager;
+use crate::notifications::NotificationsManager;
+use crate::projects::projects_manager::ProjectsManager;
+use crate::revisions::revisions_manager::RevisionsManager;
+use crate::training::TrainingManager;
+use crate::users::user_manager::UserManager;
+
+type Memory = VirtualMemory<DefaultMemoryImpl>;
+
+thread_local! {
+    static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
+        RefCell::new(MemoryManager::init(DefaultMemoryImpl::default()));
+
+    static APPROVAL_POLICIES: RefCell<StableBTreeMap<ProjectId, ApprovalPolicy, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(APPROVAL_POLICIES_MEMORY_ID))),
+        )
+    );
+
+    // (revision, sequence) -> signature; entries are never updated or removed
+    static REVISION_SIGNATURES: RefCell<StableBTreeMap<(RevisionId, u32), RevisionSignature, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(REVISION_SIGNATURES_MEMORY_ID))),
+        )
+    );
+
+    // Outstanding signing challenges by nonce. These are short-lived and do not need
+    // to survive an upgrade.
+    static SIGNING_CHALLENGES: RefCell<BTreeMap<String, SigningChallenge>> =
+        const { RefCell::new(BTreeMap::new()) };
+}
+
+pub struct ApprovalsManager;
+
+impl ApprovalsManager {
+    pub fn get_status(revision: &Revision) -> RevisionStatus {
+        revision.status.clone().unwrap_or(RevisionStatus::Draft)
+    }
+
+    pub fn get_policy(project_id: ProjectId) -> ApprovalPolicy {
+        APPROVAL_POLICIES
+            .with(|policies| policies.borrow().get(&project_id))
+            .unwrap_or(ApprovalPolicy {
+                project_id,
+                required_roles: vec![],
+                quorum: 1,
+                updated_at: None,
+                updated_by: None,
+            })
+    }
+
+    pub fn get_signatures(revision_id: RevisionId) -> Vec<RevisionSignature> {
+        REVISION_SIGNATURES.with(|signatures| {
+            signatures
+                .borrow()
+                .range((revision_id, 0)..=(revision_id, u32::MAX))
+                .map(|(_, signature)| signature)
+                .collect()
+        })
+    }
+
+    fn append_signature(
+        user: &User,
+        revision: &Revision,
+        challenge: SigningChallenge,
+        reason: String,
+        resulting_status: RevisionStatus,
+    ) -> RevisionSignature {
+        let sequence = Self::get_signatures(revision.id).len() as u32;
+        let role_ids = Self::get_project_role_ids(user.id, revision.project_id);
+        let signature = RevisionSignature {
+            revision_id: revision.id,
+            sequence,
+            signer: user.id,
+            signer_name: format!("{} {}", user.first_name, user.last_name),
+            principal: challenge.principal,
+            role_ids,
+            meaning: challenge.meaning,
+            reason,
+            content_hash: challenge.content_hash,
+            challenge_issued_at: challenge.issued_at,
+            signed_at: ic_cdk::api::time(),
+            resulting_status,
+        };
+        REVISION_SIGNATURES.with(|signatures| {
+            signatures
+                .borrow_mut()
+                .insert((revision.id, sequence), signature.clone())
+        });
+        log_info!(
+            "revision_signature: Signature recorded [revision_id={}, sequence={}, signer={}, meaning={:?}, status={:?}, principal={}]",
+            signature.revision_id,
+            signature.sequence,
+            signature.signer,
+            signature.meaning,
+            signature.resulting_status,
+            signature.principal
+        );
+        signature
+    }
+
+    /// Takes the challenge for `attestation.nonce` and checks that it was issued to
+    /// the caller for this revision and meaning, has not expired, and that the
+    /// attested hash matches the revision's content. A nonce can only be used once.
+    fn consume_challenge(
+        principal: candid::Principal,
+        revision: &Revision,
+        meaning: &SignatureMeaning,
+        attestation: &SignatureAttestation,
+    ) -> Result<SigningChallenge, AppError> {
+        let challenge = SIGNING_CHALLENGES
+            .with(|challenges| challenges.borrow_mut().remove(&attestation.nonce))
+            .ok_or_else(|| {
+                log_warn!(
+                    "revision_signature: Security event - unknown signing challenge used [principal={}, revision_id={}]",
+                    principal,
+                    revision.id
+                );
+                AppError::Unauthorized
+            })?;
+        if challenge.principal != principal {
+            log_warn!(
+                "revision_signature: Security event - signing challenge used by another principal [issued_to={}, principal={}, revision_id={}]",
+                challenge.principal,
+                principal,
+                revision.id
+            );
+            return Err(AppError::Unauthorized);
+        }
+        if challenge.expires_at <= ic_cdk::api::time() {
+            return Err(AppError::ValidationError(
+                "Signing challenge has expired, please re-authenticate".to_string(),
+            ));
+        }
+        if challenge.revision_id != revision.id || &challenge.meaning != meaning {
+            return Err(AppError::ValidationError(
+                "Signing challenge was issued for a different revision or meaning".to_string(),
+            ));
+        }
+        if attestation.content_hash != challenge.content_hash
+            || challenge.content_hash != RevisionsManager::get_revision_hash(revision)
+        {
+            return Err(AppError::ValidationError(
+                "Signed content hash does not match the revision content".to_string(),
+            ));
+        }
+        Ok(challenge)
+    }
+
+    /// Signs a training acknowledgement of a released revision with the `Acknowledged`
+    /// meaning.
+    pub fn sign_acknowledgement(
+        principal: candid::Principal,
+        user: &User,
+        revision: &Revision,
+        attestation: &SignatureAttestation,
+    ) -> Result<RevisionSignature, AppError> {
+        let challenge = Self::consume_challenge(
+            principal,
+            revision,
+            &SignatureMeaning::Acknowledged,
+            attestation,
+        )?;
+        Ok(Self::append_signature(
+            user,
+            revision,
+            challenge,
+            TRAINING_ACKNOWLEDGEMENT_REASON.to_string(),
+            Self::get_status(revision),
+        ))
+    }
+
+    pub async fn request_signing_challenge(
+        input: RequestSigningChallengeInput,
+    ) -> RequestSigningChallengeResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Signing challenge request [principal={}, revision_id={}, meaning={:?}]",
+            principal,
+            input.revision_id,
+            input.meaning
+        );
+
+        let user = match UserManager::authenticate(principal, "signing challenge request") {
+            Ok(user) => user,
+            Err(e) => return RequestSigningChallengeResult::Err(e),
+        };
+        let (revision, document) = match Self::get_revision_and_document(input.revision_id) {
+            Ok(found) => found,
+            Err(e) => return RequestSigningChallengeResult::Err(e),
+        };
+        if let Err(e) = Self::authorize(
+            &user,
+            &document,
+            Permission::Document(DocumentPermission::Read),
+            "signing challenge request",
+        ) {
+            return RequestSigningChallengeResult::Err(e);
+        }
+
+        let nonce = random(32).await;
+        let now = ic_cdk::api::time();
+        let challenge = SigningChallenge {
+            nonce: nonce.clone(),
+            revision_id: revision.id,
+            principal,
+            meaning: input.meaning,
+            content_hash: RevisionsManager::get_revision_hash(&revision),
+            issued_at: now,
+            expires_at: now + SIGNING_CHALLENGE_TTL,
+        };
+        SIGNING_CHALLENGES.with(|challenges| {
+            let mut challenges = challenges.borrow_mut();
+            challenges.retain(|_, challenge| challenge.expires_at > now);
+            challenges.insert(nonce, challenge.clone());
+        });
+        log_info!(
+            "revision_signature: Signing challenge issued [revision_id={}, meaning={:?}, user_id={}, expires_at={}, principal={}]",
+            challenge.revision_id,
+            challenge.meaning,
+            user.id,
+            challenge.expires_at,
+            principal
+        );
+        RequestSigningChallengeResult::Ok(challenge)
+    }
+
+    fn get_project_role_ids(user_id: UserId, project_id: ProjectId) -> Vec<RoleId> {
+        AccessControlManager::get_user_roles(user_id)
+            .into_iter()
+            .filter(|role| role.project_id == project_id)
+            .map(|role| role.id)
+            .collect()
+    }
+
+    /// Whether the given approvers, each with the project roles they signed with,
+    /// satisfy the project's policy.
+    fn is_policy_satisfied(policy: &ApprovalPolicy, approvals: &[(UserId, Vec<RoleId>)]) -> bool {
+        let mut approvers: Vec<UserId> = approvals.iter().map(|(signer, _)| *signer).collect();
+        approvers.sort();
+        approvers.dedup();
+
+        approvers.len() as u32 >= policy.quorum.max(1)
+            && policy.required_roles.iter().all(|role_id| {
+                approvals
+                    .iter()
+                    .any(|(_, role_ids)| role_ids.contains(role_id))
+            })
+    }
+
+    fn authorize(
+        user: &User,
+        document: &Document,
+        permission: Permission,
+        operation: &str,
+    ) -> Result<(), AppError> {
+        if DocumentManager::has_permission(user.id, document, &permission) {
+            return Ok(());
+        }
+        log_warn!(
+            "access_control: Permission denied for {} [user_id={}, document_id={}, permission={:?}]",
+            operation,
+            user.id,
+            document.id,
+            permission
+        );
+        Err(AppError::Unauthorized)
+    }
+
+    fn get_revision_and_document(
+        revision_id: RevisionId,
+    ) -> Result<(Revision, Document), AppError> {
+        let revision = RevisionsManager::get_by_id(revision_id)
+            .ok_or_else(|| AppError::EntityNotFound("Revision not found".to_string()))?;
+        let document = DocumentManager::get_by_id(revision.document_id)
+            .ok_or_else(|| AppError::EntityNotFound("Document not found".to_string()))?;
+        Ok((revision, document))
+    }
+
+    fn validate_reason(reason: &str) -> Result<(), AppError> {
+        if reason.trim().is_empty() {
+            return Err(AppError::ValidationError(
+                "A reason is required".to_string(),
+            ));
+        }
+        if reason.chars().count() > MAX_SIGNATURE_REASON_LENGTH {
+            return Err(AppError::ValidationError(format!(
+                "Reason exceeds the maximum length of {} characters",
+                MAX_SIGNATURE_REASON_LENGTH
+            )));
+        }
+        Ok(())
+    }
+
+    fn expect_status(revision: &Revision, expected: RevisionStatus) -> Result<(), AppError> {
+        let status = Self::get_status(revision);
+        if status != expected {
+            return Err(AppError::InvalidStateTransition(format!(
+                "Revision is {:?}, expected {:?}",
+                status, expected
+            )));
+        }
+        Ok(())
+    }
+
+    fn status_error(e: AppError, revision_id: RevisionId, operation: &str) -> RevisionStatusResult {
+        log_warn!(
+            "revision_lifecycle: {} failed [revision_id={}, principal={}] - {:?}",
+            operation,
+            revision_id,
+            ic_cdk::api::msg_caller(),
+            e
+        );
+        RevisionStatusResult::Err(e)
+    }
+
+    /// Users allowed to approve revisions of a document.
+    fn get_approvers(document: &Document) -> Vec<UserId> {
+        let approve = Permission::Revision(RevisionPermission::Approve);
+        UserManager::get_all()
+            .into_iter()
+            .filter(|user| DocumentManager::has_permission(user.id, document, &approve))
+            .map(|user| user.id)
+            .collect()
+    }
+
+    pub fn submit_revision_for_review(input: SubmitRevisionForReviewInput) -> RevisionStatusResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Revision review submission attempt [principal={}, revision_id={}]",
+            principal,
+            input.revision_id
+        );
+
+        let user = match UserManager::authenticate(principal, "revision review submission") {
+            Ok(user) => user,
+            Err(e) => return RevisionStatusResult::Err(e),
+        };
+        let (revision, document) = match Self::get_revision_and_document(input.revision_id) {
+            Ok(found) => found,
+            Err(e) => return Self::status_error(e, input.revision_id, "Review submission"),
+        };
+        if let Err(e) = Self::authorize(
+            &user,
+            &document,
+            Permission::Revision(RevisionPermission::Create),
+            "revision review submission",
+        ) {
+            return RevisionStatusResult::Err(e);
+        }
+        if let Err(e) = Self::validate_reason(&input.reason) {
+            return Self::status_error(e, revision.id, "Review submission");
+        }
+        if let Err(e) = Self::expect_status(&revision, RevisionStatus::Draft) {
+            return Self::status_error(e, revision.id, "Review submission");
+        }
+        let challenge = match Self::consume_challenge(
+            principal,
+            &revision,
+            &SignatureMeaning::Authored,
+            &input.attestation,
+        ) {
+            Ok(challenge) => challenge,
+            Err(e) => return Self::status_error(e, revision.id, "Review submission"),
+        };
+
+        RevisionsManager::update_status(revision.id, RevisionStatus::InReview);
+        Self::append_signature(
+            &user,
+            &revision,
+            challenge,
+            input.reason,
+            RevisionStatus::InReview,
+        );
+        NotificationsManager::notify_document(
+            &document,
+            user.id,
+            NotificationEvent::ReviewRequested {
+                document_id: document.id,
+                revision_id: revision.id,
+                version: revision.version,
+            },
+            &Self::get_approvers(&document),
+        );
+        RevisionStatusResult::Ok(RevisionStatus::InReview)
+    }
+
+    pub fn approve_revision(input: ApproveRevisionInput) -> RevisionStatusResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Revision approval attempt [principal={}, revision_id={}, meaning={:?}]",
+            principal,
+            input.revision_id,
+            input.meaning
+        );
+
+        let user = match UserManager::authenticate(principal, "revision approval") {
+            Ok(user) => user,
+            Err(e) => return RevisionStatusResult::Err(e),
+        };
+        let (revision, document) = match Self::get_revision_and_document(input.revision_id) {
+            Ok(found) => found,
+            Err(e) => return Self::status_error(e, input.revision_id, "Approval"),
+        };
+        if let Err(e) = Self::authorize(
+            &user,
+            &document,
+            Permission::Revision(RevisionPermission::Approve),
+            "revision approval",
+        ) {
+            return RevisionStatusResult::Err(e);
+        }
+        if let Err(e) = Self::validate_reason(&input.reason) {
+            return Self::status_error(e, revision.id, "Approval");
+        }
+        if !matches!(
+            input.meaning,
+            SignatureMeaning::Reviewed | SignatureMeaning::Approved
+        ) {
+            return Self::status_error(
+                AppError::ValidationError(
+                    "Approvals must be signed as Reviewed or Approved".to_string(),
+                ),
+                revision.id,
+                "Approval",
+            );
+        }
+        if let Err(e) = Self::expect_status(&revision, RevisionStatus::InReview) {
+            return Self::status_error(e, revision.id, "Approval");
+        }
+
+        let signatures = Self::get_signatures(revision.id);
+        if signatures
+            .iter()
+            .any(|signature| signature.signer == user.id && signature.meaning == input.meaning)
+        {
+            return Self::status_error(
+                AppError::ValidationError(format!(
+                    "Revision has already been signed as {:?} by this user",
+                    input.meaning
+                )),
+                revision.id,
+                "Approval",
+            );
+        }
+
+        // Evaluate the policy including this signature, so the recorded signature
+        // carries the status it resulted in.
+        let policy = Self::get_policy(revision.project_id);
+        let mut approvals: Vec<(UserId, Vec<RoleId>)> = signatures
+            .into_iter()
+            .filter(|signature| signature.meaning == SignatureMeaning::Approved)
+            .map(|signature| (signature.signer, signature.role_ids))
+            .collect();
+        if input.meaning == SignatureMeaning::Approved {
+            approvals.push((
+                user.id,
+                Self::get_project_role_ids(user.id, revision.project_id),
+            ));
+        }
+        let status = if Self::is_policy_satisfied(&policy, &approvals) {
+            RevisionStatus::Approved
+        } else {
+            RevisionStatus::InReview
+        };
+        let challenge =
+            match Self::consume_challenge(principal, &revision, &input.meaning, &input.attestation)
+            {
+                Ok(challenge) => challenge,
+                Err(e) => return Self::status_error(e, revision.id, "Approval"),
+            };
+
+        RevisionsManager::update_status(revision.id, status.clone());
+        Self::append_signature(&user, &revision, challenge, input.reason, status.clone());
+        if status == RevisionStatus::Approved {
+            log_info!(
+                "revision_lifecycle: Revision approved [revision_id={}, document_id={}, quorum={}, required_roles={:?}]",
+                revision.id,
+                revision.document_id,
+                policy.quorum,
+                policy.required_roles
+            );
+        }
+        RevisionStatusResult::Ok(status)
+    }
+
+    pub fn reject_revision(input: RejectRevisionInput) -> RevisionStatusResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Revision rejection attempt [principal={}, revision_id={}]",
+            principal,
+            input.revision_id
+        );
+
+        let user = match UserManager::authenticate(principal, "revision rejection") {
+            Ok(user) => user,
+            Err(e) => return RevisionStatusResult::Err(e),
+        };
+        let (revision, document) = match Self::get_revision_and_document(input.revision_id) {
+            Ok(found) => found,
+            Err(e) => return Self::status_error(e, input.revision_id, "Rejection"),
+        };
+        if let Err(e) = Self::authorize(
+            &user,
+            &document,
+            Permission::Revision(RevisionPermission::Reject),
+            "revision rejection",
+        ) {
+            return RevisionStatusResult::Err(e);
+        }
+        if let Err(e) = Self::validate_reason(&input.reason) {
+            return Self::status_error(e, revision.id, "Rejection");
+        }
+        if let Err(e) = Self::expect_status(&revision, RevisionStatus::InReview) {
+            return Self::status_error(e, revision.id, "Rejection");
+        }
+        let challenge = match Self::consume_challenge(
+            principal,
+            &revision,
+            &SignatureMeaning::Rejected,
+            &input.attestation,
+        ) {
+            Ok(challenge) => challenge,
+            Err(e) => return Self::status_error(e, revision.id, "Rejection"),
+        };
+
+        RevisionsManager::update_status(revision.id, RevisionStatus::Rejected);
+        Self::append_signature(
+            &user,
+            &revision,
+            challenge,
+            input.reason,
+            RevisionStatus::Rejected,
+        );
+        RevisionStatusResult::Ok(RevisionStatus::Rejected)
+    }
+
+    /// Releases an approved revision; the previously released revision of the
+    /// document becomes `Superseded`.
+    pub fn release_revision(input: ReleaseRevisionInput) -> RevisionStatusResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Revision release attempt [principal={}, revision_id={}]",
+            principal,
+            input.revision_id
+        );
+
+        let user = match UserManager::authenticate(principal, "revision release") {
+            Ok(user) => user,
+            Err(e) => return RevisionStatusResult::Err(e),
+        };
+        let (revision, document) = match Self::get_revision_and_document(input.revision_id) {
+            Ok(found) => found,
+            Err(e) => return Self::status_error(e, input.revision_id, "Release"),
+        };
+        if let Err(e) = Self::authorize(
+            &user,
+            &document,
+            Permission::Revision(RevisionPermission::Approve),
+            "revision release",
+        ) {
+            return RevisionStatusResult::Err(e);
+        }
+        if let Err(e) = Self::expect_status(&revision, RevisionStatus::Approved) {
+            return Self::status_error(e, revision.id, "Release");
+        }
+
+        for previous_id in document.revisions.iter().filter(|id| **id != revision.id) {
+            if let Some(previous) = RevisionsManager::get_by_id(*previous_id) {
+                if Self::get_status(&previous) == RevisionStatus::Released {
+                    RevisionsManager::update_status(previous.id, RevisionStatus::Superseded);
+                    log_info!(
+                        "revision_lifecycle: Revision superseded [revision_id={}, superseded_by={}, document_id={}]",
+                        previous.id,
+                        revision.id,
+                        document.id
+                    );
+                }
+            }
+        }
+
+        RevisionsManager::update_status(revision.id, RevisionStatus::Released);
+        log_info!(
+            "revision_lifecycle: Revision released [revision_id={}, document_id={}, version={}, user_id={}, principal={}]",
+            revision.id,
+            document.id,
+            revision.version,
+            user.id,
+            principal
+        );
+        DocumentLinksManager::log_revision_impact(document.id, revision.id);
+        TrainingManager::assign_training(&revision, &document);
+        RevisionStatusResult::Ok(RevisionStatus::Released)
+    }
+
+    pub fn obsolete_revision(input: ObsoleteRevisionInput) -> RevisionStatusResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Revision retirement attempt [principal={}, revision_id={}]",
+            principal,
+            input.revision_id
+        );
+
+        let user = match UserManager::authenticate(principal, "revision retirement") {
+            Ok(user) => user,
+            Err(e) => return RevisionStatusResult::Err(e),
+        };
+        let (revision, document) = match Self::get_revision_and_document(input.revision_id) {
+            Ok(found) => found,
+            Err(e) => return Self::status_error(e, input.revision_id, "Retirement"),
+        };
+        if let Err(e) = Self::authorize(
+            &user,
+            &document,
+            Permission::Document(DocumentPermission::Archive),
+            "revision retirement",
+        ) {
+            return RevisionStatusResult::Err(e);
+        }
+        if let Err(e) = Self::validate_reason(&input.reason) {
+            return Self::status_error(e, revision.id, "Retirement");
+        }
+        if let Err(e) = Self::expect_status(&revision, RevisionStatus::Released) {
+            return Self::status_error(e, revision.id, "Retirement");
+        }
+
+        RevisionsManager::update_status(revision.id, RevisionStatus::Obsolete);
+        log_info!(
+            "revision_lifecycle: Revision made obsolete [revision_id={}, document_id={}, reason='{}', user_id={}, principal={}]",
+            revision.id,
+            document.id,
+            input.reason,
+            user.id,
+            principal
+        );
+        RevisionStatusResult::Ok(RevisionStatus::Obsolete)
+    }
+
+    pub fn set_approval_policy(input: SetApprovalPolicyInput) -> SetApprovalPolicyResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Approval policy update attempt [principal={}, project_id={}, quorum={}, required_roles={:?}]",
+            principal,
+            input.project_id,
+            input.quorum,
+            input.required_roles
+        );
+
+        let user = match UserManager::authenticate(principal, "approval policy update") {
+            Ok(user) => user,
+            Err(e) => return SetApprovalPolicyResult::Err(e),
+        };
+        if !AccessControlManager::has_project_permission(
+            user.id,
+            input.project_id,
+            &Permission::Project(ProjectPermission::ConfigureSettings),
+        ) {
+            log_warn!(
+                "access_control: Permission denied for approval policy update [user_id={}, project_id={}]",
+                user.id,
+                input.project_id
+            );
+            return SetApprovalPolicyResult::Err(AppError::Unauthorized);
+        }
+        if input.quorum == 0 {
+            log_warn!(
+                "approval_policy: Quorum must be at least 1 [project_id={}, principal={}]",
+                input.project_id,
+                principal
+            );
+            return SetApprovalPolicyResult::Err(AppError::ValidationError(
+                "Quorum must be at least 1".to_string(),
+            ));
+        }
+        for role_id in &input.required_roles {
+            match AccessControlManager::get_role(role_id) {
+                Some(role) if role.project_id == input.project_id => {}
+                _ => {
+                    log_warn!(
+                        "approval_policy: Required role not found in project [project_id={}, role_id={}, principal={}]",
+                        input.project_id,
+                        role_id,
+                        principal
+                    );
+                    return SetApprovalPolicyResult::Err(AppError::EntityNotFound(format!(
+                        "Role {} not found in project",
+                        role_id
+                    )));
+                }
+            }
+        }
+
+        let policy = ApprovalPolicy {
+            project_id: input.project_id,
+            required_roles: input.required_roles,
+            quorum: input.quorum,
+            updated_at: Some(ic_cdk::api::time()),
+            updated_by: Some(user.id),
+        };
+        APPROVAL_POLICIES.with(|policies| {
+            policies
+                .borrow_mut()
+                .insert(policy.project_id, policy.clone())
+        });
+        log_info!(
+            "approval_policy: Updated approval policy [project_id={}, quorum={}, required_roles={:?}, user_id={}, principal={}]",
+            policy.project_id,
+            policy.quorum,
+            policy.required_roles,
+            user.id,
+            principal
+        );
+        SetApprovalPolicyResult::Ok
+    }
+
+    pub fn get_approval_policy(input: GetApprovalPolicyInput) -> GetApprovalPolicyResult {
+        let principal = ic_cdk::api::msg_caller();
+        let user = match UserManager::authenticate(principal, "approval policy retrieval") {
+            Ok(user) => user,
+            Err(e) => return GetApprovalPolicyResult::Err(e),
+        };
+        if !ProjectsManager::is_member(input.project_id, user.id) {
+            log_warn!(
+                "access_control: Permission denied for approval policy retrieval [user_id={}, project_id={}]",
+                user.id,
+                input.project_id
+            );
+            return GetApprovalPolicyResult::Err(AppError::Unauthorized);
+        }
+        GetApprovalPolicyResult::Ok(Self::get_policy(input.project_id))
+    }
+
+    pub fn list_revision_signatures(
+        input: ListRevisionSignaturesInput,
+    ) -> ListRevisionSignaturesResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "revision_signature: Listing signatures [principal={}, revision_id={}]",
+            principal,
+            input.revision_id
+        );
+
+        let user = match UserManager::authenticate(principal, "signature listing") {
+            Ok(user) => user,
+            Err(e) => return ListRevisionSignaturesResult::Err(e),
+        };
+        let document = match Self::get_revision_and_document(input.revision_id) {
+            Ok((_, document)) => document,
+            Err(e) => return ListRevisionSignaturesResult::Err(e),
+        };
+        if let Err(e) = Self::authorize(
+            &user,
+            &document,
+            Permission::Document(DocumentPermission::Read),
+            "signature listing",
+        ) {
+            return ListRevisionSignaturesResult::Err(e);
+        }
+        ListRevisionSignaturesResult::Ok(Self::get_signatures(input.revision_id))
+    }
+
+    /// Computes the revision hash from the stored content checksums and checks it
+    /// against every recorded signature, and that the signature sequence is complete.
+    pub fn verify_signatures(input: VerifySignaturesInput) -> VerifySignaturesResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "revision_signature: Verifying signatures [principal={}, revision_id={}]",
+            principal,
+            input.revision_id
+        );
+
+        let user = match UserManager::authenticate(principal, "signature verification") {
+            Ok(user) => user,
+            Err(e) => return VerifySignaturesResult::Err(e),
+        };
+        let (revision, document) = match Self::get_revision_and_document(input.revision_id) {
+            Ok(found) => found,
+            Err(e) => return VerifySignaturesResult::Err(e),
+        };
+        if let Err(e) = Self::authorize(
+            &user,
+            &document,
+            Permission::Document(DocumentPermission::Read),
+            "signature verification",
+        ) {
+            return VerifySignaturesResult::Err(e);
+        }
+
+        let content_hash = RevisionsManager::get_revision_hash(&revision);
+        let signatures: Vec<VerifiedSignature> = Self::get_signatures(revision.id)
+            .into_iter()
+            .map(|signature| VerifiedSignature {
+                content_hash_matches: signature.content_hash == content_hash,
+                signature,
+            })
+            .collect();
+        let is_valid = signatures.iter().enumerate().all(|(index, verified)| {
+            verified.content_hash_matches && verified.signature.sequence == index as u32
+        });
+        if !is_valid {
+            log_warn!(
+                "revision_signature: Security event - signature verification failed [revision_id={}, content_hash={}, principal={}]",
+                revision.id,
+                content_hash,
+                principal
+            );
+        }
+        VerifySignaturesResult::Ok(SignatureVerification {
+            revision_id: revision.id,
+            content_hash,
+            signatures,
+            is_valid,
+        })
+    }
+}
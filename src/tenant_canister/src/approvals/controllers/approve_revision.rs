@@ -0,0 +1,7 @@
+use crate::approvals::approvals_manager::ApprovalsManager;
+use shared::types::approvals::{ApproveRevisionInput, RevisionStatusResult};
+
+#[ic_cdk_macros::update]
+pub fn approve_revision(input: ApproveRevisionInput) -> RevisionStatusResult {
+    ApprovalsManager::approve_revision(input)
+}
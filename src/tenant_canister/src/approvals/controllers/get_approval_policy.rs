@@ -0,0 +1,7 @@
+use crate::approvals::approvals_manager::ApprovalsManager;
+use shared::types::approvals::{GetApprovalPolicyInput, GetApprovalPolicyResult};
+
+#[ic_cdk_macros::query]
+pub fn get_approval_policy(input: GetApprovalPolicyInput) -> GetApprovalPolicyResult {
+    ApprovalsManager::get_approval_policy(input)
+}
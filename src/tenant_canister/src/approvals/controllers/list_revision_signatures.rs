@@ -0,0 +1,9 @@
+use crate::approvals::approvals_manager::ApprovalsManager;
+use shared::types::approvals::{ListRevisionSignaturesInput, ListRevisionSignaturesResult};
+
+#[ic_cdk_macros::query]
+pub fn list_revision_signatures(
+    input: ListRevisionSignaturesInput,
+) -> ListRevisionSignaturesResult {
+    ApprovalsManager::list_revision_signatures(input)
+}
@@ -0,0 +1,10 @@
+pub mod approve_revision;
+pub mod get_approval_policy;
+pub mod list_revision_signatures;
+pub mod obsolete_revision;
+pub mod reject_revision;
+pub mod release_revision;
+pub mod request_signing_challenge;
+pub mod set_approval_policy;
+pub mod submit_revision_for_review;
+pub mod verify_signatures;
@@ -0,0 +1,7 @@
+use crate::approvals::approvals_manager::ApprovalsManager;
+use shared::types::approvals::{ObsoleteRevisionInput, RevisionStatusResult};
+
+#[ic_cdk_macros::update]
+pub fn obsolete_revision(input: ObsoleteRevisionInput) -> RevisionStatusResult {
+    ApprovalsManager::obsolete_revision(input)
+}
@@ -0,0 +1,7 @@
+use crate::approvals::approvals_manager::ApprovalsManager;
+use shared::types::approvals::{RejectRevisionInput, RevisionStatusResult};
+
+#[ic_cdk_macros::update]
+pub fn reject_revision(input: RejectRevisionInput) -> RevisionStatusResult {
+    ApprovalsManager::reject_revision(input)
+}
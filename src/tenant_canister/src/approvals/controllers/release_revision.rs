@@ -0,0 +1,7 @@
+use crate::approvals::approvals_manager::ApprovalsManager;
+use shared::types::approvals::{ReleaseRevisionInput, RevisionStatusResult};
+
+#[ic_cdk_macros::update]
+pub fn release_revision(input: ReleaseRevisionInput) -> RevisionStatusResult {
+    ApprovalsManager::release_revision(input)
+}
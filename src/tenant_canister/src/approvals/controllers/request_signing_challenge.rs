@@ -0,0 +1,9 @@
+use crate::approvals::approvals_manager::ApprovalsManager;
+use shared::types::approvals::{RequestSigningChallengeInput, RequestSigningChallengeResult};
+
+#[ic_cdk_macros::update]
+pub async fn request_signing_challenge(
+    input: RequestSigningChallengeInput,
+) -> RequestSigningChallengeResult {
+    ApprovalsManager::request_signing_challenge(input).await
+}
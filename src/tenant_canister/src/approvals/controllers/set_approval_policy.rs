@@ -0,0 +1,7 @@
+use crate::approvals::approvals_manager::ApprovalsManager;
+use shared::types::approvals::{SetApprovalPolicyInput, SetApprovalPolicyResult};
+
+#[ic_cdk_macros::update]
+pub fn set_approval_policy(input: SetApprovalPolicyInput) -> SetApprovalPolicyResult {
+    ApprovalsManager::set_approval_policy(input)
+}
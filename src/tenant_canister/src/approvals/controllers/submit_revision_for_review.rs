@@ -0,0 +1,7 @@
+use crate::approvals::approvals_manager::ApprovalsManager;
+use shared::types::approvals::{RevisionStatusResult, SubmitRevisionForReviewInput};
+
+#[ic_cdk_macros::update]
+pub fn submit_revision_for_review(input: SubmitRevisionForReviewInput) -> RevisionStatusResult {
+    ApprovalsManager::submit_revision_for_review(input)
+}
@@ -0,0 +1,7 @@
+use crate::approvals::approvals_manager::ApprovalsManager;
+use shared::types::approvals::{VerifySignaturesInput, VerifySignaturesResult};
+
+#[ic_cdk_macros::query]
+pub fn verify_signatures(input: VerifySignaturesInput) -> VerifySignaturesResult {
+    ApprovalsManager::verify_signatures(input)
+}
@@ -0,0 +1,2 @@
+pub mod approvals_manager;
+pub mod controllers;
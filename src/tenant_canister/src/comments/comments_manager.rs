@@ -0,0 +1,539 @@
+use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap};
+use shared::consts::comments::MAX_COMMENT_LENGTH;
+use shared::consts::memory_ids::tenant_canister::COMMENTS_MEMORY_ID;
+use shared::types::access_control::{DocumentPermission, Permission};
+use shared::types::comments::{
+    Comment, CommentAnchor, CommentEdit, CommentId, CommentIdInput, CommentStatus,
+    CreateCommentInput, CreateCommentResult, ListCommentsInput, ListCommentsResult,
+    ReopenCommentThreadResult, ResolveCommentThreadResult, UpdateCommentInput, UpdateCommentResult,
+};
+use shared::types::documents::{Document, DocumentId};
+use shared::types::errors::AppError;
+use shared::types::revisions::{Revision, RevisionContentData};
+use shared::types::users::{User, UserId};
+use shared::utils::pagination::paginate;
+use shared::{log_debug, log_info, log_warn};
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::documents::DocumentManager;
+use crate::notifications::NotificationsManager;
+use crate::revisions::revisions_manager::RevisionsManager;
+use crate::users::user_manager::UserManager;
+
+type Memory = VirtualMemory<DefaultMemoryImpl>;
+
+thread_local! {
+    static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
+        RefCell::new(MemoryManager::init(DefaultMemoryImpl::default()));
+
+    static COMMENTS: RefCell<StableBTreeMap<CommentId, Comment, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(COMMENTS_MEMORY_ID))),
+        )
+    );
+
+    static NEXT_ID: AtomicU64 = const { AtomicU64::new(0) };
+}
+
+pub struct CommentsManager;
+
+impl CommentsManager {
+    fn get_next_id() -> CommentId {
+        NEXT_ID.with(|id| id.fetch_add(1, Ordering::SeqCst))
+    }
+
+    fn insert(id: CommentId, comment: Comment) {
+        COMMENTS.with(|comments| {
+            comments.borrow_mut().insert(id, comment);
+        });
+    }
+
+    pub fn get_by_id(id: CommentId) -> Option<Comment> {
+        COMMENTS.with(|comments| comments.borrow().get(&id))
+    }
+
+    pub fn get_all() -> Vec<Comment> {
+        COMMENTS.with(|comments| {
+            comments
+                .borrow()
+                .iter()
+                .map(|(_, comment)| comment)
+                .collect()
+        })
+    }
+
+    fn authorize(
+        user: &User,
+        document: &Document,
+        permission: DocumentPermission,
+        operation: &str,
+    ) -> Result<(), AppError> {
+        if DocumentManager::has_permission(
+            user.id,
+            document,
+            &Permission::Document(permission.clone()),
+        ) {
+            return Ok(());
+        }
+        log_warn!(
+            "access_control: Permission denied for {} [user_id={}, document_id={}, permission={:?}]",
+            operation,
+            user.id,
+            document.id,
+            permission
+        );
+        Err(AppError::Unauthorized)
+    }
+
+    fn get_existing(id: CommentId) -> Result<Comment, AppError> {
+        Self::get_by_id(id).ok_or_else(|| AppError::EntityNotFound("Comment not found".to_string()))
+    }
+
+    fn get_document(document_id: DocumentId) -> Result<Document, AppError> {
+        DocumentManager::get_by_id(document_id)
+            .ok_or_else(|| AppError::EntityNotFound("Document not found".to_string()))
+    }
+
+    fn validate_body(body: &str) -> Result<(), AppError> {
+        if body.trim().is_empty() {
+            return Err(AppError::ValidationError(
+                "Comment cannot be empty".to_string(),
+            ));
+        }
+        if body.chars().count() > MAX_COMMENT_LENGTH {
+            return Err(AppError::ValidationError(format!(
+                "Comment exceeds the maximum length of {} characters",
+                MAX_COMMENT_LENGTH
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_mentions(mentions: &[UserId]) -> Result<Vec<UserId>, AppError> {
+        let mut mentions = mentions.to_vec();
+        mentions.sort();
+        mentions.dedup();
+        if let Some(unknown) = mentions
+            .iter()
+            .find(|user_id| UserManager::get_by_id(**user_id).is_none())
+        {
+            return Err(AppError::EntityNotFound(format!(
+                "Mentioned user {} not found",
+                unknown
+            )));
+        }
+        Ok(mentions)
+    }
+
+    fn validate_anchor(revision: &Revision, anchor: &CommentAnchor) -> Result<(), AppError> {
+        if !revision.contents.contains(&anchor.content_id) {
+            return Err(AppError::ValidationError(
+                "Anchored content is not part of the revision".to_string(),
+            ));
+        }
+        let Some(range) = &anchor.range else {
+            return Ok(());
+        };
+        if range.start >= range.end {
+            return Err(AppError::ValidationError(
+                "Anchor range must not be empty".to_string(),
+            ));
+        }
+        let content = RevisionsManager::get_revision_content_by_id(anchor.content_id)
+            .ok_or_else(|| AppError::EntityNotFound("Anchored content not found".to_string()))?;
+        if let Some(RevisionContentData::Direct { bytes }) = content.content_data {
+            let length = String::from_utf8_lossy(&bytes).chars().count();
+            if range.end as usize > length {
+                return Err(AppError::ValidationError(
+                    "Anchor range exceeds the content length".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn create_comment(input: CreateCommentInput) -> CreateCommentResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Comment creation attempt [principal={}, revision_id={}, parent_id={:?}]",
+            principal,
+            input.revision_id,
+            input.parent_id
+        );
+
+        let user = match UserManager::authenticate(principal, "comment creation") {
+            Ok(user) => user,
+            Err(e) => return CreateCommentResult::Err(e),
+        };
+        let revision = match RevisionsManager::get_by_id(input.revision_id) {
+            Some(revision) => revision,
+            None => {
+                log_warn!(
+                    "comment_creation: Revision not found [revision_id={}, principal={}]",
+                    input.revision_id,
+                    principal
+                );
+                return CreateCommentResult::Err(AppError::EntityNotFound(
+                    "Revision not found".to_string(),
+                ));
+            }
+        };
+        let document = match DocumentManager::get_by_id(revision.document_id) {
+            Some(document) => document,
+            None => {
+                log_warn!(
+                    "comment_creation: Document not found [document_id={}, revision_id={}, principal={}]",
+                    revision.document_id,
+                    revision.id,
+                    principal
+                );
+                return CreateCommentResult::Err(AppError::EntityNotFound(
+                    "Document not found".to_string(),
+                ));
+            }
+        };
+        if let Err(e) = Self::authorize(
+            &user,
+            &document,
+            DocumentPermission::Comment,
+            "comment creation",
+        ) {
+            return CreateCommentResult::Err(e);
+        }
+        if let Err(e) = Self::validate_body(&input.body) {
+            log_warn!(
+                "comment_creation: Invalid comment body [revision_id={}, principal={}] - {:?}",
+                revision.id,
+                principal,
+                e
+            );
+            return CreateCommentResult::Err(e);
+        }
+        let mentions = match Self::validate_mentions(&input.mentions) {
+            Ok(mentions) => mentions,
+            Err(e) => {
+                log_warn!(
+                    "comment_creation: Invalid mentions [revision_id={}, principal={}] - {:?}",
+                    revision.id,
+                    principal,
+                    e
+                );
+                return CreateCommentResult::Err(e);
+            }
+        };
+
+        let parent_id = match input.parent_id {
+            Some(parent_id) => {
+                let parent = match Self::get_by_id(parent_id) {
+                    Some(parent) => parent,
+                    None => {
+                        log_warn!(
+                            "comment_creation: Parent comment not found [parent_id={}, principal={}]",
+                            parent_id,
+                            principal
+                        );
+                        return CreateCommentResult::Err(AppError::EntityNotFound(
+                            "Comment not found".to_string(),
+                        ));
+                    }
+                };
+                if parent.revision_id != revision.id {
+                    log_warn!(
+                        "comment_creation: Reply on another revision than its thread [parent_id={}, revision_id={}, principal={}]",
+                        parent.id,
+                        revision.id,
+                        principal
+                    );
+                    return CreateCommentResult::Err(AppError::ValidationError(
+                        "Replies must belong to the same revision as their thread".to_string(),
+                    ));
+                }
+                if input.anchor.is_some() {
+                    log_warn!(
+                        "comment_creation: Anchored reply [parent_id={}, principal={}]",
+                        parent.id,
+                        principal
+                    );
+                    return CreateCommentResult::Err(AppError::ValidationError(
+                        "Only the first comment of a thread can be anchored".to_string(),
+                    ));
+                }
+                Some(parent.parent_id.unwrap_or(parent.id))
+            }
+            None => None,
+        };
+
+        if let Some(anchor) = &input.anchor {
+            if let Err(e) = Self::validate_anchor(&revision, anchor) {
+                log_warn!(
+                    "comment_creation: Invalid anchor [revision_id={}, content_id={}, principal={}] - {:?}",
+                    revision.id,
+                    anchor.content_id,
+                    principal,
+                    e
+                );
+                return CreateCommentResult::Err(e);
+            }
+        }
+
+        let id = Self::get_next_id();
+        let comment = Comment {
+            id,
+            document_id: document.id,
+            revision_id: revision.id,
+            project_id: document.project_id,
+            parent_id,
+            anchor: input.anchor,
+            body: input.body,
+            mentions,
+            status: CommentStatus::Open,
+            created_at: ic_cdk::api::time(),
+            created_by: user.id,
+            updated_at: None,
+            resolved_at: None,
+            resolved_by: None,
+            edit_history: vec![],
+        };
+        Self::insert(id, comment.clone());
+        NotificationsManager::notify_comment(&document, &comment);
+        log_info!(
+            "comment_creation: Created comment [id={}, document_id={}, revision_id={}, parent_id={:?}, mentions={:?}, user_id={}, principal={}]",
+            comment.id,
+            comment.document_id,
+            comment.revision_id,
+            comment.parent_id,
+            comment.mentions,
+            user.id,
+            principal
+        );
+        CreateCommentResult::Ok(id)
+    }
+
+    pub fn update_comment(input: UpdateCommentInput) -> UpdateCommentResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Comment update attempt [principal={}, id={}]",
+            principal,
+            input.id
+        );
+
+        let user = match UserManager::authenticate(principal, "comment update") {
+            Ok(user) => user,
+            Err(e) => return UpdateCommentResult::Err(e),
+        };
+        let mut comment = match Self::get_by_id(input.id) {
+            Some(comment) => comment,
+            None => {
+                log_warn!(
+                    "comment_update: Comment not found [id={}, principal={}]",
+                    input.id,
+                    principal
+                );
+                return UpdateCommentResult::Err(AppError::EntityNotFound(
+                    "Comment not found".to_string(),
+                ));
+            }
+        };
+        if comment.created_by != user.id {
+            log_warn!(
+                "access_control: Only the author can edit a comment [id={}, author={}, user_id={}]",
+                comment.id,
+                comment.created_by,
+                user.id
+            );
+            return UpdateCommentResult::Err(AppError::Unauthorized);
+        }
+        let document = match DocumentManager::get_by_id(comment.document_id) {
+            Some(document) => document,
+            None => {
+                log_warn!(
+                    "comment_update: Document not found [id={}, document_id={}, principal={}]",
+                    comment.id,
+                    comment.document_id,
+                    principal
+                );
+                return UpdateCommentResult::Err(AppError::EntityNotFound(
+                    "Document not found".to_string(),
+                ));
+            }
+        };
+        if let Err(e) =
+            Self::authorize(&user, &document, DocumentPermission::Read, "comment update")
+        {
+            return UpdateCommentResult::Err(e);
+        }
+        if let Err(e) = Self::validate_body(&input.body) {
+            log_warn!(
+                "comment_update: Invalid comment body [id={}, principal={}] - {:?}",
+                comment.id,
+                principal,
+                e
+            );
+            return UpdateCommentResult::Err(e);
+        }
+        let mentions = match Self::validate_mentions(&input.mentions) {
+            Ok(mentions) => mentions,
+            Err(e) => {
+                log_warn!(
+                    "comment_update: Invalid mentions [id={}, principal={}] - {:?}",
+                    comment.id,
+                    principal,
+                    e
+                );
+                return UpdateCommentResult::Err(e);
+            }
+        };
+
+        let now = ic_cdk::api::time();
+        comment.edit_history.push(CommentEdit {
+            body: std::mem::replace(&mut comment.body, input.body),
+            mentions: std::mem::replace(&mut comment.mentions, mentions),
+            edited_at: now,
+        });
+        comment.updated_at = Some(now);
+        Self::insert(comment.id, comment.clone());
+        log_info!(
+            "comment_update: Edited comment [id={}, edits={}, mentions={:?}, principal={}]",
+            comment.id,
+            comment.edit_history.len(),
+            comment.mentions,
+            principal
+        );
+        UpdateCommentResult::Ok
+    }
+
+    fn set_thread_status(id: CommentId, status: CommentStatus) -> Result<(), AppError> {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Comment thread status change attempt [principal={}, id={}, status={:?}]",
+            principal,
+            id,
+            status
+        );
+
+        let user = UserManager::authenticate(principal, "comment thread status change")?;
+        let comment = Self::get_existing(id)?;
+        let mut thread = match comment.parent_id {
+            Some(root_id) => Self::get_existing(root_id)?,
+            None => comment,
+        };
+        let document = Self::get_document(thread.document_id)?;
+        Self::authorize(
+            &user,
+            &document,
+            DocumentPermission::Comment,
+            "comment thread status change",
+        )?;
+
+        if thread.status == status {
+            return Err(AppError::InvalidStateTransition(format!(
+                "Comment thread is already {:?}",
+                status
+            )));
+        }
+
+        let now = ic_cdk::api::time();
+        match status {
+            CommentStatus::Resolved => {
+                thread.resolved_at = Some(now);
+                thread.resolved_by = Some(user.id);
+            }
+            CommentStatus::Open => {
+                thread.resolved_at = None;
+                thread.resolved_by = None;
+            }
+        }
+        thread.status = status;
+        thread.updated_at = Some(now);
+        Self::insert(thread.id, thread.clone());
+        log_info!(
+            "comment_update: Changed thread status [id={}, status={:?}, user_id={}, principal={}]",
+            thread.id,
+            thread.status,
+            user.id,
+            principal
+        );
+        Ok(())
+    }
+
+    pub fn resolve_comment_thread(input: CommentIdInput) -> ResolveCommentThreadResult {
+        match Self::set_thread_status(input.id, CommentStatus::Resolved) {
+            Ok(()) => ResolveCommentThreadResult::Ok,
+            Err(e) => {
+                log_warn!(
+                    "comment_update: Failed to resolve thread [id={}] - {:?}",
+                    input.id,
+                    e
+                );
+                ResolveCommentThreadResult::Err(e)
+            }
+        }
+    }
+
+    pub fn reopen_comment_thread(input: CommentIdInput) -> ReopenCommentThreadResult {
+        match Self::set_thread_status(input.id, CommentStatus::Open) {
+            Ok(()) => ReopenCommentThreadResult::Ok,
+            Err(e) => {
+                log_warn!(
+                    "comment_update: Failed to reopen thread [id={}] - {:?}",
+                    input.id,
+                    e
+                );
+                ReopenCommentThreadResult::Err(e)
+            }
+        }
+    }
+
+    pub fn list_comments(input: ListCommentsInput) -> ListCommentsResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "comment_listing: Processing request [principal={}, page={}, size={}]",
+            principal,
+            input.pagination.page_number,
+            input.pagination.page_size
+        );
+
+        let user = match UserManager::authenticate(principal, "comment listing") {
+            Ok(user) => user,
+            Err(e) => return ListCommentsResult::Err(e),
+        };
+
+        let read = Permission::Document(DocumentPermission::Read);
+        let comments: Vec<Comment> = Self::get_all()
+            .into_iter()
+            .filter(|comment| {
+                DocumentManager::get_by_id(comment.document_id).is_some_and(|document| {
+                    DocumentManager::has_permission(user.id, &document, &read)
+                })
+            })
+            .collect();
+
+        match paginate(
+            &comments,
+            input.pagination.page_size,
+            input.pagination.page_number,
+            input.pagination.filters,
+            input.pagination.sort,
+        ) {
+            Ok(result) => {
+                log_debug!(
+                    "comment_listing: Listed comments [principal={}, page_items={}, total={}]",
+                    principal,
+                    result.0.len(),
+                    comments.len()
+                );
+                ListCommentsResult::Ok(result)
+            }
+            Err(e) => {
+                log_warn!(
+                    "comment_listing: Pagination failed [principal={}] - {:?}",
+                    principal,
+                    e
+                );
+                ListCommentsResult::Err(e)
+            }
+        }
+    }
+}
@@ -0,0 +1,7 @@
+use crate::comments::comments_manager::CommentsManager;
+use shared::types::comments::{CreateCommentInput, CreateCommentResult};
+
+#[ic_cdk_macros::update]
+pub fn create_comment(input: CreateCommentInput) -> CreateCommentResult {
+    CommentsManager::create_comment(input)
+}
@@ -0,0 +1,7 @@
+use crate::comments::comments_manager::CommentsManager;
+use shared::types::comments::{ListCommentsInput, ListCommentsResult};
+
+#[ic_cdk_macros::query]
+pub fn list_comments(input: ListCommentsInput) -> ListCommentsResult {
+    CommentsManager::list_comments(input)
+}
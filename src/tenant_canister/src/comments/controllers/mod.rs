@@ -0,0 +1,5 @@
+pub mod create_comment;
+pub mod list_comments;
+pub mod reopen_comment_thread;
+pub mod resolve_comment_thread;
+pub mod update_comment;
@@ -0,0 +1,7 @@
+use crate::comments::comments_manager::CommentsManager;
+use shared::types::comments::{CommentIdInput, ReopenCommentThreadResult};
+
+#[ic_cdk_macros::update]
+pub fn reopen_comment_thread(input: CommentIdInput) -> ReopenCommentThreadResult {
+    CommentsManager::reopen_comment_thread(input)
+}
@@ -0,0 +1,7 @@
+use crate::comments::comments_manager::CommentsManager;
+use shared::types::comments::{CommentIdInput, ResolveCommentThreadResult};
+
+#[ic_cdk_macros::update]
+pub fn resolve_comment_thread(input: CommentIdInput) -> ResolveCommentThreadResult {
+    CommentsManager::resolve_comment_thread(input)
+}
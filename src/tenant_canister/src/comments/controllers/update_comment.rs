@@ -0,0 +1,7 @@
+use crate::comments::comments_manager::CommentsManager;
+use shared::types::comments::{UpdateCommentInput, UpdateCommentResult};
+
+#[ic_cdk_macros::update]
+pub fn update_comment(input: UpdateCommentInput) -> UpdateCommentResult {
+    CommentsManager::update_comment(input)
+}
@@ -0,0 +1,2 @@
+pub mod comments_manager;
+pub mod controllers;
@@ -0,0 +1,7 @@
+use crate::document_links::document_links_manager::DocumentLinksManager;
+use shared::types::document_links::{CreateDocumentLinkInput, CreateDocumentLinkResult};
+
+#[ic_cdk_macros::update]
+pub fn create_document_link(input: CreateDocumentLinkInput) -> CreateDocumentLinkResult {
+    DocumentLinksManager::create_document_link(input)
+}
@@ -0,0 +1,7 @@
+use crate::document_links::document_links_manager::DocumentLinksManager;
+use shared::types::document_links::{DeleteDocumentLinkInput, DeleteDocumentLinkResult};
+
+#[ic_cdk_macros::update]
+pub fn delete_document_link(input: DeleteDocumentLinkInput) -> DeleteDocumentLinkResult {
+    DocumentLinksManager::delete_document_link(input)
+}
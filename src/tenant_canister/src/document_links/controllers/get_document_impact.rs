@@ -0,0 +1,7 @@
+use crate::document_links::document_links_manager::DocumentLinksManager;
+use shared::types::document_links::{GetDocumentImpactInput, GetDocumentImpactResult};
+
+#[ic_cdk_macros::query]
+pub fn get_document_impact(input: GetDocumentImpactInput) -> GetDocumentImpactResult {
+    DocumentLinksManager::get_document_impact(input)
+}
@@ -0,0 +1,7 @@
+use crate::document_links::document_links_manager::DocumentLinksManager;
+use shared::types::document_links::{ListDocumentLinksInput, ListDocumentLinksResult};
+
+#[ic_cdk_macros::query]
+pub fn list_document_links(input: ListDocumentLinksInput) -> ListDocumentLinksResult {
+    DocumentLinksManager::list_document_links(input)
+}
@@ -0,0 +1,4 @@
+pub mod create_document_link;
+pub mod delete_document_link;
+pub mod get_document_impact;
+pub mod list_document_links;
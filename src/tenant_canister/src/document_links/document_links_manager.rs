@@ -0,0 +1,436 @@
+use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap};
+use shared::consts::memory_ids::tenant_canister::DOCUMENT_LINKS_MEMORY_ID;
+use shared::types::access_control::{DocumentPermission, Permission};
+use shared::types::document_links::{
+    CreateDocumentLinkInput, CreateDocumentLinkResult, DeleteDocumentLinkInput,
+    DeleteDocumentLinkResult, DocumentImpactItem, DocumentImpactReport, DocumentLink,
+    DocumentLinkDirection, DocumentLinkId, GetDocumentImpactInput, GetDocumentImpactResult,
+    ListDocumentLinksInput, ListDocumentLinksResult,
+};
+use shared::types::documents::{Document, DocumentId};
+use shared::types::errors::AppError;
+use shared::types::revisions::RevisionId;
+use shared::types::users::{User, UserId};
+use shared::{log_debug, log_info, log_warn};
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::documents;
+use crate::documents::DocumentManager;
+use crate::retention::RetentionManager;
+use crate::users::user_manager::UserManager;
+
+type Memory = VirtualMemory<DefaultMemoryImpl>;
+
+thread_local! {
+    static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
+        RefCell::new(MemoryManager::init(DefaultMemoryImpl::default()));
+
+    static DOCUMENT_LINKS: RefCell<StableBTreeMap<DocumentLinkId, DocumentLink, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(DOCUMENT_LINKS_MEMORY_ID))),
+        )
+    );
+
+    static NEXT_ID: AtomicU64 = const { AtomicU64::new(0) };
+}
+
+pub struct DocumentLinksManager;
+
+impl DocumentLinksManager {
+    fn get_next_id() -> DocumentLinkId {
+        NEXT_ID.with(|id| id.fetch_add(1, Ordering::SeqCst))
+    }
+
+    fn insert(id: DocumentLinkId, link: DocumentLink) {
+        DOCUMENT_LINKS.with(|links| {
+            links.borrow_mut().insert(id, link);
+        });
+    }
+
+    fn authorize(
+        user: &User,
+        document: &Document,
+        permission: DocumentPermission,
+        operation: &str,
+    ) -> Result<(), AppError> {
+        if !DocumentManager::has_permission(user.id, document, &Permission::Document(permission)) {
+            log_warn!(
+                "access_control: Permission denied for {} [user_id={}, document_id={}]",
+                operation,
+                user.id,
+                document.id
+            );
+            return Err(AppError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn can_read(user_id: UserId, document_id: DocumentId) -> bool {
+        documents::get_by_id(document_id).is_some_and(|document| {
+            DocumentManager::has_permission(
+                user_id,
+                &document,
+                &Permission::Document(DocumentPermission::Read),
+            )
+        })
+    }
+
+    pub fn get_outgoing(document_id: DocumentId) -> Vec<DocumentLink> {
+        DOCUMENT_LINKS.with(|links| {
+            links
+                .borrow()
+                .iter()
+                .filter(|(_, link)| link.source_document_id == document_id)
+                .map(|(_, link)| link)
+                .collect()
+        })
+    }
+
+    pub fn get_incoming(document_id: DocumentId) -> Vec<DocumentLink> {
+        DOCUMENT_LINKS.with(|links| {
+            links
+                .borrow()
+                .iter()
+                .filter(|(_, link)| link.target_document_id == document_id)
+                .map(|(_, link)| link)
+                .collect()
+        })
+    }
+
+    pub fn create_document_link(input: CreateDocumentLinkInput) -> CreateDocumentLinkResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Document link creation attempt [principal={}, source={}, target={}, type={:?}]",
+            principal,
+            input.source_document_id,
+            input.target_document_id,
+            input.link_type
+        );
+
+        let user = match UserManager::authenticate(principal, "document link creation") {
+            Ok(user) => user,
+            Err(e) => return CreateDocumentLinkResult::Err(e),
+        };
+
+        if input.source_document_id == input.target_document_id {
+            log_warn!(
+                "document_link_creation: Validation failed - self reference [document_id={}, principal={}]",
+                input.source_document_id,
+                principal
+            );
+            return CreateDocumentLinkResult::Err(AppError::ValidationError(
+                "A document cannot link to itself".to_string(),
+            ));
+        }
+
+        let source = match documents::get_by_id(input.source_document_id) {
+            Some(document) => document,
+            None => {
+                log_warn!(
+                    "document_link_creation: Source document not found [document_id={}, principal={}]",
+                    input.source_document_id,
+                    principal
+                );
+                return CreateDocumentLinkResult::Err(AppError::EntityNotFound(
+                    "Source document not found".to_string(),
+                ));
+            }
+        };
+
+        let target = match documents::get_by_id(input.target_document_id) {
+            Some(document) => document,
+            None => {
+                log_warn!(
+                    "document_link_creation: Target document not found [document_id={}, principal={}]",
+                    input.target_document_id,
+                    principal
+                );
+                return CreateDocumentLinkResult::Err(AppError::EntityNotFound(
+                    "Target document not found".to_string(),
+                ));
+            }
+        };
+
+        if let Err(e) = Self::authorize(
+            &user,
+            &source,
+            DocumentPermission::Update,
+            "document link creation",
+        )
+        .and_then(|_| {
+            Self::authorize(
+                &user,
+                &target,
+                DocumentPermission::Read,
+                "document link creation",
+            )
+        }) {
+            return CreateDocumentLinkResult::Err(e);
+        }
+
+        let duplicate = Self::get_outgoing(input.source_document_id)
+            .iter()
+            .any(|link| {
+                link.target_document_id == input.target_document_id
+                    && link.link_type == input.link_type
+            });
+        if duplicate {
+            log_warn!(
+                "document_link_creation: Duplicate link [source={}, target={}, type={:?}, principal={}]",
+                input.source_document_id,
+                input.target_document_id,
+                input.link_type,
+                principal
+            );
+            return CreateDocumentLinkResult::Err(AppError::ValidationError(
+                "An identical link between these documents already exists".to_string(),
+            ));
+        }
+
+        let id = Self::get_next_id();
+        let link = DocumentLink {
+            id,
+            source_document_id: input.source_document_id,
+            target_document_id: input.target_document_id,
+            link_type: input.link_type,
+            project_id: source.project_id,
+            created_at: ic_cdk::api::time(),
+            created_by: user.id,
+        };
+
+        Self::insert(id, link.clone());
+        log_info!(
+            "document_link_creation: Created link [id={}, source={}, target={}, type={:?}, user_id={}, principal={}]",
+            link.id,
+            link.source_document_id,
+            link.target_document_id,
+            link.link_type,
+            user.id,
+            principal
+        );
+
+        CreateDocumentLinkResult::Ok(id)
+    }
+
+    pub fn delete_document_link(input: DeleteDocumentLinkInput) -> DeleteDocumentLinkResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Document link deletion attempt [principal={}, id={}]",
+            principal,
+            input.id
+        );
+
+        let user = match UserManager::authenticate(principal, "document link deletion") {
+            Ok(user) => user,
+            Err(e) => return DeleteDocumentLinkResult::Err(e),
+        };
+
+        let Some(link) = DOCUMENT_LINKS.with(|links| links.borrow().get(&input.id)) else {
+            log_warn!(
+                "document_link_deletion: Link not found [id={}, principal={}]",
+                input.id,
+                principal
+            );
+            return DeleteDocumentLinkResult::Err(AppError::EntityNotFound(
+                "Document link not found".to_string(),
+            ));
+        };
+
+        let Some(source) = documents::get_by_id(link.source_document_id) else {
+            return DeleteDocumentLinkResult::Err(AppError::EntityNotFound(
+                "Source document not found".to_string(),
+            ));
+        };
+        if let Err(e) = Self::authorize(
+            &user,
+            &source,
+            DocumentPermission::Update,
+            "document link deletion",
+        ) {
+            return DeleteDocumentLinkResult::Err(e);
+        }
+
+        let held = [link.source_document_id, link.target_document_id]
+            .into_iter()
+            .filter_map(documents::get_by_id)
+            .any(|document| RetentionManager::is_on_hold(&document));
+        if held {
+            log_warn!(
+                "document_link_deletion: Link of a document under legal hold [id={}, principal={}]",
+                input.id,
+                principal
+            );
+            return DeleteDocumentLinkResult::Err(AppError::InvalidStateTransition(
+                "Links of documents under legal hold cannot be deleted".to_string(),
+            ));
+        }
+
+        DOCUMENT_LINKS.with(|links| links.borrow_mut().remove(&input.id));
+        log_info!(
+            "document_link_deletion: Deleted link [id={}, source={}, target={}, type={:?}, user_id={}, principal={}]",
+            link.id,
+            link.source_document_id,
+            link.target_document_id,
+            link.link_type,
+            user.id,
+            principal
+        );
+        DeleteDocumentLinkResult::Ok
+    }
+
+    pub fn list_document_links(input: ListDocumentLinksInput) -> ListDocumentLinksResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "document_link_listing: Processing request [principal={}, document_id={}, direction={:?}]",
+            principal,
+            input.document_id,
+            input.direction
+        );
+
+        let user = match UserManager::authenticate(principal, "document link listing") {
+            Ok(user) => user,
+            Err(e) => return ListDocumentLinksResult::Err(e),
+        };
+        let Some(document) = documents::get_by_id(input.document_id) else {
+            return ListDocumentLinksResult::Err(AppError::EntityNotFound(
+                "Document not found".to_string(),
+            ));
+        };
+        if let Err(e) = Self::authorize(
+            &user,
+            &document,
+            DocumentPermission::Read,
+            "document link listing",
+        ) {
+            return ListDocumentLinksResult::Err(e);
+        }
+
+        let mut links = match input.direction {
+            DocumentLinkDirection::Outgoing => Self::get_outgoing(input.document_id),
+            DocumentLinkDirection::Incoming => Self::get_incoming(input.document_id),
+            DocumentLinkDirection::Both => {
+                let mut links = Self::get_outgoing(input.document_id);
+                links.extend(Self::get_incoming(input.document_id));
+                links
+            }
+        };
+
+        if let Some(link_type) = input.link_type {
+            links.retain(|link| link.link_type == link_type);
+        }
+        // Links to documents the caller cannot read are left out
+        links.retain(|link| {
+            let other = if link.source_document_id == input.document_id {
+                link.target_document_id
+            } else {
+                link.source_document_id
+            };
+            Self::can_read(user.id, other)
+        });
+
+        log_debug!(
+            "document_link_listing: Listed links [principal={}, document_id={}, count={}]",
+            principal,
+            input.document_id,
+            links.len()
+        );
+        ListDocumentLinksResult::Ok(links)
+    }
+
+    pub fn build_impact_report(document_id: DocumentId) -> Result<DocumentImpactReport, AppError> {
+        let document = documents::get_by_id(document_id)
+            .ok_or_else(|| AppError::EntityNotFound("Document not found".to_string()))?;
+
+        let impacted = Self::get_incoming(document_id)
+            .into_iter()
+            .filter_map(|link| {
+                documents::get_by_id(link.source_document_id).map(|source| DocumentImpactItem {
+                    document: source,
+                    link,
+                })
+            })
+            .collect();
+
+        Ok(DocumentImpactReport {
+            document_id,
+            version: document.version,
+            latest_revision_id: document.revisions.last().copied(),
+            impacted,
+        })
+    }
+
+    pub fn get_document_impact(input: GetDocumentImpactInput) -> GetDocumentImpactResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "document_impact: Processing request [principal={}, document_id={}]",
+            principal,
+            input.document_id
+        );
+
+        let result =
+            UserManager::authenticate(principal, "document impact report").and_then(|user| {
+                let document = documents::get_by_id(input.document_id)
+                    .ok_or_else(|| AppError::EntityNotFound("Document not found".to_string()))?;
+                Self::authorize(
+                    &user,
+                    &document,
+                    DocumentPermission::Read,
+                    "document impact report",
+                )?;
+                let mut report = Self::build_impact_report(input.document_id)?;
+                // Impacted documents the caller cannot read are left out
+                report.impacted.retain(|item| {
+                    DocumentManager::has_permission(
+                        user.id,
+                        &item.document,
+                        &Permission::Document(DocumentPermission::Read),
+                    )
+                });
+                Ok(report)
+            });
+
+        match result {
+            Ok(report) => {
+                log_debug!(
+                    "document_impact: Built impact report [document_id={}, impacted={}, principal={}]",
+                    input.document_id,
+                    report.impacted.len(),
+                    principal
+                );
+                GetDocumentImpactResult::Ok(report)
+            }
+            Err(e) => {
+                log_warn!(
+                    "document_impact: Failed to build impact report [document_id={}, principal={}] - {:?}",
+                    input.document_id,
+                    principal,
+                    e
+                );
+                GetDocumentImpactResult::Err(e)
+            }
+        }
+    }
+
+    /// Records which linked documents are affected by a newly released revision.
+    pub fn log_revision_impact(document_id: DocumentId, revision_id: RevisionId) {
+        if let Ok(report) = Self::build_impact_report(document_id) {
+            if report.impacted.is_empty() {
+                return;
+            }
+            let impacted_ids: Vec<DocumentId> = report
+                .impacted
+                .iter()
+                .map(|item| item.document.id)
+                .collect();
+            log_info!(
+                "document_impact: Revision released with linked documents [document_id={}, revision_id={}, version={}, impacted={:?}]",
+                document_id,
+                revision_id,
+                report.version,
+                impacted_ids
+            );
+        }
+    }
+}
@@ -1,2 +1,4 @@
 pub mod create_document;
 pub mod list_documents;
+pub mod move_document;
+pub mod set_document_type;
@@ -0,0 +1,7 @@
+use crate::documents::document_manager::DocumentManager;
+use shared::types::folders::{MoveDocumentInput, MoveDocumentResult};
+
+#[ic_cdk_macros::update]
+pub fn move_document(input: MoveDocumentInput) -> MoveDocumentResult {
+    DocumentManager::move_document(input)
+}
@@ -0,0 +1,7 @@
+use crate::documents::document_manager::DocumentManager;
+use shared::types::documents::{SetDocumentTypeInput, SetDocumentTypeResult};
+
+#[ic_cdk_macros::update]
+pub fn set_document_type(input: SetDocumentTypeInput) -> SetDocumentTypeResult {
+    DocumentManager::set_document_type(input)
+}
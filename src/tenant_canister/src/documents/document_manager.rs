@@ -1,13 +1,21 @@
 use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
 use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap};
 use shared::consts::memory_ids::tenant_canister::DOCUMENTS_MEMORY_ID;
+use shared::consts::retention::MAX_DOCUMENT_TYPE_LENGTH;
+use shared::types::access_control::{DocumentPermission, Permission};
 use shared::types::documents::{
     CreateDocumentInput, CreateDocumentResult, Document, DocumentId, ListDocumentsInput,
-    ListDocumentsResult,
+    ListDocumentsResult, SetDocumentTypeInput, SetDocumentTypeResult,
 };
+use shared::types::errors::AppError;
+use shared::types::folders::{MoveDocumentInput, MoveDocumentResult};
+use shared::types::imports::ImportOrigin;
 use shared::types::revisions::RevisionId;
-use shared::types::users::GetUserResult;
+use shared::types::users::{GetUserResult, UserId};
 
+use crate::encryption::EncryptionManager;
+use crate::folders::FoldersManager;
+use crate::projects::projects_manager::ProjectsManager;
 use crate::users::user_manager::UserManager;
 use shared::utils::pagination::paginate;
 use shared::{log_debug, log_error, log_info, log_warn};
@@ -63,10 +71,54 @@ impl DocumentManager {
         });
     }
 
+    pub fn set_import_origin(document_id: DocumentId, origin: ImportOrigin) {
+        DOCUMENTS.with(|documents| {
+            let mut documents_ref = documents.borrow_mut();
+            if let Some(mut document) = documents_ref.get(&document_id) {
+                document.imported = Some(origin);
+                documents_ref.insert(document_id, document);
+            }
+        });
+    }
+
     pub fn get_by_id(document_id: DocumentId) -> Option<Document> {
         DOCUMENTS.with(|documents| documents.borrow().get(&document_id))
     }
 
+    /// Whether the user holds `permission` on the document. Project members can
+    /// always read; anything else comes from project roles or folder grants.
+    pub fn has_permission(user_id: UserId, document: &Document, permission: &Permission) -> bool {
+        if *permission == Permission::Document(DocumentPermission::Read)
+            && ProjectsManager::is_member(document.project_id, user_id)
+        {
+            return true;
+        }
+        FoldersManager::has_permission(user_id, document.project_id, document.folder_id, permission)
+    }
+
+    /// Trimmed, lowercase document type; blank types are treated as none.
+    pub fn normalize_document_type(
+        document_type: Option<String>,
+    ) -> Result<Option<String>, AppError> {
+        let Some(document_type) = document_type
+            .map(|document_type| document_type.trim().to_lowercase())
+            .filter(|document_type| !document_type.is_empty())
+        else {
+            return Ok(None);
+        };
+        if document_type.chars().count() > MAX_DOCUMENT_TYPE_LENGTH {
+            return Err(AppError::ValidationError(format!(
+                "Document type must not be longer than {} characters",
+                MAX_DOCUMENT_TYPE_LENGTH
+            )));
+        }
+        Ok(Some(document_type))
+    }
+
+    pub fn is_encrypted(document: &Document) -> bool {
+        document.encrypted.unwrap_or(false)
+    }
+
     pub fn create_document(input: CreateDocumentInput) -> CreateDocumentResult {
         let document_id = Self::get_next_id();
         let principal = ic_cdk::api::msg_caller();
@@ -119,6 +171,45 @@ impl DocumentManager {
             }
         };
 
+        if let Some(folder_id) = input.folder_id {
+            match FoldersManager::get_by_id(folder_id) {
+                Some(folder) if folder.project_id == input.project_id => {}
+                Some(_) => {
+                    log_warn!(
+                        "document_creation: Folder belongs to a different project [folder_id={}, project_id={}, principal={}]",
+                        folder_id,
+                        input.project_id,
+                        principal
+                    );
+                    return CreateDocumentResult::Err(AppError::ValidationError(
+                        "Folder belongs to a different project".to_string(),
+                    ));
+                }
+                None => {
+                    log_warn!(
+                        "document_creation: Folder not found [folder_id={}, principal={}]",
+                        folder_id,
+                        principal
+                    );
+                    return CreateDocumentResult::Err(AppError::EntityNotFound(
+                        "Folder not found".to_string(),
+                    ));
+                }
+            }
+        }
+
+        let document_type = match Self::normalize_document_type(input.document_type) {
+            Ok(document_type) => document_type,
+            Err(e) => {
+                log_warn!(
+                    "document_creation: Invalid document type [principal={}] - {:?}",
+                    principal,
+                    e
+                );
+                return CreateDocumentResult::Err(e);
+            }
+        };
+
         let document = Document {
             id: document_id,
             title: input.title,
@@ -127,6 +218,10 @@ impl DocumentManager {
             created_by: user.id,
             created_at: ic_cdk::api::time(),
             project_id: input.project_id,
+            folder_id: input.folder_id,
+            encrypted: input.encrypted,
+            imported: None,
+            document_type,
         };
 
         Self::insert(document_id, document.clone());
@@ -162,7 +257,43 @@ impl DocumentManager {
             input.pagination.page_size
         );
 
-        let documents = Self::get_all();
+        let mut documents = Self::get_all();
+        if let Some(folder_id) = input.folder_id {
+            let user = match UserManager::get_user_by_principal(principal) {
+                GetUserResult::Ok(u) => u,
+                GetUserResult::Err(e) => {
+                    log_warn!(
+                        "auth_check: Authentication failed for document listing [principal={}] - {:?}",
+                        principal,
+                        e
+                    );
+                    return ListDocumentsResult::Err(e);
+                }
+            };
+            if FoldersManager::get_by_id(folder_id).is_none() {
+                return ListDocumentsResult::Err(AppError::EntityNotFound(
+                    "Folder not found".to_string(),
+                ));
+            }
+            let folder_ids = if input.recursive.unwrap_or(false) {
+                FoldersManager::get_subtree_ids(folder_id)
+            } else {
+                vec![folder_id]
+            };
+            let read = Permission::Document(DocumentPermission::Read);
+            documents.retain(|document| {
+                document
+                    .folder_id
+                    .is_some_and(|id| folder_ids.contains(&id))
+                    && Self::has_permission(user.id, document, &read)
+            });
+            log_debug!(
+                "document_listing: Scoped to folder [folder_id={}, recursive={}, count={}]",
+                folder_id,
+                input.recursive.unwrap_or(false),
+                documents.len()
+            );
+        }
         log_debug!(
             "document_access: Retrieved documents [principal={}, total_count={}]",
             principal,
@@ -209,4 +340,159 @@ impl DocumentManager {
             }
         }
     }
+
+    pub fn move_document(input: MoveDocumentInput) -> MoveDocumentResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Document move attempt [principal={}, document_id={}, folder_id={:?}]",
+            principal,
+            input.document_id,
+            input.folder_id
+        );
+
+        let user = match UserManager::get_user_by_principal(principal) {
+            GetUserResult::Ok(u) => u,
+            GetUserResult::Err(e) => {
+                log_warn!(
+                    "auth_check: Authentication failed for document move [principal={}] - {:?}",
+                    principal,
+                    e
+                );
+                return MoveDocumentResult::Err(e);
+            }
+        };
+
+        let mut document = match Self::get_by_id(input.document_id) {
+            Some(document) => document,
+            None => {
+                return MoveDocumentResult::Err(AppError::EntityNotFound(
+                    "Document not found".to_string(),
+                ))
+            }
+        };
+
+        if let Some(folder_id) = input.folder_id {
+            match FoldersManager::get_by_id(folder_id) {
+                Some(folder) if folder.project_id == document.project_id => {}
+                Some(_) => {
+                    return MoveDocumentResult::Err(AppError::ValidationError(
+                        "Folder belongs to a different project".to_string(),
+                    ))
+                }
+                None => {
+                    return MoveDocumentResult::Err(AppError::EntityNotFound(
+                        "Folder not found".to_string(),
+                    ))
+                }
+            }
+        }
+
+        let update = Permission::Document(DocumentPermission::Update);
+        let allowed = Self::has_permission(user.id, &document, &update)
+            && FoldersManager::has_permission(
+                user.id,
+                document.project_id,
+                input.folder_id,
+                &update,
+            );
+        if !allowed {
+            log_warn!(
+                "access_control: Permission denied for document move [user_id={}, document_id={}, from={:?}, to={:?}]",
+                user.id,
+                document.id,
+                document.folder_id,
+                input.folder_id
+            );
+            return MoveDocumentResult::Err(AppError::Unauthorized);
+        }
+
+        let old_folder_id = document.folder_id;
+        document.folder_id = input.folder_id;
+        Self::insert(document.id, document.clone());
+        log_info!(
+            "document_update: Moved document [id={}, from={:?}, to={:?}, user_id={}, principal={}]",
+            document.id,
+            old_folder_id,
+            document.folder_id,
+            user.id,
+            principal
+        );
+        // Folder grants of the old folder no longer apply
+        EncryptionManager::rotate_revoked_keys();
+        MoveDocumentResult::Ok
+    }
+
+    /// Sets the document type, which selects the retention rule for its revisions.
+    pub fn set_document_type(input: SetDocumentTypeInput) -> SetDocumentTypeResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Document type update attempt [principal={}, document_id={}, document_type={:?}]",
+            principal,
+            input.document_id,
+            input.document_type
+        );
+
+        let user = match UserManager::get_user_by_principal(principal) {
+            GetUserResult::Ok(u) => u,
+            GetUserResult::Err(e) => {
+                log_warn!(
+                    "auth_check: Authentication failed for document type update [principal={}, document_id={}] - {:?}",
+                    principal,
+                    input.document_id,
+                    e
+                );
+                return SetDocumentTypeResult::Err(e);
+            }
+        };
+        let mut document = match Self::get_by_id(input.document_id) {
+            Some(document) => document,
+            None => {
+                log_warn!(
+                    "document_update: Document not found [id={}, principal={}]",
+                    input.document_id,
+                    principal
+                );
+                return SetDocumentTypeResult::Err(AppError::EntityNotFound(
+                    "Document not found".to_string(),
+                ));
+            }
+        };
+        if !Self::has_permission(
+            user.id,
+            &document,
+            &Permission::Document(DocumentPermission::Update),
+        ) {
+            log_warn!(
+                "access_control: Permission denied for document type update [user_id={}, document_id={}]",
+                user.id,
+                document.id
+            );
+            return SetDocumentTypeResult::Err(AppError::Unauthorized);
+        }
+        let document_type = match Self::normalize_document_type(input.document_type) {
+            Ok(document_type) => document_type,
+            Err(e) => {
+                log_warn!(
+                    "document_update: Invalid document type [id={}, principal={}] - {:?}",
+                    document.id,
+                    principal,
+                    e
+                );
+                return SetDocumentTypeResult::Err(e);
+            }
+        };
+
+        let old_document_type = document.document_type.take();
+        document.document_type = document_type;
+        Self::insert(document.id, document.clone());
+        log_info!(
+            "document_update: Document type changed [id={}, from={:?}, to={:?}, user_id={}, principal={}]",
+            document.id,
+            old_document_type,
+            document.document_type,
+            user.id,
+            principal
+        );
+        SetDocumentTypeResult::Ok
+    }
 }
@@ -0,0 +1,7 @@
+use crate::encryption::EncryptionManager;
+use shared::types::encryption::{DeriveDocumentKeyInput, DeriveDocumentKeyResult};
+
+#[ic_cdk_macros::update]
+async fn derive_document_key(input: DeriveDocumentKeyInput) -> DeriveDocumentKeyResult {
+    EncryptionManager::derive_document_key(input).await
+}
@@ -0,0 +1,7 @@
+use crate::encryption::EncryptionManager;
+use shared::types::encryption::GetEncryptionPublicKeyResult;
+
+#[ic_cdk_macros::update]
+async fn get_encryption_public_key() -> GetEncryptionPublicKeyResult {
+    EncryptionManager::get_encryption_public_key().await
+}
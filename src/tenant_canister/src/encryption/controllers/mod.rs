@@ -0,0 +1,3 @@
+pub mod derive_document_key;
+pub mod get_encryption_public_key;
+pub mod rotate_document_key;
@@ -0,0 +1,7 @@
+use crate::encryption::EncryptionManager;
+use shared::types::encryption::{RotateDocumentKeyInput, RotateDocumentKeyResult};
+
+#[ic_cdk_macros::update]
+pub fn rotate_document_key(input: RotateDocumentKeyInput) -> RotateDocumentKeyResult {
+    EncryptionManager::rotate_document_key(input)
+}
@@ -0,0 +1,350 @@
+use ic_cdk::management_canister::{
+    vetkd_derive_key, vetkd_public_key, VetKDCurve, VetKDDeriveKeyArgs, VetKDKeyId,
+    VetKDPublicKeyArgs,
+};
+use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap};
+use shared::consts::encryption::DOCUMENT_KEY_CONTEXT;
+use shared::consts::memory_ids::tenant_canister::DOCUMENT_KEYS_MEMORY_ID;
+use shared::types::access_control::{DocumentPermission, Permission};
+use shared::types::documents::{Document, DocumentId};
+use shared::types::encryption::{
+    ContentEncryption, DeriveDocumentKeyInput, DeriveDocumentKeyResult, DerivedDocumentKey,
+    DocumentKeyState, GetEncryptionPublicKeyResult, RotateDocumentKeyInput,
+    RotateDocumentKeyResult,
+};
+use shared::types::errors::AppError;
+use shared::{log_debug, log_info, log_warn};
+use std::cell::RefCell;
+
+use crate::documents::DocumentManager;
+use crate::env;
+use crate::users::user_manager::UserManager;
+
+type Memory = VirtualMemory<DefaultMemoryImpl>;
+
+thread_local! {
+    static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
+        RefCell::new(MemoryManager::init(DefaultMemoryImpl::default()));
+
+    // encrypted documents whose key has been derived or rotated
+    static DOCUMENT_KEYS: RefCell<StableBTreeMap<DocumentId, DocumentKeyState, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(DOCUMENT_KEYS_MEMORY_ID))),
+        )
+    );
+
+    // the public key does not change, fetched once per canister version
+    static PUBLIC_KEY: RefCell<Option<Vec<u8>>> = const { RefCell::new(None) };
+}
+
+/// Keys of encrypted documents. Content of an encrypted document is encrypted and
+/// decrypted by clients; the canister only stores ciphertext and derives per-document
+/// vetKD keys, encrypted for the caller's transport key, for users who may read the
+/// document. When a user who received a key loses access, the document key is rotated
+/// so content added afterwards is encrypted with a key they never received.
+pub struct EncryptionManager;
+
+impl EncryptionManager {
+    fn key_id() -> VetKDKeyId {
+        VetKDKeyId {
+            curve: VetKDCurve::Bls12_381_G2,
+            name: env::vetkd_key_name().to_string(),
+        }
+    }
+
+    /// vetKD input of a document key: the document ID followed by the key epoch.
+    fn key_input(document_id: DocumentId, epoch: u32) -> Vec<u8> {
+        let mut input = document_id.to_be_bytes().to_vec();
+        input.extend_from_slice(&epoch.to_be_bytes());
+        input
+    }
+
+    pub fn get_key_state(document_id: DocumentId) -> DocumentKeyState {
+        DOCUMENT_KEYS
+            .with(|keys| keys.borrow().get(&document_id))
+            .unwrap_or(DocumentKeyState {
+                document_id,
+                epoch: 0,
+                rotated_at: None,
+                key_holders: vec![],
+            })
+    }
+
+    fn insert_key_state(state: DocumentKeyState) {
+        DOCUMENT_KEYS.with(|keys| {
+            keys.borrow_mut().insert(state.document_id, state);
+        });
+    }
+
+    /// Content of encrypted documents must be encrypted, new content with the key of the
+    /// current epoch. Other documents only take plaintext content.
+    pub fn validate_content_encryption(
+        document: &Document,
+        encryption: Option<&ContentEncryption>,
+        is_new: bool,
+    ) -> Result<(), AppError> {
+        match (DocumentManager::is_encrypted(document), encryption) {
+            (true, None) => Err(AppError::ValidationError(
+                "Content of an encrypted document must be encrypted".to_string(),
+            )),
+            (true, Some(encryption)) if is_new => {
+                let epoch = Self::get_key_state(document.id).epoch;
+                if encryption.key_epoch == epoch {
+                    Ok(())
+                } else {
+                    Err(AppError::ValidationError(format!(
+                        "Content must be encrypted with the current document key (epoch {})",
+                        epoch
+                    )))
+                }
+            }
+            (true, Some(_)) | (false, None) => Ok(()),
+            (false, Some(_)) => Err(AppError::ValidationError(
+                "Document is not encrypted".to_string(),
+            )),
+        }
+    }
+
+    fn get_encrypted_document(document_id: DocumentId) -> Result<Document, AppError> {
+        let document = DocumentManager::get_by_id(document_id)
+            .ok_or_else(|| AppError::EntityNotFound("Document not found".to_string()))?;
+        if !DocumentManager::is_encrypted(&document) {
+            return Err(AppError::ValidationError(
+                "Document is not encrypted".to_string(),
+            ));
+        }
+        Ok(document)
+    }
+
+    /// Public key document keys are derived under, to verify derived keys against.
+    pub async fn get_encryption_public_key() -> GetEncryptionPublicKeyResult {
+        if let Some(public_key) = PUBLIC_KEY.with(|key| key.borrow().clone()) {
+            return GetEncryptionPublicKeyResult::Ok(public_key);
+        }
+
+        let args = VetKDPublicKeyArgs {
+            canister_id: None,
+            context: DOCUMENT_KEY_CONTEXT.to_vec(),
+            key_id: Self::key_id(),
+        };
+        match vetkd_public_key(&args).await {
+            Ok(result) => {
+                PUBLIC_KEY.with(|key| *key.borrow_mut() = Some(result.public_key.clone()));
+                GetEncryptionPublicKeyResult::Ok(result.public_key)
+            }
+            Err(e) => {
+                log_warn!(
+                    "document_encryption: Failed to fetch vetKD public key [key_name={}] - {:?}",
+                    args.key_id.name,
+                    e
+                );
+                GetEncryptionPublicKeyResult::Err(AppError::InternalError(format!(
+                    "Failed to fetch encryption public key: {:?}",
+                    e
+                )))
+            }
+        }
+    }
+
+    /// Derives the key of an encrypted document for a caller with read access, encrypted
+    /// for their transport key.
+    pub async fn derive_document_key(input: DeriveDocumentKeyInput) -> DeriveDocumentKeyResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Document key derivation attempt [principal={}, document_id={}, key_epoch={:?}]",
+            principal,
+            input.document_id,
+            input.key_epoch
+        );
+
+        let user = match UserManager::authenticate(principal, "document key derivation") {
+            Ok(user) => user,
+            Err(e) => return DeriveDocumentKeyResult::Err(e),
+        };
+        let document = match Self::get_encrypted_document(input.document_id) {
+            Ok(document) => document,
+            Err(e) => {
+                log_warn!(
+                    "document_encryption: Key derivation denied [document_id={}, principal={}] - {:?}",
+                    input.document_id,
+                    principal,
+                    e
+                );
+                return DeriveDocumentKeyResult::Err(e);
+            }
+        };
+        if !DocumentManager::has_permission(
+            user.id,
+            &document,
+            &Permission::Document(DocumentPermission::Read),
+        ) {
+            log_warn!(
+                "access_control: Permission denied for document key derivation [user_id={}, document_id={}]",
+                user.id,
+                document.id
+            );
+            return DeriveDocumentKeyResult::Err(AppError::Unauthorized);
+        }
+        if !ic_vetkeys::is_valid_transport_public_key_encoding(&input.transport_public_key) {
+            log_warn!(
+                "document_encryption: Invalid transport public key [document_id={}, principal={}]",
+                document.id,
+                principal
+            );
+            return DeriveDocumentKeyResult::Err(AppError::InvalidInput(
+                "Invalid transport public key".to_string(),
+            ));
+        }
+        let current = Self::get_key_state(document.id).epoch;
+        let epoch = input.key_epoch.unwrap_or(current);
+        if epoch > current {
+            log_warn!(
+                "document_encryption: Key epoch does not exist yet [document_id={}, key_epoch={}, current_epoch={}, principal={}]",
+                document.id,
+                epoch,
+                current,
+                principal
+            );
+            return DeriveDocumentKeyResult::Err(AppError::InvalidInput(format!(
+                "Key epoch {} does not exist yet, the current epoch is {}",
+                epoch, current
+            )));
+        }
+
+        let args = VetKDDeriveKeyArgs {
+            input: Self::key_input(input.document_id, epoch),
+            context: DOCUMENT_KEY_CONTEXT.to_vec(),
+            transport_public_key: input.transport_public_key,
+            key_id: Self::key_id(),
+        };
+        let encrypted_key = match vetkd_derive_key(&args).await {
+            Ok(result) => result.encrypted_key,
+            Err(e) => {
+                log_warn!(
+                    "document_encryption: Key derivation failed [document_id={}, key_epoch={}, principal={}] - {:?}",
+                    input.document_id,
+                    epoch,
+                    principal,
+                    e
+                );
+                return DeriveDocumentKeyResult::Err(AppError::InternalError(format!(
+                    "Failed to derive document key: {:?}",
+                    e
+                )));
+            }
+        };
+
+        // Holders are tracked per epoch, a rotation while the call was in flight means
+        // the user only received a key that is no longer current
+        let mut state = Self::get_key_state(input.document_id);
+        if state.epoch == epoch && !state.key_holders.contains(&user.id) {
+            state.key_holders.push(user.id);
+            Self::insert_key_state(state);
+        }
+
+        log_info!(
+            "document_encryption: Derived document key [document_id={}, key_epoch={}, user_id={}, principal={}]",
+            input.document_id,
+            epoch,
+            user.id,
+            principal
+        );
+        DeriveDocumentKeyResult::Ok(DerivedDocumentKey {
+            document_id: input.document_id,
+            key_epoch: epoch,
+            encrypted_key,
+        })
+    }
+
+    fn rotate(mut state: DocumentKeyState) -> u32 {
+        state.epoch += 1;
+        state.rotated_at = Some(ic_cdk::api::time());
+        state.key_holders.clear();
+        let epoch = state.epoch;
+        Self::insert_key_state(state);
+        epoch
+    }
+
+    /// Starts a new key epoch for a document. Requires permission to share the document,
+    /// which decides who may read it.
+    pub fn rotate_document_key(input: RotateDocumentKeyInput) -> RotateDocumentKeyResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Document key rotation attempt [principal={}, document_id={}]",
+            principal,
+            input.document_id
+        );
+
+        let user = match UserManager::authenticate(principal, "document key rotation") {
+            Ok(user) => user,
+            Err(e) => return RotateDocumentKeyResult::Err(e),
+        };
+        let document = match Self::get_encrypted_document(input.document_id) {
+            Ok(document) => document,
+            Err(e) => {
+                log_warn!(
+                    "document_encryption: Failed to rotate document key [document_id={}, principal={}] - {:?}",
+                    input.document_id,
+                    principal,
+                    e
+                );
+                return RotateDocumentKeyResult::Err(e);
+            }
+        };
+        if !DocumentManager::has_permission(
+            user.id,
+            &document,
+            &Permission::Document(DocumentPermission::Share),
+        ) {
+            log_warn!(
+                "access_control: Permission denied for document key rotation [user_id={}, document_id={}]",
+                user.id,
+                document.id
+            );
+            return RotateDocumentKeyResult::Err(AppError::Unauthorized);
+        }
+        let epoch = Self::rotate(Self::get_key_state(document.id));
+        log_info!(
+            "document_encryption: Rotated document key [document_id={}, key_epoch={}, user_id={}, principal={}]",
+            document.id,
+            epoch,
+            user.id,
+            principal
+        );
+        RotateDocumentKeyResult::Ok(epoch)
+    }
+
+    /// Rotates the key of every encrypted document a holder of the current key can no
+    /// longer read. Called after changes to roles, folder grants or document placement.
+    pub fn rotate_revoked_keys() {
+        let states: Vec<DocumentKeyState> =
+            DOCUMENT_KEYS.with(|keys| keys.borrow().values().collect());
+        let read = Permission::Document(DocumentPermission::Read);
+        for state in states {
+            if state.key_holders.is_empty() {
+                continue;
+            }
+            let Some(document) = DocumentManager::get_by_id(state.document_id) else {
+                continue;
+            };
+            let revoked: Vec<_> = state
+                .key_holders
+                .iter()
+                .filter(|user_id| !DocumentManager::has_permission(**user_id, &document, &read))
+                .copied()
+                .collect();
+            if revoked.is_empty() {
+                continue;
+            }
+            let document_id = state.document_id;
+            let epoch = Self::rotate(state);
+            log_info!(
+                "document_encryption: Rotated document key after access was revoked [document_id={}, key_epoch={}, revoked_users={:?}]",
+                document_id,
+                epoch,
+                revoked
+            );
+        }
+    }
+}
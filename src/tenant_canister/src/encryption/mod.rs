@@ -0,0 +1,4 @@
+pub mod controllers;
+pub mod encryption_manager;
+
+pub use encryption_manager::EncryptionManager;
@@ -7,3 +7,9 @@ macro_rules! env_or_default {
 pub fn canister_id_upgrade() -> &'static str {
     env_or_default!("CANISTER_ID_UPGRADE_CANISTER", "unknown")
 }
+
+/// vetKD master key to derive document keys from: `dfx_test_key` on a local replica,
+/// `test_key_1` or `key_1` on mainnet.
+pub fn vetkd_key_name() -> &'static str {
+    env_or_default!("VETKD_KEY_NAME", "key_1")
+}
@@ -0,0 +1,7 @@
+use crate::exports::ExportsManager;
+use shared::types::exports::{BeginExportInput, BeginExportResult};
+
+#[ic_cdk_macros::update]
+pub fn begin_export(input: BeginExportInput) -> BeginExportResult {
+    ExportsManager::begin_export(input)
+}
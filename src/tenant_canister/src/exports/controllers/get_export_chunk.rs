@@ -0,0 +1,7 @@
+use crate::exports::ExportsManager;
+use shared::types::exports::{GetExportChunkInput, GetExportChunkResult};
+
+#[ic_cdk_macros::query]
+pub fn get_export_chunk(input: GetExportChunkInput) -> GetExportChunkResult {
+    ExportsManager::get_export_chunk(input)
+}
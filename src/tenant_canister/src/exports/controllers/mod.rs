@@ -0,0 +1,2 @@
+pub mod begin_export;
+pub mod get_export_chunk;
@@ -0,0 +1,609 @@
+use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use shared::consts::exports::{
+    EXPORT_CHUNK_SIZE, EXPORT_FORMAT, EXPORT_FORMAT_VERSION, EXPORT_TTL,
+};
+use shared::consts::memory_ids::tenant_canister::EXPORTS_MEMORY_ID;
+use shared::types::access_control::{DocumentPermission, Permission};
+use shared::types::documents::Document;
+use shared::types::errors::AppError;
+use shared::types::exports::{
+    BeginExportInput, BeginExportResult, ExportEntry, ExportId, ExportScope, ExportSession,
+    ExportSummary, GetExportChunkInput, GetExportChunkResult,
+};
+use shared::types::projects::Project;
+use shared::types::revisions::{Revision, RevisionContent, RevisionContentId, RevisionContentType};
+use shared::types::users::{User, UserId};
+use shared::utils::tar::{
+    padded_size, sanitize_file_name, tar_header, trailer_size, TAR_BLOCK_SIZE,
+};
+use shared::{log_debug, log_error, log_info, log_warn};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::approvals::approvals_manager::ApprovalsManager;
+use crate::documents::DocumentManager;
+use crate::projects::projects_manager::ProjectsManager;
+use crate::revisions::revisions_manager::RevisionsManager;
+use crate::users::user_manager::UserManager;
+
+type Memory = VirtualMemory<DefaultMemoryImpl>;
+
+const MANIFEST_PATH: &str = "manifest.json";
+// Keeps archive paths within the 100 byte name field of a tar header
+const MAX_FILE_NAME_LENGTH: usize = 48;
+
+thread_local! {
+    static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
+        RefCell::new(MemoryManager::init(DefaultMemoryImpl::default()));
+
+    static EXPORTS: RefCell<StableBTreeMap<ExportId, ExportSession, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(EXPORTS_MEMORY_ID))),
+        )
+    );
+}
+
+/// A file to be placed in the archive, before offsets are assigned.
+struct PlannedFile {
+    path: String,
+    content: RevisionContent,
+    link_target: Option<String>,
+    mtime: u64,
+}
+
+/// Exports documents as tar archives: a `manifest.json` with the metadata, authors,
+/// approval signatures and SHA-256 of every file, followed by the content of every
+/// revision under `documents/{document_id}/v{version}/`. Content unchanged between
+/// revisions is stored once and hard linked. `begin_export` lays the archive out and
+/// `get_export_chunk` assembles it chunk by chunk, so large exports are read with
+/// queries.
+pub struct ExportsManager;
+
+impl ExportsManager {
+    fn get_next_id() -> ExportId {
+        EXPORTS.with(|exports| {
+            exports
+                .borrow()
+                .last_key_value()
+                .map(|(id, _)| id + 1)
+                .unwrap_or_default()
+        })
+    }
+
+    fn can_export(user_id: UserId, document: &Document) -> bool {
+        DocumentManager::has_permission(
+            user_id,
+            document,
+            &Permission::Document(DocumentPermission::Export),
+        )
+    }
+
+    /// Documents in scope, all of which the user may export.
+    fn resolve_scope(
+        user: &User,
+        scope: &ExportScope,
+    ) -> Result<(Project, Vec<Document>), AppError> {
+        match scope {
+            ExportScope::Document(document_id) => {
+                let document = DocumentManager::get_by_id(*document_id)
+                    .ok_or_else(|| AppError::EntityNotFound("Document not found".to_string()))?;
+                if !Self::can_export(user.id, &document) {
+                    return Err(AppError::Unauthorized);
+                }
+                let project = ProjectsManager::get_by_id(document.project_id)
+                    .ok_or_else(|| AppError::EntityNotFound("Project not found".to_string()))?;
+                Ok((project, vec![document]))
+            }
+            ExportScope::Project(project_id) => {
+                let project = ProjectsManager::get_by_id(*project_id)
+                    .ok_or_else(|| AppError::EntityNotFound("Project not found".to_string()))?;
+                if !project.members.contains(&user.id) {
+                    return Err(AppError::Unauthorized);
+                }
+                let project_documents: Vec<Document> = DocumentManager::get_all()
+                    .into_iter()
+                    .filter(|document| document.project_id == project.id)
+                    .collect();
+                let has_documents = !project_documents.is_empty();
+                let documents: Vec<Document> = project_documents
+                    .into_iter()
+                    .filter(|document| Self::can_export(user.id, document))
+                    .collect();
+                if documents.is_empty() && has_documents {
+                    return Err(AppError::Unauthorized);
+                }
+                Ok((project, documents))
+            }
+        }
+    }
+
+    fn user_reference(user_id: UserId, names: &mut HashMap<UserId, Value>) -> Value {
+        names
+            .entry(user_id)
+            .or_insert_with(|| match UserManager::get_by_id(user_id) {
+                Some(user) => json!({
+                    "id": user.id,
+                    "name": format!("{} {}", user.first_name, user.last_name),
+                }),
+                None => json!({ "id": user_id, "name": null }),
+            })
+            .clone()
+    }
+
+    fn default_file_name(content: &RevisionContent) -> &'static str {
+        match content.content_type {
+            RevisionContentType::Markdown => "content.md",
+            RevisionContentType::StructuredForm => "content.json",
+            _ => "content.bin",
+        }
+    }
+
+    fn file_path(revision: &Revision, content_index: usize, content: &RevisionContent) -> String {
+        let file_name = content
+            .file_name
+            .as_deref()
+            .map(|name| sanitize_file_name(name, MAX_FILE_NAME_LENGTH))
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| Self::default_file_name(content).to_string());
+        format!(
+            "documents/{}/v{}/{}-{}",
+            revision.document_id, revision.version, content_index, file_name
+        )
+    }
+
+    fn signatures_manifest(revision: &Revision) -> Vec<Value> {
+        ApprovalsManager::get_signatures(revision.id)
+            .into_iter()
+            .map(|signature| {
+                json!({
+                    "sequence": signature.sequence,
+                    "signer": { "id": signature.signer, "name": signature.signer_name },
+                    "principal": signature.principal.to_text(),
+                    "role_ids": signature.role_ids,
+                    "meaning": format!("{:?}", signature.meaning),
+                    "reason": signature.reason,
+                    "content_hash": signature.content_hash,
+                    "challenge_issued_at": signature.challenge_issued_at,
+                    "signed_at": signature.signed_at,
+                    "resulting_status": format!("{:?}", signature.resulting_status),
+                })
+            })
+            .collect()
+    }
+
+    /// Lists the files of every revision and builds the manifest describing them.
+    fn plan_archive(
+        user: &User,
+        scope: &ExportScope,
+        project: &Project,
+        documents: &[Document],
+        now: u64,
+    ) -> Result<(Vec<PlannedFile>, Value), AppError> {
+        let mut files = Vec::new();
+        let mut names = HashMap::new();
+        // content -> path it was first stored at
+        let mut stored: HashMap<RevisionContentId, String> = HashMap::new();
+
+        let mut documents_manifest = Vec::new();
+        for document in documents {
+            let mut revisions_manifest = Vec::new();
+            for revision_id in &document.revisions {
+                let Some(revision) = RevisionsManager::get_by_id(*revision_id) else {
+                    continue;
+                };
+                let mut files_manifest = Vec::new();
+                let mut checksums = Vec::new();
+                let contents = revision.contents.iter().filter_map(|content_id| {
+                    RevisionsManager::get_revision_content_by_id(*content_id)
+                });
+                for (content_index, content) in contents.enumerate() {
+                    // Purged content is described by its checksum, without a file
+                    if let Some(purge) = &content.purged {
+                        files_manifest.push(json!({
+                            "path": Value::Null,
+                            "content_id": content.id,
+                            "file_name": content.file_name,
+                            "content_type": format!("{:?}", content.content_type),
+                            "mime_type": content.mime_type,
+                            "size": purge.size,
+                            "sha256": purge.sha256,
+                            "purged": { "purged_at": purge.purged_at },
+                        }));
+                        checksums.push(purge.sha256.clone());
+                        continue;
+                    }
+                    let path = Self::file_path(&revision, content_index, &content);
+                    // Checksums are recorded when content is stored, the archive never
+                    // rehashes content data
+                    let (sha256, size) = RevisionsManager::get_recorded_checksum(&content)
+                        .ok_or_else(|| {
+                            AppError::InternalError(format!(
+                                "Checksum of content {} is not recorded",
+                                content.id
+                            ))
+                        })?;
+                    let link_target = stored.get(&content.id).cloned();
+                    if link_target.is_none() {
+                        stored.insert(content.id, path.clone());
+                    }
+                    files_manifest.push(json!({
+                        "path": path,
+                        "content_id": content.id,
+                        "file_name": content.file_name,
+                        "content_type": format!("{:?}", content.content_type),
+                        "mime_type": content.mime_type,
+                        "size": size,
+                        "sha256": sha256,
+                        "encryption": content.encryption.as_ref().map(|encryption| json!({
+                            "key_epoch": encryption.key_epoch,
+                        })),
+                    }));
+                    checksums.push(sha256);
+                    files.push(PlannedFile {
+                        path,
+                        content,
+                        link_target,
+                        mtime: revision.created_at / 1_000_000_000,
+                    });
+                }
+
+                let content_hash =
+                    RevisionsManager::hash_checksums(checksums.iter().map(String::as_str));
+                revisions_manifest.push(json!({
+                    "id": revision.id,
+                    "version": revision.version,
+                    "status": format!("{:?}", ApprovalsManager::get_status(&revision)),
+                    "created_at": revision.created_at,
+                    "created_by": Self::user_reference(revision.created_by, &mut names),
+                    "rollback": revision.rollback.as_ref().map(|rollback| json!({
+                        "target_revision_id": rollback.target_revision_id,
+                        "target_version": rollback.target_version,
+                        "reason": rollback.reason,
+                    })),
+                    "content_hash": content_hash,
+                    "files": files_manifest,
+                    "signatures": Self::signatures_manifest(&revision),
+                }));
+            }
+
+            documents_manifest.push(json!({
+                "id": document.id,
+                "title": document.title,
+                "folder_id": document.folder_id,
+                "encrypted": DocumentManager::is_encrypted(document),
+                "document_type": document.document_type,
+                "created_at": document.created_at,
+                "created_by": Self::user_reference(document.created_by, &mut names),
+                "revisions": revisions_manifest,
+            }));
+        }
+
+        let scope = match scope {
+            ExportScope::Document(id) => json!({ "type": "document", "id": id }),
+            ExportScope::Project(id) => json!({ "type": "project", "id": id }),
+        };
+        let manifest = json!({
+            "format": EXPORT_FORMAT,
+            "format_version": EXPORT_FORMAT_VERSION,
+            "exported_at": now,
+            "exported_by": Self::user_reference(user.id, &mut names),
+            "scope": scope,
+            "project": { "id": project.id, "name": project.name },
+            "documents": documents_manifest,
+        });
+        Ok((files, manifest))
+    }
+
+    /// Assigns archive offsets to the manifest and the planned files.
+    fn layout_entries(
+        files: Vec<PlannedFile>,
+        manifest_size: u64,
+        now: u64,
+    ) -> Result<(Vec<ExportEntry>, u64), AppError> {
+        let manifest_entry = (
+            MANIFEST_PATH.to_string(),
+            None,
+            None,
+            manifest_size,
+            now / 1_000_000_000,
+        );
+        let file_entries = files.into_iter().map(|file| {
+            (
+                file.path,
+                Some(file.content.id),
+                file.link_target,
+                RevisionsManager::get_content_size(&file.content),
+                file.mtime,
+            )
+        });
+
+        let mut offset = 0;
+        let mut entries = Vec::new();
+        for (path, content_id, link_target, size, mtime) in
+            std::iter::once(manifest_entry).chain(file_entries)
+        {
+            // Reject paths the header cannot hold now rather than when chunks are read
+            tar_header(&path, size, mtime, link_target.as_deref())
+                .map_err(AppError::InternalError)?;
+            let size = if link_target.is_some() { 0 } else { size };
+            entries.push(ExportEntry {
+                path,
+                content_id,
+                link_target,
+                size,
+                mtime,
+                offset,
+            });
+            offset += TAR_BLOCK_SIZE + padded_size(size);
+        }
+        Ok((entries, offset + trailer_size()))
+    }
+
+    pub fn begin_export(input: BeginExportInput) -> BeginExportResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Export attempt [principal={}, scope={:?}]",
+            principal,
+            input.scope
+        );
+
+        let user = match UserManager::authenticate(principal, "export") {
+            Ok(user) => user,
+            Err(e) => return BeginExportResult::Err(e),
+        };
+        let (project, documents) = match Self::resolve_scope(&user, &input.scope) {
+            Ok(resolved) => resolved,
+            Err(e) => {
+                log_warn!(
+                    "document_export: Failed to resolve export scope [scope={:?}, principal={}] - {:?}",
+                    input.scope,
+                    principal,
+                    e
+                );
+                return BeginExportResult::Err(e);
+            }
+        };
+
+        let now = ic_cdk::api::time();
+        if RevisionsManager::is_migrating() {
+            log_warn!(
+                "document_export: Export refused while stored content is migrated [scope={:?}, principal={}]",
+                input.scope,
+                principal
+            );
+            return BeginExportResult::Err(AppError::InvalidStateTransition(
+                "Stored content is being migrated after an upgrade, retry the export later"
+                    .to_string(),
+            ));
+        }
+        let (files, manifest) = match Self::plan_archive(
+            &user,
+            &input.scope,
+            &project,
+            &documents,
+            now,
+        ) {
+            Ok(planned) => planned,
+            Err(e) => {
+                log_warn!(
+                        "document_export: Failed to plan export archive [scope={:?}, principal={}] - {:?}",
+                        input.scope,
+                        principal,
+                        e
+                    );
+                return BeginExportResult::Err(e);
+            }
+        };
+        let manifest = match serde_json::to_vec_pretty(&manifest) {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                log_error!(
+                    "document_export: Failed to serialize manifest [scope={:?}, principal={}] - {}",
+                    input.scope,
+                    principal,
+                    e
+                );
+                return BeginExportResult::Err(AppError::InternalError(format!(
+                    "Failed to serialize manifest: {}",
+                    e
+                )));
+            }
+        };
+        let file_count = files.len() as u32;
+        let (entries, total_size) = match Self::layout_entries(files, manifest.len() as u64, now) {
+            Ok(layout) => layout,
+            Err(e) => {
+                log_warn!(
+                        "document_export: Failed to lay out export archive [scope={:?}, principal={}] - {:?}",
+                        input.scope,
+                        principal,
+                        e
+                    );
+                return BeginExportResult::Err(e);
+            }
+        };
+
+        let manifest_sha256 = hex::encode(Sha256::digest(&manifest));
+        let session = ExportSession {
+            id: Self::get_next_id(),
+            scope: input.scope.clone(),
+            created_by: user.id,
+            created_at: now,
+            expires_at: now + EXPORT_TTL,
+            entries,
+            manifest,
+            total_size,
+        };
+        EXPORTS.with(|exports| exports.borrow_mut().insert(session.id, session.clone()));
+
+        log_info!(
+            "document_export: Export prepared [export_id={}, scope={:?}, documents={}, files={}, total_size={}, user_id={}, principal={}]",
+            session.id,
+            session.scope,
+            documents.len(),
+            file_count,
+            session.total_size,
+            user.id,
+            principal
+        );
+        BeginExportResult::Ok(ExportSummary {
+            export_id: session.id,
+            scope: session.scope,
+            document_count: documents.len() as u32,
+            file_count,
+            total_size,
+            chunk_size: EXPORT_CHUNK_SIZE,
+            total_chunks: total_size.div_ceil(EXPORT_CHUNK_SIZE) as u32,
+            manifest_sha256,
+            expires_at: session.expires_at,
+        })
+    }
+
+    /// Bytes `start..end` of an entry's data.
+    fn read_entry_data(
+        session: &ExportSession,
+        entry: &ExportEntry,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<u8>, AppError> {
+        let Some(content_id) = entry.content_id else {
+            return Ok(session.manifest[start as usize..end as usize].to_vec());
+        };
+        RevisionsManager::get_revision_content_by_id(content_id)
+            .and_then(|content| RevisionsManager::read_content_range(&content, start, end))
+            .ok_or_else(|| {
+                AppError::InternalError(format!("Data of content {} is incomplete", content_id))
+            })
+    }
+
+    /// Assembles bytes `start..end` of the archive from the entries overlapping them.
+    fn read_archive_range(
+        session: &ExportSession,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<u8>, AppError> {
+        let mut bytes = Vec::with_capacity((end - start) as usize);
+        // Part `from..to` of a section at `section_start`, clipped to the requested range
+        let overlap = |section_start: u64, section_end: u64| {
+            let from = section_start.max(start);
+            let to = section_end.min(end);
+            (from < to).then_some((from - section_start, to - section_start))
+        };
+
+        let first = session.entries.partition_point(|entry| {
+            entry.offset + TAR_BLOCK_SIZE + padded_size(entry.size) <= start
+        });
+        for entry in &session.entries[first..] {
+            if entry.offset >= end {
+                break;
+            }
+            let data_start = entry.offset + TAR_BLOCK_SIZE;
+            let data_end = data_start + entry.size;
+            let entry_end = data_start + padded_size(entry.size);
+
+            if let Some((from, to)) = overlap(entry.offset, data_start) {
+                let header = tar_header(
+                    &entry.path,
+                    entry.size,
+                    entry.mtime,
+                    entry.link_target.as_deref(),
+                )
+                .map_err(AppError::InternalError)?;
+                bytes.extend_from_slice(&header[from as usize..to as usize]);
+            }
+            if let Some((from, to)) = overlap(data_start, data_end) {
+                bytes.extend(Self::read_entry_data(session, entry, from, to)?);
+            }
+            if let Some((from, to)) = overlap(data_end, entry_end) {
+                bytes.resize(bytes.len() + (to - from) as usize, 0);
+            }
+        }
+        // End-of-archive marker
+        if let Some((from, to)) = overlap(session.total_size - trailer_size(), session.total_size) {
+            bytes.resize(bytes.len() + (to - from) as usize, 0);
+        }
+        Ok(bytes)
+    }
+
+    pub fn get_export_chunk(input: GetExportChunkInput) -> GetExportChunkResult {
+        let principal = ic_cdk::api::msg_caller();
+
+        let user = match UserManager::authenticate(principal, "export download") {
+            Ok(user) => user,
+            Err(e) => return GetExportChunkResult::Err(e),
+        };
+        let session = match EXPORTS
+            .with(|exports| exports.borrow().get(&input.export_id))
+            .filter(|session| session.expires_at > ic_cdk::api::time())
+        {
+            Some(session) => session,
+            None => {
+                log_warn!(
+                    "document_export: Export not found [export_id={}, principal={}]",
+                    input.export_id,
+                    principal
+                );
+                return GetExportChunkResult::Err(AppError::EntityNotFound(
+                    "Export not found".to_string(),
+                ));
+            }
+        };
+        if session.created_by != user.id {
+            log_warn!(
+                "access_control: Permission denied for export download [user_id={}, export_id={}]",
+                user.id,
+                session.id
+            );
+            return GetExportChunkResult::Err(AppError::Unauthorized);
+        }
+        let start = input.chunk_id as u64 * EXPORT_CHUNK_SIZE;
+        if start >= session.total_size {
+            log_warn!(
+                "document_export: Export chunk out of range [export_id={}, chunk_id={}, principal={}]",
+                session.id,
+                input.chunk_id,
+                principal
+            );
+            return GetExportChunkResult::Err(AppError::InvalidInput(format!(
+                "Chunk {} is out of range",
+                input.chunk_id
+            )));
+        }
+        let end = (start + EXPORT_CHUNK_SIZE).min(session.total_size);
+        match Self::read_archive_range(&session, start, end) {
+            Ok(bytes) => GetExportChunkResult::Ok(bytes),
+            Err(e) => {
+                log_warn!(
+                    "document_export: Failed to read export chunk [export_id={}, chunk_id={}, principal={}] - {:?}",
+                    input.export_id,
+                    input.chunk_id,
+                    principal,
+                    e
+                );
+                GetExportChunkResult::Err(e)
+            }
+        }
+    }
+
+    /// Removes expired exports, returns how many were removed.
+    pub fn remove_expired_exports(now: u64) -> u64 {
+        let expired: Vec<ExportId> = EXPORTS.with(|exports| {
+            exports
+                .borrow()
+                .iter()
+                .filter(|(_, session)| session.expires_at <= now)
+                .map(|(id, _)| id)
+                .collect()
+        });
+        EXPORTS.with(|exports| {
+            let mut exports = exports.borrow_mut();
+            for export_id in &expired {
+                exports.remove(export_id);
+            }
+        });
+        expired.len() as u64
+    }
+}
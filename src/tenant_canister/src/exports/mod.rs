@@ -0,0 +1,4 @@
+pub mod controllers;
+pub mod exports_manager;
+
+pub use exports_manager::ExportsManager;
@@ -0,0 +1,7 @@
+use crate::folders::folders_manager::FoldersManager;
+use shared::types::folders::{CreateFolderInput, CreateFolderResult};
+
+#[ic_cdk_macros::update]
+pub fn create_folder(input: CreateFolderInput) -> CreateFolderResult {
+    FoldersManager::create_folder(input)
+}
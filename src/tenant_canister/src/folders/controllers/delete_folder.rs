@@ -0,0 +1,7 @@
+use crate::folders::folders_manager::FoldersManager;
+use shared::types::folders::{DeleteFolderInput, DeleteFolderResult};
+
+#[ic_cdk_macros::update]
+pub fn delete_folder(input: DeleteFolderInput) -> DeleteFolderResult {
+    FoldersManager::delete_folder(input)
+}
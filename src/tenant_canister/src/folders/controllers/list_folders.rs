@@ -0,0 +1,7 @@
+use crate::folders::folders_manager::FoldersManager;
+use shared::types::folders::{ListFoldersInput, ListFoldersResult};
+
+#[ic_cdk_macros::query]
+pub fn list_folders(input: ListFoldersInput) -> ListFoldersResult {
+    FoldersManager::list_folders(input)
+}
@@ -0,0 +1,6 @@
+pub mod create_folder;
+pub mod delete_folder;
+pub mod list_folders;
+pub mod move_folder;
+pub mod rename_folder;
+pub mod set_folder_permissions;
@@ -0,0 +1,7 @@
+use crate::folders::folders_manager::FoldersManager;
+use shared::types::folders::{MoveFolderInput, MoveFolderResult};
+
+#[ic_cdk_macros::update]
+pub fn move_folder(input: MoveFolderInput) -> MoveFolderResult {
+    FoldersManager::move_folder(input)
+}
@@ -0,0 +1,7 @@
+use crate::folders::folders_manager::FoldersManager;
+use shared::types::folders::{RenameFolderInput, RenameFolderResult};
+
+#[ic_cdk_macros::update]
+pub fn rename_folder(input: RenameFolderInput) -> RenameFolderResult {
+    FoldersManager::rename_folder(input)
+}
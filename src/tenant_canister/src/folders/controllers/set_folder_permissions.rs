@@ -0,0 +1,7 @@
+use crate::folders::folders_manager::FoldersManager;
+use shared::types::folders::{SetFolderPermissionsInput, SetFolderPermissionsResult};
+
+#[ic_cdk_macros::update]
+pub fn set_folder_permissions(input: SetFolderPermissionsInput) -> SetFolderPermissionsResult {
+    FoldersManager::set_folder_permissions(input)
+}
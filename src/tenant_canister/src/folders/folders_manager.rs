@@ -0,0 +1,645 @@
+use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap};
+use shared::consts::memory_ids::tenant_canister::FOLDERS_MEMORY_ID;
+use shared::types::access_control::{DocumentPermission, Permission, ProjectPermission, RoleId};
+use shared::types::errors::AppError;
+use shared::types::folders::{
+    CreateFolderInput, CreateFolderResult, DeleteFolderInput, DeleteFolderResult, Folder, FolderId,
+    ListFoldersInput, ListFoldersResult, MoveFolderInput, MoveFolderResult, RenameFolderInput,
+    RenameFolderResult, SetFolderPermissionsInput, SetFolderPermissionsResult,
+};
+use shared::types::projects::ProjectId;
+use shared::types::users::{User, UserId};
+use shared::{log_debug, log_info, log_warn};
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::access_control::AccessControlManager;
+use crate::documents::DocumentManager;
+use crate::encryption::EncryptionManager;
+use crate::projects::projects_manager::ProjectsManager;
+use crate::retention::RetentionManager;
+use crate::users::user_manager::UserManager;
+
+type Memory = VirtualMemory<DefaultMemoryImpl>;
+
+thread_local! {
+    static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
+        RefCell::new(MemoryManager::init(DefaultMemoryImpl::default()));
+
+    static FOLDERS: RefCell<StableBTreeMap<FolderId, Folder, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(FOLDERS_MEMORY_ID))),
+        )
+    );
+
+    static NEXT_ID: AtomicU64 = const { AtomicU64::new(0) };
+}
+
+pub struct FoldersManager;
+
+impl FoldersManager {
+    fn get_next_id() -> FolderId {
+        NEXT_ID.with(|id| id.fetch_add(1, Ordering::SeqCst))
+    }
+
+    fn insert(id: FolderId, folder: Folder) {
+        FOLDERS.with(|folders| {
+            folders.borrow_mut().insert(id, folder);
+        });
+    }
+
+    pub fn get_by_id(id: FolderId) -> Option<Folder> {
+        FOLDERS.with(|folders| folders.borrow().get(&id))
+    }
+
+    pub fn get_by_project(project_id: ProjectId) -> Vec<Folder> {
+        FOLDERS.with(|folders| {
+            folders
+                .borrow()
+                .iter()
+                .filter(|(_, folder)| folder.project_id == project_id)
+                .map(|(_, folder)| folder)
+                .collect()
+        })
+    }
+
+    fn get_children(parent_id: FolderId) -> Vec<Folder> {
+        FOLDERS.with(|folders| {
+            folders
+                .borrow()
+                .iter()
+                .filter(|(_, folder)| folder.parent_id == Some(parent_id))
+                .map(|(_, folder)| folder)
+                .collect()
+        })
+    }
+
+    /// The folder itself followed by all folders nested below it.
+    pub fn get_subtree_ids(id: FolderId) -> Vec<FolderId> {
+        let mut ids = vec![id];
+        let mut index = 0;
+        while index < ids.len() {
+            let children = Self::get_children(ids[index]);
+            ids.extend(children.iter().map(|child| child.id));
+            index += 1;
+        }
+        ids
+    }
+
+    /// Permissions granted to `role_ids` by the folder, including grants inherited
+    /// from parent folders for as long as `inherit_permissions` is set.
+    pub fn get_granted_permissions(folder_id: FolderId, role_ids: &[RoleId]) -> Vec<Permission> {
+        let mut permissions = Vec::new();
+        let mut current = Self::get_by_id(folder_id);
+        while let Some(folder) = current {
+            for grant in folder
+                .grants
+                .iter()
+                .filter(|grant| role_ids.contains(&grant.role_id))
+            {
+                permissions.extend(grant.permissions.iter().cloned());
+            }
+            current = match (folder.inherit_permissions, folder.parent_id) {
+                (true, Some(parent_id)) => Self::get_by_id(parent_id),
+                _ => None,
+            };
+        }
+        permissions
+    }
+
+    /// Whether the user holds `permission` in the project, either through a project
+    /// role or through a grant on `folder_id` (or one of the folders it inherits from).
+    pub fn has_permission(
+        user_id: UserId,
+        project_id: ProjectId,
+        folder_id: Option<FolderId>,
+        permission: &Permission,
+    ) -> bool {
+        if AccessControlManager::has_project_permission(user_id, project_id, permission) {
+            return true;
+        }
+        match folder_id {
+            Some(folder_id) => {
+                let role_ids = AccessControlManager::get_user_role_ids(user_id);
+                Self::get_granted_permissions(folder_id, &role_ids).contains(permission)
+            }
+            None => false,
+        }
+    }
+
+    fn authorize(
+        user: &User,
+        project_id: ProjectId,
+        folder_id: Option<FolderId>,
+        permission: Permission,
+        operation: &str,
+    ) -> Result<(), AppError> {
+        if Self::has_permission(user.id, project_id, folder_id, &permission) {
+            return Ok(());
+        }
+        log_warn!(
+            "access_control: Permission denied for {} [user_id={}, project_id={}, folder_id={:?}, permission={:?}]",
+            operation,
+            user.id,
+            project_id,
+            folder_id,
+            permission
+        );
+        Err(AppError::Unauthorized)
+    }
+
+    fn validate_name(
+        name: &str,
+        project_id: ProjectId,
+        parent_id: Option<FolderId>,
+        exclude_id: Option<FolderId>,
+    ) -> Result<(), AppError> {
+        if name.trim().is_empty() {
+            return Err(AppError::ValidationError(
+                "Folder name cannot be empty".to_string(),
+            ));
+        }
+        let taken = Self::get_by_project(project_id).iter().any(|folder| {
+            folder.parent_id == parent_id
+                && Some(folder.id) != exclude_id
+                && folder.name.eq_ignore_ascii_case(name.trim())
+        });
+        if taken {
+            return Err(AppError::ValidationError(format!(
+                "A folder named '{}' already exists here",
+                name.trim()
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_parent(project_id: ProjectId, parent_id: Option<FolderId>) -> Result<(), AppError> {
+        match parent_id {
+            Some(parent_id) => match Self::get_by_id(parent_id) {
+                Some(parent) if parent.project_id == project_id => Ok(()),
+                Some(_) => Err(AppError::ValidationError(
+                    "Parent folder belongs to a different project".to_string(),
+                )),
+                None => Err(AppError::EntityNotFound(
+                    "Parent folder not found".to_string(),
+                )),
+            },
+            None => Ok(()),
+        }
+    }
+
+    pub fn create_folder(input: CreateFolderInput) -> CreateFolderResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Folder creation attempt [principal={}, project_id={}, parent_id={:?}, name='{}']",
+            principal,
+            input.project_id,
+            input.parent_id,
+            input.name
+        );
+
+        let user = match UserManager::authenticate(principal, "folder creation") {
+            Ok(user) => user,
+            Err(e) => return CreateFolderResult::Err(e),
+        };
+        if ProjectsManager::get_by_id(input.project_id).is_none() {
+            log_warn!(
+                "folder_creation: Project not found [project_id={}, principal={}]",
+                input.project_id,
+                principal
+            );
+            return CreateFolderResult::Err(AppError::EntityNotFound(
+                "Project not found".to_string(),
+            ));
+        }
+        if let Err(e) = Self::validate_parent(input.project_id, input.parent_id) {
+            log_warn!(
+                "folder_creation: Invalid parent folder [project_id={}, parent_id={:?}, principal={}] - {:?}",
+                input.project_id,
+                input.parent_id,
+                principal,
+                e
+            );
+            return CreateFolderResult::Err(e);
+        }
+        if let Err(e) = Self::authorize(
+            &user,
+            input.project_id,
+            input.parent_id,
+            Permission::Document(DocumentPermission::Create),
+            "folder creation",
+        ) {
+            return CreateFolderResult::Err(e);
+        }
+        if let Err(e) = Self::validate_name(&input.name, input.project_id, input.parent_id, None) {
+            log_warn!(
+                "folder_creation: Invalid folder name [project_id={}, name='{}', principal={}] - {:?}",
+                input.project_id,
+                input.name,
+                principal,
+                e
+            );
+            return CreateFolderResult::Err(e);
+        }
+
+        let id = Self::get_next_id();
+        let folder = Folder {
+            id,
+            project_id: input.project_id,
+            parent_id: input.parent_id,
+            name: input.name.trim().to_string(),
+            created_at: ic_cdk::api::time(),
+            created_by: user.id,
+            updated_at: None,
+            inherit_permissions: true,
+            grants: vec![],
+        };
+        Self::insert(id, folder.clone());
+        log_info!(
+            "folder_creation: Created folder [id={}, name='{}', project_id={}, parent_id={:?}, user_id={}, principal={}]",
+            folder.id,
+            folder.name,
+            folder.project_id,
+            folder.parent_id,
+            user.id,
+            principal
+        );
+        CreateFolderResult::Ok(id)
+    }
+
+    pub fn rename_folder(input: RenameFolderInput) -> RenameFolderResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Folder rename attempt [principal={}, id={}, name='{}']",
+            principal,
+            input.id,
+            input.name
+        );
+
+        let user = match UserManager::authenticate(principal, "folder rename") {
+            Ok(user) => user,
+            Err(e) => return RenameFolderResult::Err(e),
+        };
+        let mut folder = match Self::get_by_id(input.id) {
+            Some(folder) => folder,
+            None => {
+                log_warn!(
+                    "folder_update: Folder not found [id={}, principal={}]",
+                    input.id,
+                    principal
+                );
+                return RenameFolderResult::Err(AppError::EntityNotFound(
+                    "Folder not found".to_string(),
+                ));
+            }
+        };
+        if let Err(e) = Self::authorize(
+            &user,
+            folder.project_id,
+            Some(folder.id),
+            Permission::Document(DocumentPermission::Update),
+            "folder rename",
+        ) {
+            return RenameFolderResult::Err(e);
+        }
+        if let Err(e) = Self::validate_name(
+            &input.name,
+            folder.project_id,
+            folder.parent_id,
+            Some(folder.id),
+        ) {
+            log_warn!(
+                "folder_update: Invalid folder name [id={}, name='{}', principal={}] - {:?}",
+                folder.id,
+                input.name,
+                principal,
+                e
+            );
+            return RenameFolderResult::Err(e);
+        }
+
+        let old_name = folder.name.clone();
+        folder.name = input.name.trim().to_string();
+        folder.updated_at = Some(ic_cdk::api::time());
+        Self::insert(folder.id, folder.clone());
+        log_info!(
+            "folder_update: Renamed folder [id={}, old_name='{}', new_name='{}', principal={}]",
+            folder.id,
+            old_name,
+            folder.name,
+            principal
+        );
+        RenameFolderResult::Ok
+    }
+
+    pub fn move_folder(input: MoveFolderInput) -> MoveFolderResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Folder move attempt [principal={}, id={}, parent_id={:?}]",
+            principal,
+            input.id,
+            input.parent_id
+        );
+
+        let user = match UserManager::authenticate(principal, "folder move") {
+            Ok(user) => user,
+            Err(e) => return MoveFolderResult::Err(e),
+        };
+        let mut folder = match Self::get_by_id(input.id) {
+            Some(folder) => folder,
+            None => {
+                log_warn!(
+                    "folder_update: Folder not found [id={}, principal={}]",
+                    input.id,
+                    principal
+                );
+                return MoveFolderResult::Err(AppError::EntityNotFound(
+                    "Folder not found".to_string(),
+                ));
+            }
+        };
+        if let Err(e) = Self::validate_parent(folder.project_id, input.parent_id) {
+            log_warn!(
+                "folder_update: Invalid parent folder [id={}, parent_id={:?}, principal={}] - {:?}",
+                folder.id,
+                input.parent_id,
+                principal,
+                e
+            );
+            return MoveFolderResult::Err(e);
+        }
+
+        if let Some(parent_id) = input.parent_id {
+            if Self::get_subtree_ids(folder.id).contains(&parent_id) {
+                log_warn!(
+                    "folder_update: Folder cannot be moved into its own subtree [id={}, parent_id={}, principal={}]",
+                    folder.id,
+                    parent_id,
+                    principal
+                );
+                return MoveFolderResult::Err(AppError::ValidationError(
+                    "A folder cannot be moved into itself or one of its subfolders".to_string(),
+                ));
+            }
+        }
+
+        let update = Permission::Document(DocumentPermission::Update);
+        if let Err(e) = Self::authorize(
+            &user,
+            folder.project_id,
+            Some(folder.id),
+            update.clone(),
+            "folder move",
+        ) {
+            return MoveFolderResult::Err(e);
+        }
+        if let Err(e) = Self::authorize(
+            &user,
+            folder.project_id,
+            input.parent_id,
+            update,
+            "folder move",
+        ) {
+            return MoveFolderResult::Err(e);
+        }
+        if let Err(e) = Self::validate_name(
+            &folder.name,
+            folder.project_id,
+            input.parent_id,
+            Some(folder.id),
+        ) {
+            log_warn!(
+                "folder_update: Folder name conflicts at the destination [id={}, parent_id={:?}, principal={}] - {:?}",
+                folder.id,
+                input.parent_id,
+                principal,
+                e
+            );
+            return MoveFolderResult::Err(e);
+        }
+
+        let old_parent_id = folder.parent_id;
+        folder.parent_id = input.parent_id;
+        folder.updated_at = Some(ic_cdk::api::time());
+        Self::insert(folder.id, folder.clone());
+        log_info!(
+            "folder_update: Moved folder [id={}, from={:?}, to={:?}, principal={}]",
+            folder.id,
+            old_parent_id,
+            folder.parent_id,
+            principal
+        );
+        // Inherited grants change with the parent
+        EncryptionManager::rotate_revoked_keys();
+        MoveFolderResult::Ok
+    }
+
+    pub fn delete_folder(input: DeleteFolderInput) -> DeleteFolderResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Folder deletion attempt [principal={}, id={}]",
+            principal,
+            input.id
+        );
+
+        let user = match UserManager::authenticate(principal, "folder deletion") {
+            Ok(user) => user,
+            Err(e) => return DeleteFolderResult::Err(e),
+        };
+        let folder = match Self::get_by_id(input.id) {
+            Some(folder) => folder,
+            None => {
+                log_warn!(
+                    "folder_deletion: Folder not found [id={}, principal={}]",
+                    input.id,
+                    principal
+                );
+                return DeleteFolderResult::Err(AppError::EntityNotFound(
+                    "Folder not found".to_string(),
+                ));
+            }
+        };
+        if let Err(e) = Self::authorize(
+            &user,
+            folder.project_id,
+            Some(folder.id),
+            Permission::Document(DocumentPermission::Delete),
+            "folder deletion",
+        ) {
+            return DeleteFolderResult::Err(e);
+        }
+
+        if RetentionManager::is_project_on_hold(folder.project_id) {
+            log_warn!(
+                "folder_deletion: Project is under legal hold [id={}, project_id={}, principal={}]",
+                folder.id,
+                folder.project_id,
+                principal
+            );
+            return DeleteFolderResult::Err(AppError::InvalidStateTransition(
+                "Folders of a project under legal hold cannot be deleted".to_string(),
+            ));
+        }
+        if !Self::get_children(folder.id).is_empty() {
+            log_warn!(
+                "folder_deletion: Folder still contains subfolders [id={}, principal={}]",
+                folder.id,
+                principal
+            );
+            return DeleteFolderResult::Err(AppError::ValidationError(
+                "Folder still contains subfolders".to_string(),
+            ));
+        }
+        if DocumentManager::get_all()
+            .iter()
+            .any(|document| document.folder_id == Some(folder.id))
+        {
+            log_warn!(
+                "folder_deletion: Folder still contains documents [id={}, principal={}]",
+                folder.id,
+                principal
+            );
+            return DeleteFolderResult::Err(AppError::ValidationError(
+                "Folder still contains documents".to_string(),
+            ));
+        }
+
+        FOLDERS.with(|folders| folders.borrow_mut().remove(&folder.id));
+        log_info!(
+            "folder_deletion: Deleted folder [id={}, name='{}', project_id={}, principal={}]",
+            folder.id,
+            folder.name,
+            folder.project_id,
+            principal
+        );
+        DeleteFolderResult::Ok
+    }
+
+    pub fn set_folder_permissions(input: SetFolderPermissionsInput) -> SetFolderPermissionsResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Folder permission update attempt [principal={}, id={}, grants={}]",
+            principal,
+            input.id,
+            input.grants.len()
+        );
+
+        let user = match UserManager::authenticate(principal, "folder permission update") {
+            Ok(user) => user,
+            Err(e) => return SetFolderPermissionsResult::Err(e),
+        };
+        let mut folder = match Self::get_by_id(input.id) {
+            Some(folder) => folder,
+            None => {
+                log_warn!(
+                    "folder_update: Folder not found [id={}, principal={}]",
+                    input.id,
+                    principal
+                );
+                return SetFolderPermissionsResult::Err(AppError::EntityNotFound(
+                    "Folder not found".to_string(),
+                ));
+            }
+        };
+
+        // Granting access is a project-level privilege; folder grants must not be
+        // able to escalate themselves.
+        let manage_members = Permission::Project(ProjectPermission::ManageMembers);
+        if !AccessControlManager::has_project_permission(
+            user.id,
+            folder.project_id,
+            &manage_members,
+        ) {
+            log_warn!(
+                "access_control: Permission denied for folder permission update [user_id={}, project_id={}, folder_id={}]",
+                user.id,
+                folder.project_id,
+                folder.id
+            );
+            return SetFolderPermissionsResult::Err(AppError::Unauthorized);
+        }
+
+        for grant in &input.grants {
+            match AccessControlManager::get_role(&grant.role_id) {
+                Some(role) if role.project_id == folder.project_id => {}
+                Some(_) => {
+                    log_warn!(
+                        "folder_update: Granted role belongs to a different project [id={}, role_id={}, principal={}]",
+                        folder.id,
+                        grant.role_id,
+                        principal
+                    );
+                    return SetFolderPermissionsResult::Err(AppError::ValidationError(format!(
+                        "Role {} belongs to a different project",
+                        grant.role_id
+                    )));
+                }
+                None => {
+                    log_warn!(
+                        "folder_update: Granted role not found [id={}, role_id={}, principal={}]",
+                        folder.id,
+                        grant.role_id,
+                        principal
+                    );
+                    return SetFolderPermissionsResult::Err(AppError::EntityNotFound(format!(
+                        "Role {} not found",
+                        grant.role_id
+                    )));
+                }
+            }
+        }
+
+        folder.inherit_permissions = input.inherit_permissions;
+        folder.grants = input.grants;
+        folder.updated_at = Some(ic_cdk::api::time());
+        Self::insert(folder.id, folder.clone());
+        log_info!(
+            "folder_update: Updated folder permissions [id={}, inherit={}, grants={}, principal={}]",
+            folder.id,
+            folder.inherit_permissions,
+            folder.grants.len(),
+            principal
+        );
+        EncryptionManager::rotate_revoked_keys();
+        SetFolderPermissionsResult::Ok
+    }
+
+    pub fn list_folders(input: ListFoldersInput) -> ListFoldersResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "folder_listing: Processing request [principal={}, project_id={}]",
+            principal,
+            input.project_id
+        );
+
+        let user = match UserManager::authenticate(principal, "folder listing") {
+            Ok(user) => user,
+            Err(e) => return ListFoldersResult::Err(e),
+        };
+
+        let can_read = ProjectsManager::is_member(input.project_id, user.id)
+            || AccessControlManager::has_project_permission(
+                user.id,
+                input.project_id,
+                &Permission::Project(ProjectPermission::Read),
+            );
+        if !can_read {
+            log_warn!(
+                "access_control: Permission denied for folder listing [user_id={}, project_id={}]",
+                user.id,
+                input.project_id
+            );
+            return ListFoldersResult::Err(AppError::Unauthorized);
+        }
+
+        let folders = Self::get_by_project(input.project_id);
+        log_debug!(
+            "folder_listing: Listed folders [principal={}, project_id={}, count={}]",
+            principal,
+            input.project_id,
+            folders.len()
+        );
+        ListFoldersResult::Ok(folders)
+    }
+}
@@ -0,0 +1,4 @@
+pub mod controllers;
+pub mod folders_manager;
+
+pub use folders_manager::FoldersManager;
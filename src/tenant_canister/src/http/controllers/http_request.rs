@@ -0,0 +1,7 @@
+use crate::http::http_manager::HttpManager;
+use shared::types::http::{HttpRequest, HttpResponse};
+
+#[ic_cdk_macros::query]
+pub fn http_request(request: HttpRequest) -> HttpResponse {
+    HttpManager::http_request(request)
+}
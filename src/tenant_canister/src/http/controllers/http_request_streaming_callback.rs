@@ -0,0 +1,9 @@
+use crate::http::http_manager::HttpManager;
+use shared::types::http::{StreamingCallbackHttpResponse, StreamingCallbackToken};
+
+#[ic_cdk_macros::query]
+pub fn http_request_streaming_callback(
+    token: StreamingCallbackToken,
+) -> StreamingCallbackHttpResponse {
+    HttpManager::http_request_streaming_callback(token)
+}
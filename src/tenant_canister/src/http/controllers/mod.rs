@@ -0,0 +1,2 @@
+pub mod http_request;
+pub mod http_request_streaming_callback;
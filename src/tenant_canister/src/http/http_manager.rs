@@ -0,0 +1,454 @@
+use shared::consts::http::{CONTENT_HTTP_PATH, CONTENT_PREVIEW_HTTP_SUFFIX, MAX_HTTP_BODY_SIZE};
+use shared::types::errors::AppError;
+use shared::types::http::{
+    HeaderField, HttpRequest, HttpResponse, StreamingCallback, StreamingCallbackHttpResponse,
+    StreamingCallbackToken, StreamingStrategy,
+};
+use shared::types::revisions::{RevisionContent, RevisionContentId, RevisionContentType};
+use shared::utils::markdown::html_document;
+use shared::{log_debug, log_warn};
+
+use crate::previews::PreviewsManager;
+use crate::revisions::revisions_manager::RevisionsManager;
+
+/// A validated request for bytes `start..end` of a content item.
+struct ContentRequest {
+    content: RevisionContent,
+    share_token: Option<String>,
+    start: u64,
+    end: u64,
+    total_size: u64,
+    is_partial: bool,
+}
+
+/// Serves revision content over HTTP at `/contents/{content_id}` and the HTML preview
+/// of Markdown content at `/contents/{content_id}/preview`. Callers without a user
+/// record (e.g. browsers going through the HTTP gateway) pass a share link token as
+/// `?token=`. Responses are not certified, so content has to be fetched
+/// through the raw domain of the canister.
+pub struct HttpManager;
+
+impl HttpManager {
+    fn get_header<'a>(headers: &'a [HeaderField], name: &str) -> Option<&'a str> {
+        headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.trim())
+    }
+
+    /// Splits a request URL into the content ID, whether the preview is requested and
+    /// the optional share token.
+    fn parse_url(url: &str) -> Option<(RevisionContentId, bool, Option<String>)> {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let path = path.strip_prefix(CONTENT_HTTP_PATH)?.trim_end_matches('/');
+        let (content_id, is_preview) = match path.strip_suffix(CONTENT_PREVIEW_HTTP_SUFFIX) {
+            Some(content_id) => (content_id, true),
+            None => (path, false),
+        };
+        let content_id = content_id.parse().ok()?;
+        let share_token = query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("token="))
+            .filter(|token| !token.is_empty())
+            .map(str::to_string);
+        Some((content_id, is_preview, share_token))
+    }
+
+    /// Resolves a `Range` header against the content size to an exclusive byte range.
+    /// Only single ranges are supported; `Ok(None)` means the whole content.
+    fn parse_range(header: Option<&str>, total_size: u64) -> Result<Option<(u64, u64)>, ()> {
+        let Some(header) = header else {
+            return Ok(None);
+        };
+        let spec = header.strip_prefix("bytes=").ok_or(())?.trim();
+        if spec.contains(',') {
+            return Err(());
+        }
+        let (first, last) = spec.split_once('-').ok_or(())?;
+        let (start, end) = match (first.trim(), last.trim()) {
+            ("", suffix) => {
+                let suffix: u64 = suffix.parse().map_err(|_| ())?;
+                if suffix == 0 {
+                    return Err(());
+                }
+                (total_size.saturating_sub(suffix), total_size)
+            }
+            (first, "") => (first.parse().map_err(|_| ())?, total_size),
+            (first, last) => {
+                let first: u64 = first.parse().map_err(|_| ())?;
+                let last: u64 = last.parse().map_err(|_| ())?;
+                if last < first {
+                    return Err(());
+                }
+                (first, last.saturating_add(1).min(total_size))
+            }
+        };
+        if start >= total_size {
+            return Err(());
+        }
+        Ok(Some((start, end)))
+    }
+
+    fn content_type(content: &RevisionContent) -> String {
+        // Only clients holding the document key can make sense of encrypted content
+        if content.encryption.is_some() {
+            return "application/octet-stream".to_string();
+        }
+        if let Some(mime_type) = &content.mime_type {
+            return mime_type.clone();
+        }
+        let extension = content
+            .file_name
+            .as_deref()
+            .and_then(|name| name.rsplit_once('.'))
+            .map(|(_, extension)| extension.to_ascii_lowercase());
+        match (&content.content_type, extension.as_deref()) {
+            (RevisionContentType::Markdown, _) | (_, Some("md")) => "text/markdown; charset=utf-8",
+            (_, Some("pdf")) => "application/pdf",
+            (_, Some("png")) => "image/png",
+            (_, Some("jpg" | "jpeg")) => "image/jpeg",
+            (_, Some("gif")) => "image/gif",
+            (_, Some("svg")) => "image/svg+xml",
+            (_, Some("txt")) => "text/plain; charset=utf-8",
+            (_, Some("csv")) => "text/csv; charset=utf-8",
+            (_, Some("json")) => "application/json",
+            (_, Some("zip")) => "application/zip",
+            _ => "application/octet-stream",
+        }
+        .to_string()
+    }
+
+    fn content_disposition(content: &RevisionContent) -> String {
+        match &content.file_name {
+            Some(file_name) => {
+                let file_name: String = file_name
+                    .chars()
+                    .filter(|c| !c.is_control() && *c != '"' && *c != '\\')
+                    .collect();
+                format!("attachment; filename=\"{}\"", file_name)
+            }
+            None => "attachment".to_string(),
+        }
+    }
+
+    fn error_response(status_code: u16, message: &str) -> HttpResponse {
+        HttpResponse {
+            status_code,
+            headers: vec![(
+                "Content-Type".to_string(),
+                "text/plain; charset=utf-8".to_string(),
+            )],
+            body: message.as_bytes().to_vec(),
+            streaming_strategy: None,
+            upgrade: None,
+        }
+    }
+
+    fn error_status(error: &AppError) -> u16 {
+        match error {
+            AppError::EntityNotFound(_) => 404,
+            AppError::Unauthorized => 403,
+            _ => 400,
+        }
+    }
+
+    fn resolve_request(request: &HttpRequest) -> Result<ContentRequest, Box<HttpResponse>> {
+        let (content_id, _, share_token) = Self::parse_url(&request.url)
+            .ok_or_else(|| Box::new(Self::error_response(404, "Not found")))?;
+
+        let principal = ic_cdk::api::msg_caller();
+        if let Err(e) = RevisionsManager::authorize_content_download(
+            content_id,
+            share_token.as_deref(),
+            principal,
+        ) {
+            log_warn!(
+                "content_download: HTTP access denied [content_id={}, principal={}] - {:?}",
+                content_id,
+                principal,
+                e
+            );
+            return Err(Box::new(Self::error_response(
+                Self::error_status(&e),
+                "Content is not available",
+            )));
+        }
+
+        let content = RevisionsManager::get_revision_content_by_id(content_id)
+            .ok_or_else(|| Box::new(Self::error_response(404, "Content not found")))?;
+        let total_size = RevisionsManager::get_content_size(&content);
+        let range = Self::parse_range(Self::get_header(&request.headers, "Range"), total_size)
+            .map_err(|()| {
+                let mut response = Self::error_response(416, "Range not satisfiable");
+                response.headers.push((
+                    "Content-Range".to_string(),
+                    format!("bytes */{}", total_size),
+                ));
+                response
+            })?;
+        let (start, end) = range.unwrap_or((0, total_size));
+
+        Ok(ContentRequest {
+            content,
+            share_token,
+            start,
+            end,
+            total_size,
+            is_partial: range.is_some(),
+        })
+    }
+
+    /// Renders the preview as a standalone page. Scripts and external resources other
+    /// than images are blocked by the content security policy.
+    fn preview_response(
+        content_id: RevisionContentId,
+        share_token: Option<&str>,
+        is_head: bool,
+    ) -> HttpResponse {
+        let preview = match PreviewsManager::authorized_preview(content_id, share_token) {
+            Ok(preview) => preview,
+            Err(e) => {
+                return Self::error_response(Self::error_status(&e), "Preview is not available")
+            }
+        };
+
+        let body = html_document(preview.outline.title.as_deref(), &preview.html).into_bytes();
+        HttpResponse {
+            status_code: 200,
+            headers: vec![
+                (
+                    "Content-Type".to_string(),
+                    "text/html; charset=utf-8".to_string(),
+                ),
+                ("Content-Length".to_string(), body.len().to_string()),
+                (
+                    "Content-Security-Policy".to_string(),
+                    "default-src 'none'; img-src https: data:; style-src 'unsafe-inline'"
+                        .to_string(),
+                ),
+                ("X-Content-Type-Options".to_string(), "nosniff".to_string()),
+            ],
+            body: if is_head { vec![] } else { body },
+            streaming_strategy: None,
+            upgrade: None,
+        }
+    }
+
+    /// Reads the body part starting at `start` and the token for the part after it.
+    fn read_body_part(
+        content: &RevisionContent,
+        share_token: Option<String>,
+        start: u64,
+        end: u64,
+    ) -> Option<(Vec<u8>, Option<StreamingCallbackToken>)> {
+        let part_end = end.min(start.saturating_add(MAX_HTTP_BODY_SIZE));
+        let body = RevisionsManager::read_content_range(content, start, part_end)?;
+        let token = (part_end < end).then_some(StreamingCallbackToken {
+            content_id: content.id,
+            share_token,
+            offset: part_end,
+            end,
+        });
+        Some((body, token))
+    }
+
+    pub fn http_request(request: HttpRequest) -> HttpResponse {
+        log_debug!(
+            "content_download: HTTP request [method={}, url={}]",
+            request.method,
+            request.url
+        );
+
+        let is_head = request.method.eq_ignore_ascii_case("HEAD");
+        if !is_head && !request.method.eq_ignore_ascii_case("GET") {
+            let mut response = Self::error_response(405, "Method not allowed");
+            response
+                .headers
+                .push(("Allow".to_string(), "GET, HEAD".to_string()));
+            return response;
+        }
+
+        if let Some((content_id, true, share_token)) = Self::parse_url(&request.url) {
+            return Self::preview_response(content_id, share_token.as_deref(), is_head);
+        }
+
+        let request = match Self::resolve_request(&request) {
+            Ok(request) => request,
+            Err(response) => return *response,
+        };
+
+        let mut headers = vec![
+            (
+                "Content-Type".to_string(),
+                Self::content_type(&request.content),
+            ),
+            (
+                "Content-Disposition".to_string(),
+                Self::content_disposition(&request.content),
+            ),
+            (
+                "Content-Length".to_string(),
+                (request.end - request.start).to_string(),
+            ),
+            ("Accept-Ranges".to_string(), "bytes".to_string()),
+        ];
+        if request.is_partial {
+            headers.push((
+                "Content-Range".to_string(),
+                format!(
+                    "bytes {}-{}/{}",
+                    request.start,
+                    request.end.saturating_sub(1),
+                    request.total_size
+                ),
+            ));
+        }
+        let status_code = if request.is_partial { 206 } else { 200 };
+
+        if is_head {
+            return HttpResponse {
+                status_code,
+                headers,
+                body: vec![],
+                streaming_strategy: None,
+                upgrade: None,
+            };
+        }
+
+        let Some((body, token)) = Self::read_body_part(
+            &request.content,
+            request.share_token,
+            request.start,
+            request.end,
+        ) else {
+            log_warn!(
+                "content_download: Content data missing [content_id={}]",
+                request.content.id
+            );
+            return Self::error_response(500, "Content data is incomplete");
+        };
+
+        HttpResponse {
+            status_code,
+            headers,
+            body,
+            streaming_strategy: token.map(|token| StreamingStrategy::Callback {
+                callback: StreamingCallback::new(
+                    ic_cdk::api::canister_self(),
+                    "http_request_streaming_callback".to_string(),
+                ),
+                token,
+            }),
+            upgrade: None,
+        }
+    }
+
+    /// Returns the next body part of a streamed download. Access is checked again, so
+    /// a revoked share link stops the download.
+    pub fn http_request_streaming_callback(
+        token: StreamingCallbackToken,
+    ) -> StreamingCallbackHttpResponse {
+        let principal = ic_cdk::api::msg_caller();
+        let content = RevisionsManager::authorize_content_download(
+            token.content_id,
+            token.share_token.as_deref(),
+            principal,
+        )
+        .and_then(|()| {
+            RevisionsManager::get_revision_content_by_id(token.content_id)
+                .ok_or_else(|| AppError::EntityNotFound("Content not found".to_string()))
+        });
+
+        let part = content.as_ref().ok().and_then(|content| {
+            Self::read_body_part(content, token.share_token.clone(), token.offset, token.end)
+        });
+        match part {
+            Some((body, token)) => StreamingCallbackHttpResponse { body, token },
+            None => {
+                log_warn!(
+                    "content_download: Streaming stopped [content_id={}, offset={}, principal={}] - {:?}",
+                    token.content_id,
+                    token.offset,
+                    principal,
+                    content.err()
+                );
+                StreamingCallbackHttpResponse {
+                    body: vec![],
+                    token: None,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HttpManager;
+
+    #[test]
+    fn parse_range_without_header_is_whole_content() {
+        assert_eq!(HttpManager::parse_range(None, 100), Ok(None));
+    }
+
+    #[test]
+    fn parse_range_bounded() {
+        assert_eq!(
+            HttpManager::parse_range(Some("bytes=10-19"), 100),
+            Ok(Some((10, 20)))
+        );
+        assert_eq!(
+            HttpManager::parse_range(Some("bytes=90-150"), 100),
+            Ok(Some((90, 100)))
+        );
+        assert_eq!(HttpManager::parse_range(Some("bytes=20-10"), 100), Err(()));
+    }
+
+    #[test]
+    fn parse_range_open() {
+        assert_eq!(
+            HttpManager::parse_range(Some("bytes=40-"), 100),
+            Ok(Some((40, 100)))
+        );
+    }
+
+    #[test]
+    fn parse_range_suffix() {
+        assert_eq!(
+            HttpManager::parse_range(Some("bytes=-30"), 100),
+            Ok(Some((70, 100)))
+        );
+        assert_eq!(
+            HttpManager::parse_range(Some("bytes=-500"), 100),
+            Ok(Some((0, 100)))
+        );
+        assert_eq!(HttpManager::parse_range(Some("bytes=-0"), 100), Err(()));
+    }
+
+    #[test]
+    fn parse_range_start_beyond_end() {
+        assert_eq!(HttpManager::parse_range(Some("bytes=100-"), 100), Err(()));
+        assert_eq!(
+            HttpManager::parse_range(Some("bytes=150-200"), 100),
+            Err(())
+        );
+        assert_eq!(HttpManager::parse_range(Some("bytes=-10"), 0), Err(()));
+    }
+
+    #[test]
+    fn parse_range_last_byte_overflow() {
+        assert_eq!(
+            HttpManager::parse_range(Some("bytes=0-18446744073709551615"), 100),
+            Ok(Some((0, 100)))
+        );
+    }
+
+    #[test]
+    fn parse_range_rejects_unsupported() {
+        assert_eq!(HttpManager::parse_range(Some("items=0-10"), 100), Err(()));
+        assert_eq!(
+            HttpManager::parse_range(Some("bytes=0-10,20-30"), 100),
+            Err(())
+        );
+        assert_eq!(HttpManager::parse_range(Some("bytes=a-b"), 100), Err(()));
+    }
+}
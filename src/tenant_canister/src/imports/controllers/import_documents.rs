@@ -0,0 +1,7 @@
+use crate::imports::ImportsManager;
+use shared::types::imports::{ImportDocumentsInput, ImportDocumentsResult};
+
+#[ic_cdk_macros::update]
+pub fn import_documents(input: ImportDocumentsInput) -> ImportDocumentsResult {
+    ImportsManager::import_documents(input)
+}
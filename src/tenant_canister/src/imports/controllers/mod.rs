@@ -0,0 +1 @@
+pub mod import_documents;
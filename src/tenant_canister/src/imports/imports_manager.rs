@@ -0,0 +1,579 @@
+use serde_json::Value;
+use shared::consts::exports::{EXPORT_FORMAT, EXPORT_FORMAT_VERSION};
+use shared::types::access_control::{DocumentPermission, Permission};
+use shared::types::documents::{CreateDocumentInput, CreateDocumentResult};
+use shared::types::errors::AppError;
+use shared::types::folders::FolderId;
+use shared::types::imports::{
+    ImportContent, ImportDocument, ImportDocumentReport, ImportDocumentsInput,
+    ImportDocumentsResult, ImportError, ImportFile, ImportOrigin, ImportReport, ImportRevision,
+    ImportSource,
+};
+use shared::types::projects::ProjectId;
+use shared::types::revisions::{
+    CreateRevisionInput, CreateRevisionResult, RevisionContent, RevisionContentData,
+    RevisionContentId, RevisionContentType,
+};
+use shared::types::users::User;
+use shared::{log_debug, log_info, log_warn};
+use std::collections::HashMap;
+
+use crate::documents::DocumentManager;
+use crate::folders::folders_manager::FoldersManager;
+use crate::projects::projects_manager::ProjectsManager;
+use crate::quotas::quotas_manager::QuotasManager;
+use crate::revisions::revisions_manager::RevisionsManager;
+use crate::uploads::uploads_manager::UploadsManager;
+use crate::users::user_manager::UserManager;
+
+/// Bulk import of documents with their revisions, from a list of documents or from the
+/// manifest of an export archive whose files were uploaded with upload sessions.
+/// Everything is validated before anything is created; an import with errors, or a dry
+/// run, only returns the report.
+pub struct ImportsManager;
+
+impl ImportsManager {
+    fn invalid_manifest(message: &str) -> AppError {
+        AppError::InvalidInput(format!("Invalid export manifest: {}", message))
+    }
+
+    fn manifest_array<'a>(value: &'a Value, key: &str) -> &'a [Value] {
+        value
+            .get(key)
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    fn manifest_source_id(value: &Value) -> Option<String> {
+        value
+            .get("id")
+            .and_then(Value::as_u64)
+            .map(|id| id.to_string())
+    }
+
+    fn manifest_author(value: &Value) -> Option<String> {
+        value
+            .get("created_by")
+            .and_then(|user| user.get("name"))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    }
+
+    fn is_purged_file(file: &Value) -> bool {
+        file.get("purged").is_some_and(|purged| !purged.is_null())
+    }
+
+    /// Documents described by an export manifest, each with the errors of parts that
+    /// cannot be imported. Folders of the exporting canister are not carried over, nor
+    /// are revisions whose content it purged.
+    fn documents_from_manifest(
+        manifest: &str,
+        files: &[ImportFile],
+    ) -> Result<Vec<(ImportDocument, Vec<ImportError>)>, AppError> {
+        let manifest: Value =
+            serde_json::from_str(manifest).map_err(|e| Self::invalid_manifest(&e.to_string()))?;
+        if manifest.get("format").and_then(Value::as_str) != Some(EXPORT_FORMAT) {
+            return Err(Self::invalid_manifest("not a permatrust export"));
+        }
+        let format_version = manifest
+            .get("format_version")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| Self::invalid_manifest("missing format version"))?;
+        if format_version > EXPORT_FORMAT_VERSION as u64 {
+            return Err(Self::invalid_manifest(&format!(
+                "format version {} is not supported",
+                format_version
+            )));
+        }
+        let documents = Self::manifest_array(&manifest, "documents");
+
+        let uploads: HashMap<&str, RevisionContentId> = files
+            .iter()
+            .map(|file| (file.path.as_str(), file.content_id))
+            .collect();
+        // Hard links share the exported content ID, any uploaded copy will do
+        let mut uploads_by_content = HashMap::new();
+        for document in documents {
+            for revision in Self::manifest_array(document, "revisions") {
+                for file in Self::manifest_array(revision, "files") {
+                    let path = file.get("path").and_then(Value::as_str).unwrap_or_default();
+                    if let (Some(content_id), Some(uploaded)) = (
+                        file.get("content_id").and_then(Value::as_u64),
+                        uploads.get(path),
+                    ) {
+                        uploads_by_content.entry(content_id).or_insert(*uploaded);
+                    }
+                }
+            }
+        }
+
+        let mut result = Vec::new();
+        for document in documents {
+            let mut errors = Vec::new();
+            if document.get("encrypted").and_then(Value::as_bool) == Some(true) {
+                errors.push(ImportError {
+                    revision_index: None,
+                    content_index: None,
+                    error: AppError::ValidationError(
+                        "Encrypted documents cannot be imported, their content is encrypted with keys of the exporting canister".to_string(),
+                    ),
+                });
+            }
+            let mut revisions = Vec::new();
+            for (revision_index, revision) in Self::manifest_array(document, "revisions")
+                .iter()
+                .enumerate()
+            {
+                let files = Self::manifest_array(revision, "files");
+                let mut contents = Vec::new();
+                for (content_index, file) in files.iter().enumerate() {
+                    if Self::is_purged_file(file) {
+                        continue;
+                    }
+                    let path = file.get("path").and_then(Value::as_str).unwrap_or_default();
+                    let uploaded = uploads.get(path).copied().or_else(|| {
+                        file.get("content_id")
+                            .and_then(Value::as_u64)
+                            .and_then(|content_id| uploads_by_content.get(&content_id).copied())
+                    });
+                    match uploaded {
+                        Some(content_id) => contents.push(ImportContent::Upload {
+                            content_id,
+                            sha256: file
+                                .get("sha256")
+                                .and_then(Value::as_str)
+                                .map(str::to_string),
+                        }),
+                        None => errors.push(ImportError {
+                            revision_index: Some(revision_index as u32),
+                            content_index: Some(content_index as u32),
+                            error: AppError::EntityNotFound(format!(
+                                "File '{}' has not been uploaded",
+                                path
+                            )),
+                        }),
+                    }
+                }
+                // Revisions whose content was purged by the exporting tenant have no
+                // files in the archive
+                if !files.is_empty() && files.iter().all(Self::is_purged_file) {
+                    continue;
+                }
+                revisions.push(ImportRevision {
+                    source_id: Self::manifest_source_id(revision),
+                    original_created_at: revision.get("created_at").and_then(Value::as_u64),
+                    original_created_by: Self::manifest_author(revision),
+                    contents,
+                });
+            }
+            result.push((
+                ImportDocument {
+                    source_id: Self::manifest_source_id(document),
+                    title: document
+                        .get("title")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    folder_id: None,
+                    document_type: document
+                        .get("document_type")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                    original_created_at: document.get("created_at").and_then(Value::as_u64),
+                    original_created_by: Self::manifest_author(document),
+                    revisions,
+                },
+                errors,
+            ));
+        }
+        Ok(result)
+    }
+
+    /// Revision content to create for an imported content item. Uploads keep the type
+    /// and name they were declared with.
+    fn revision_content(content: &ImportContent) -> RevisionContent {
+        match content {
+            ImportContent::Direct {
+                file_name,
+                content_type,
+                mime_type,
+                bytes,
+            } => RevisionContent {
+                id: 0,
+                file_name: file_name.clone(),
+                content_type: content_type.clone(),
+                mime_type: Some(mime_type.clone()),
+                encryption: None,
+                content_data: Some(RevisionContentData::Direct {
+                    bytes: bytes.clone(),
+                }),
+                purged: None,
+                sha256: None,
+            },
+            ImportContent::Upload { content_id, .. } => {
+                let (file_name, content_type, mime_type) =
+                    match UploadsManager::get_unattached_upload(*content_id) {
+                        Some(session) => {
+                            (session.file_name, session.content_type, session.mime_type)
+                        }
+                        // Attached by an earlier revision of the import
+                        None => RevisionsManager::get_revision_content_by_id(*content_id)
+                            .map(|content| {
+                                (content.file_name, content.content_type, content.mime_type)
+                            })
+                            .unwrap_or((None, RevisionContentType::Upload, None)),
+                    };
+                RevisionContent {
+                    id: *content_id,
+                    file_name,
+                    content_type,
+                    mime_type,
+                    encryption: None,
+                    content_data: None,
+                    purged: None,
+                    sha256: None,
+                }
+            }
+        }
+    }
+
+    fn validate_content(user: &User, content: &ImportContent) -> Result<(), AppError> {
+        match content {
+            ImportContent::Direct { bytes, .. } => {
+                RevisionsManager::validate_direct_content(&Self::revision_content(content), bytes)
+                    .map(|_| ())
+            }
+            ImportContent::Upload { content_id, sha256 } => {
+                UploadsManager::authorize_attachment(user.id, *content_id)?;
+                let session =
+                    UploadsManager::get_unattached_upload(*content_id).ok_or_else(|| {
+                        AppError::EntityNotFound(format!("Content {} not found", content_id))
+                    })?;
+                if session.encryption.is_some() {
+                    return Err(AppError::ValidationError(
+                        "Encrypted uploads cannot be imported".to_string(),
+                    ));
+                }
+                if sha256
+                    .as_ref()
+                    .is_some_and(|sha256| !sha256.eq_ignore_ascii_case(&session.expected_sha256))
+                {
+                    return Err(AppError::ValidationError(format!(
+                        "Checksum of content {} does not match the upload",
+                        content_id
+                    )));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn validate_document(
+        user: &User,
+        project_id: ProjectId,
+        folder_id: Option<FolderId>,
+        document: &ImportDocument,
+    ) -> Vec<ImportError> {
+        let mut errors = Vec::new();
+        let mut document_error = |error| {
+            errors.push(ImportError {
+                revision_index: None,
+                content_index: None,
+                error,
+            })
+        };
+        if document.title.trim().is_empty() {
+            document_error(AppError::ValidationError(
+                "Title must not be empty".to_string(),
+            ));
+        }
+        if let Err(error) = DocumentManager::normalize_document_type(document.document_type.clone())
+        {
+            document_error(error);
+        }
+        if let Some(folder_id) = folder_id {
+            match FoldersManager::get_by_id(folder_id) {
+                Some(folder) if folder.project_id == project_id => {}
+                Some(_) => document_error(AppError::ValidationError(
+                    "Folder belongs to a different project".to_string(),
+                )),
+                None => document_error(AppError::EntityNotFound("Folder not found".to_string())),
+            }
+        }
+        if !FoldersManager::has_permission(
+            user.id,
+            project_id,
+            folder_id,
+            &Permission::Document(DocumentPermission::Create),
+        ) {
+            document_error(AppError::Unauthorized);
+        }
+
+        for (revision_index, revision) in document.revisions.iter().enumerate() {
+            if revision.contents.is_empty() {
+                errors.push(ImportError {
+                    revision_index: Some(revision_index as u32),
+                    content_index: None,
+                    error: AppError::ValidationError("Revision has no content".to_string()),
+                });
+            }
+            for (content_index, content) in revision.contents.iter().enumerate() {
+                if let Err(error) = Self::validate_content(user, content) {
+                    errors.push(ImportError {
+                        revision_index: Some(revision_index as u32),
+                        content_index: Some(content_index as u32),
+                        error,
+                    });
+                }
+            }
+        }
+        errors
+    }
+
+    fn import_origin(
+        source_id: &Option<String>,
+        original_created_at: Option<u64>,
+        original_created_by: &Option<String>,
+        user: &User,
+        now: u64,
+    ) -> ImportOrigin {
+        ImportOrigin {
+            source_id: source_id.clone(),
+            original_created_at,
+            original_created_by: original_created_by.clone(),
+            imported_at: now,
+            imported_by: user.id,
+        }
+    }
+
+    /// Creates a validated document and its revisions. Stops at the first failure, later
+    /// revisions would otherwise be imported out of order.
+    fn import_document(
+        user: &User,
+        project_id: ProjectId,
+        folder_id: Option<FolderId>,
+        document: &ImportDocument,
+        report: &mut ImportDocumentReport,
+        now: u64,
+    ) {
+        let document_id = match DocumentManager::create_document(CreateDocumentInput {
+            project_id,
+            title: document.title.trim().to_string(),
+            folder_id,
+            encrypted: None,
+            document_type: document.document_type.clone(),
+        }) {
+            CreateDocumentResult::Ok(document_id) => document_id,
+            CreateDocumentResult::Err(error) => {
+                report.errors.push(ImportError {
+                    revision_index: None,
+                    content_index: None,
+                    error,
+                });
+                return;
+            }
+        };
+        DocumentManager::set_import_origin(
+            document_id,
+            Self::import_origin(
+                &document.source_id,
+                document.original_created_at,
+                &document.original_created_by,
+                user,
+                now,
+            ),
+        );
+        report.document_id = Some(document_id);
+
+        for (revision_index, revision) in document.revisions.iter().enumerate() {
+            let input = CreateRevisionInput {
+                project_id,
+                document_id,
+                contents: revision
+                    .contents
+                    .iter()
+                    .map(Self::revision_content)
+                    .collect(),
+            };
+            match RevisionsManager::create_revision(input) {
+                CreateRevisionResult::Ok(revision_id) => {
+                    RevisionsManager::set_import_origin(
+                        revision_id,
+                        Self::import_origin(
+                            &revision.source_id,
+                            revision.original_created_at,
+                            &revision.original_created_by,
+                            user,
+                            now,
+                        ),
+                    );
+                    report.revision_ids.push(revision_id);
+                }
+                CreateRevisionResult::Err(error) => {
+                    report.errors.push(ImportError {
+                        revision_index: Some(revision_index as u32),
+                        content_index: None,
+                        error,
+                    });
+                    return;
+                }
+            }
+        }
+    }
+
+    pub fn import_documents(input: ImportDocumentsInput) -> ImportDocumentsResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Document import attempt [principal={}, project_id={}, dry_run={}]",
+            principal,
+            input.project_id,
+            input.dry_run
+        );
+
+        let user = match UserManager::authenticate(principal, "document import") {
+            Ok(user) => user,
+            Err(e) => return ImportDocumentsResult::Err(e),
+        };
+        let project = match ProjectsManager::get_by_id(input.project_id) {
+            Some(project) => project,
+            None => {
+                log_warn!(
+                    "document_import: Project not found [project_id={}, principal={}]",
+                    input.project_id,
+                    principal
+                );
+                return ImportDocumentsResult::Err(AppError::EntityNotFound(
+                    "Project not found".to_string(),
+                ));
+            }
+        };
+        if !project.members.contains(&user.id) {
+            log_warn!(
+                "access_control: Permission denied for document import [user_id={}, project_id={}]",
+                user.id,
+                project.id
+            );
+            return ImportDocumentsResult::Err(AppError::Unauthorized);
+        }
+        let sources = match input.source {
+            ImportSource::Documents(documents) => documents
+                .into_iter()
+                .map(|document| (document, Vec::new()))
+                .collect::<Vec<_>>(),
+            ImportSource::ExportManifest { manifest, files } => {
+                match Self::documents_from_manifest(&manifest, &files) {
+                    Ok(sources) => sources,
+                    Err(e) => {
+                        log_warn!(
+                            "document_import: Invalid export manifest [project_id={}, principal={}] - {:?}",
+                            input.project_id,
+                            principal,
+                            e
+                        );
+                        return ImportDocumentsResult::Err(e);
+                    }
+                }
+            }
+        };
+        let (documents, manifest_errors): (Vec<_>, Vec<_>) = sources.into_iter().unzip();
+
+        let mut report = ImportReport {
+            dry_run: input.dry_run,
+            imported: false,
+            document_count: documents.len() as u32,
+            revision_count: 0,
+            content_count: 0,
+            direct_bytes: 0,
+            documents: Vec::new(),
+        };
+        let mut folder_ids = Vec::new();
+        for (index, (document, mut errors)) in documents.iter().zip(manifest_errors).enumerate() {
+            let folder_id = document.folder_id.or(input.folder_id);
+            errors.extend(Self::validate_document(
+                &user,
+                input.project_id,
+                folder_id,
+                document,
+            ));
+            for revision in &document.revisions {
+                report.revision_count += 1;
+                for content in &revision.contents {
+                    report.content_count += 1;
+                    if let ImportContent::Direct { bytes, .. } = content {
+                        report.direct_bytes += bytes.len() as u64;
+                    }
+                }
+            }
+            folder_ids.push(folder_id);
+            report.documents.push(ImportDocumentReport {
+                index: index as u32,
+                source_id: document.source_id.clone(),
+                title: document.title.clone(),
+                document_id: None,
+                revision_ids: Vec::new(),
+                errors,
+            });
+        }
+        if let Err(e) = QuotasManager::check_quota(report.direct_bytes) {
+            log_warn!(
+                "document_import: Import exceeds the storage quota [project_id={}, direct_bytes={}, principal={}] - {:?}",
+                input.project_id,
+                report.direct_bytes,
+                principal,
+                e
+            );
+            return ImportDocumentsResult::Err(e);
+        }
+
+        let invalid = report
+            .documents
+            .iter()
+            .filter(|document| !document.errors.is_empty())
+            .count();
+        if input.dry_run || invalid > 0 {
+            log_info!(
+                "document_import: Import validated [project_id={}, dry_run={}, documents={}, invalid_documents={}, user_id={}, principal={}]",
+                input.project_id,
+                input.dry_run,
+                report.document_count,
+                invalid,
+                user.id,
+                principal
+            );
+            return ImportDocumentsResult::Ok(report);
+        }
+
+        let now = ic_cdk::api::time();
+        for ((document, folder_id), document_report) in documents
+            .iter()
+            .zip(folder_ids)
+            .zip(report.documents.iter_mut())
+        {
+            Self::import_document(
+                &user,
+                input.project_id,
+                folder_id,
+                document,
+                document_report,
+                now,
+            );
+        }
+        report.imported = report
+            .documents
+            .iter()
+            .all(|document| document.errors.is_empty());
+
+        log_info!(
+            "document_import: Import completed [project_id={}, documents={}, revisions={}, contents={}, complete={}, user_id={}, principal={}]",
+            input.project_id,
+            report.document_count,
+            report.revision_count,
+            report.content_count,
+            report.imported,
+            user.id,
+            principal
+        );
+        ImportDocumentsResult::Ok(report)
+    }
+}
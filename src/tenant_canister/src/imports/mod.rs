@@ -0,0 +1,4 @@
+pub mod controllers;
+pub mod imports_manager;
+
+pub use imports_manager::ImportsManager;
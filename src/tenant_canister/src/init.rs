@@ -1,6 +1,11 @@
+use crate::access_control::AccessControlManager;
 use crate::logs::logs_manager::LogsManager;
-use crate::organization::create_init_organization;
+use crate::organization::{create_init_organization, OrganizationManager};
 use crate::projects::projects_manager::ProjectsManager;
+use crate::quotas::quotas_manager::QuotasManager;
+use crate::reviews::ReviewsManager;
+use crate::revisions::revisions_manager::RevisionsManager;
+use crate::uploads::uploads_manager::UploadsManager;
 use crate::{access_control::init_default_roles, users::user_manager::UserManager};
 use ic_cdk_macros::{init, post_upgrade, pre_upgrade};
 use shared::types::logs::CanisterOrigin;
@@ -64,6 +69,16 @@ fn create_initial_entities(input: CreateInitTenantCanisterInput) -> Result<(), S
         }
     };
 
+    if let Err(e) = AccessControlManager::assign_admin_role(user.id, project_id) {
+        log_error!(
+            "role_assignment: Failed to assign admin role to initial user [user_id={}, project_id={}] - {:?}",
+            user.id,
+            project_id,
+            e
+        );
+        return Err(format!("Failed to assign admin role: {:?}", e));
+    }
+
     let organization_input = CreateInitOrganizationInput {
         name: input.organization.name.clone(),
         created_by: user.id,
@@ -92,6 +107,24 @@ fn create_initial_entities(input: CreateInitTenantCanisterInput) -> Result<(), S
     Ok(())
 }
 
+// Tenants created before owners were granted the Admin role get it on upgrade
+fn assign_owner_admin_role() {
+    let Some(organization) = OrganizationManager::get_organization() else {
+        return;
+    };
+    let Some(project_id) = organization.projects.first().copied() else {
+        return;
+    };
+    if let Err(e) = AccessControlManager::assign_admin_role(organization.created_by, project_id) {
+        log_error!(
+            "role_assignment: Failed to assign admin role to tenant owner [user_id={}, project_id={}] - {:?}",
+            organization.created_by,
+            project_id,
+            e
+        );
+    }
+}
+
 #[init]
 fn init(input: CreateInitTenantCanisterInput) {
     // Initialize core systems first
@@ -99,6 +132,7 @@ fn init(input: CreateInitTenantCanisterInput) {
     let log_storage = LogsManager::init_log_storage();
     set_log_storage(log_storage);
     init_default_roles();
+    RevisionsManager::skip_storage_migration();
 
     log_info!(
         "initialization: Starting tenant canister [org='{}', project='{}']",
@@ -115,6 +149,9 @@ fn init(input: CreateInitTenantCanisterInput) {
         ic_cdk::trap(&error);
     }
 
+    UploadsManager::start_cleanup_timer();
+    ReviewsManager::start_review_timer();
+    RevisionsManager::start_retention_timer();
     log_info!("initialization: Tenant canister initialized successfully");
 }
 
@@ -134,5 +171,11 @@ fn post_upgrade() {
     init_default_roles();
 
     log_info!("upgrade_start: Post-upgrade initiated");
+    assign_owner_admin_role();
+    QuotasManager::init_usage();
+    UploadsManager::start_cleanup_timer();
+    ReviewsManager::start_review_timer();
+    RevisionsManager::start_retention_timer();
+    RevisionsManager::start_storage_migration();
     log_info!("upgrade_complete: Tenant canister post-upgrade completed successfully");
 }
@@ -3,12 +3,51 @@ use shared::types::access_control::{
     GetProjectRolesInput, GetProjectRolesResult, UpdateRolePermissionsInput,
     UpdateRolePermissionsResult,
 };
+use shared::types::approvals::{
+    ApproveRevisionInput, GetApprovalPolicyInput, GetApprovalPolicyResult,
+    ListRevisionSignaturesInput, ListRevisionSignaturesResult, ObsoleteRevisionInput,
+    RejectRevisionInput, ReleaseRevisionInput, RequestSigningChallengeInput,
+    RequestSigningChallengeResult, RevisionStatusResult, SetApprovalPolicyInput,
+    SetApprovalPolicyResult, SubmitRevisionForReviewInput, VerifySignaturesInput,
+    VerifySignaturesResult,
+};
+use shared::types::comments::{
+    CommentIdInput, CreateCommentInput, CreateCommentResult, ListCommentsInput, ListCommentsResult,
+    ReopenCommentThreadResult, ResolveCommentThreadResult, UpdateCommentInput, UpdateCommentResult,
+};
+use shared::types::document_links::{
+    CreateDocumentLinkInput, CreateDocumentLinkResult, DeleteDocumentLinkInput,
+    DeleteDocumentLinkResult, GetDocumentImpactInput, GetDocumentImpactResult,
+    ListDocumentLinksInput, ListDocumentLinksResult,
+};
 use shared::types::documents::{
     CreateDocumentInput, CreateDocumentResult, ListDocumentsInput, ListDocumentsResult,
+    SetDocumentTypeInput, SetDocumentTypeResult,
+};
+use shared::types::encryption::{
+    DeriveDocumentKeyInput, DeriveDocumentKeyResult, GetEncryptionPublicKeyResult,
+    RotateDocumentKeyInput, RotateDocumentKeyResult,
+};
+use shared::types::exports::{
+    BeginExportInput, BeginExportResult, GetExportChunkInput, GetExportChunkResult,
+};
+use shared::types::folders::{
+    CreateFolderInput, CreateFolderResult, DeleteFolderInput, DeleteFolderResult, ListFoldersInput,
+    ListFoldersResult, MoveDocumentInput, MoveDocumentResult, MoveFolderInput, MoveFolderResult,
+    RenameFolderInput, RenameFolderResult, SetFolderPermissionsInput, SetFolderPermissionsResult,
+};
+use shared::types::http::{
+    HttpRequest, HttpResponse, StreamingCallbackHttpResponse, StreamingCallbackToken,
 };
+use shared::types::imports::{ImportDocumentsInput, ImportDocumentsResult};
 use shared::types::invites::{CreateInviteResult, GetInviteResult, ListInvitesResult};
 use shared::types::logs::{ListLogsInput, ListLogsResult};
 use shared::types::management::{CreateInitTenantCanisterInput, UpgradeCanisterResult};
+use shared::types::notifications::{
+    GetUnreadNotificationCountResult, ListNotificationsInput, ListNotificationsResult,
+    ListSubscriptionsResult, MarkNotificationsReadInput, MarkNotificationsReadResult,
+    SubscribeInput, SubscribeResult, UnsubscribeInput, UnsubscribeResult,
+};
 use shared::types::organization::{
     CreateOrganizationInput, CreateOrganizationResult, GetOrganizationResult,
 };
@@ -17,13 +56,39 @@ use shared::types::projects::{
     CreateProjectInput, CreateProjectResult, ListProjectMembersInput, ListProjectMembersResult,
     ListProjectsResult,
 };
+use shared::types::quotas::{GetStorageUsageResult, SetStorageQuotaInput, SetStorageQuotaResult};
+use shared::types::retention::{
+    GetRetentionPolicyResult, ListLegalHoldsResult, ListPurgeRecordsInput, ListPurgeRecordsResult,
+    SetLegalHoldInput, SetLegalHoldResult, SetRetentionPolicyInput, SetRetentionPolicyResult,
+};
+use shared::types::reviews::{
+    GetDocumentReviewInput, GetDocumentReviewResult, ListDueReviewsInput, ListDueReviewsResult,
+    SetReviewScheduleInput, SetReviewScheduleResult,
+};
 use shared::types::revisions::{
     CreateRevisionInput, CreateRevisionResult, DiffRevisionsInput, DiffRevisionsResult,
-    DownloadRevisionContentInput, DownloadRevisionContentResult, FinishRevisionContentUploadInput,
-    FinishRevisionContentUploadResult, GetRevisionContentChunkInput, GetRevisionContentChunkResult,
+    DownloadRevisionContentInput, DownloadRevisionContentResult, GetContentOutlineInput,
+    GetContentOutlineResult, GetContentPreviewInput, GetContentPreviewResult,
+    GetContentTypePolicyResult, GetRevisionContentChunkInput, GetRevisionContentChunkResult,
     GetRevisionContentInput, GetRevisionContentResult, ListRevisionContentsInput,
-    ListRevisionContentsResult, ListRevisionsInput, ListRevisionsResult,
-    StoreRevisionContentChunkInput, StoreRevisionContentChunkResult,
+    ListRevisionContentsResult, ListRevisionsInput, ListRevisionsResult, RollbackDocumentInput,
+    RollbackDocumentResult, SetContentTypePolicyInput, SetContentTypePolicyResult,
+    VerifyContentInput, VerifyContentResult,
+};
+use shared::types::search::{SearchDocumentsInput, SearchDocumentsResult};
+use shared::types::share_links::{
+    CreateShareLinkInput, CreateShareLinkResult, GetSharedRevisionInput, GetSharedRevisionResult,
+    ListShareLinksInput, ListShareLinksResult, RevokeShareLinkInput, RevokeShareLinkResult,
+};
+use shared::types::training::{
+    AcknowledgeRevisionInput, AcknowledgeRevisionResult, GetTrainingPolicyInput,
+    GetTrainingPolicyResult, GetTrainingReportInput, GetTrainingReportResult, ListMyTrainingInput,
+    ListMyTrainingResult, SetTrainingPolicyInput, SetTrainingPolicyResult,
+};
+use shared::types::uploads::{
+    BeginUploadInput, BeginUploadResult, FinishUploadInput, FinishUploadResult,
+    GetStorageReportResult, SetUploadSessionTtlInput, SetUploadSessionTtlResult, UploadChunkInput,
+    UploadChunkResult,
 };
 use shared::types::users::{
     CreateUserInput, CreateUserResult, GetUserResult, ListUsersInput, ListUsersResult,
@@ -41,13 +106,30 @@ mod init;
 
 // entities
 mod access_control;
+mod approvals;
+mod comments;
+mod document_links;
 mod documents;
+mod encryption;
+mod exports;
+mod folders;
+mod http;
+mod imports;
 mod invites;
 mod logs;
 mod management;
+mod notifications;
 mod organization;
+mod previews;
 mod projects;
+mod quotas;
+mod retention;
+mod reviews;
 mod revisions;
+mod search;
+mod share_links;
+mod training;
+mod uploads;
 mod users;
 mod workflows;
 
@@ -0,0 +1,7 @@
+use crate::notifications::NotificationsManager;
+use shared::types::notifications::GetUnreadNotificationCountResult;
+
+#[ic_cdk_macros::query]
+pub fn get_unread_notification_count() -> GetUnreadNotificationCountResult {
+    NotificationsManager::get_unread_notification_count()
+}
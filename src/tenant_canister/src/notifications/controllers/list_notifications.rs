@@ -0,0 +1,7 @@
+use crate::notifications::NotificationsManager;
+use shared::types::notifications::{ListNotificationsInput, ListNotificationsResult};
+
+#[ic_cdk_macros::query]
+pub fn list_notifications(input: ListNotificationsInput) -> ListNotificationsResult {
+    NotificationsManager::list_notifications(input)
+}
@@ -0,0 +1,7 @@
+use crate::notifications::NotificationsManager;
+use shared::types::notifications::ListSubscriptionsResult;
+
+#[ic_cdk_macros::query]
+pub fn list_subscriptions() -> ListSubscriptionsResult {
+    NotificationsManager::list_subscriptions()
+}
@@ -0,0 +1,7 @@
+use crate::notifications::NotificationsManager;
+use shared::types::notifications::{MarkNotificationsReadInput, MarkNotificationsReadResult};
+
+#[ic_cdk_macros::update]
+pub fn mark_notifications_read(input: MarkNotificationsReadInput) -> MarkNotificationsReadResult {
+    NotificationsManager::mark_notifications_read(input)
+}
@@ -0,0 +1,6 @@
+pub mod get_unread_notification_count;
+pub mod list_notifications;
+pub mod list_subscriptions;
+pub mod mark_notifications_read;
+pub mod subscribe;
+pub mod unsubscribe;
@@ -0,0 +1,7 @@
+use crate::notifications::NotificationsManager;
+use shared::types::notifications::{SubscribeInput, SubscribeResult};
+
+#[ic_cdk_macros::update]
+pub fn subscribe(input: SubscribeInput) -> SubscribeResult {
+    NotificationsManager::subscribe(input)
+}
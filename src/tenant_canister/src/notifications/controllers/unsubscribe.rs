@@ -0,0 +1,7 @@
+use crate::notifications::NotificationsManager;
+use shared::types::notifications::{UnsubscribeInput, UnsubscribeResult};
+
+#[ic_cdk_macros::update]
+pub fn unsubscribe(input: UnsubscribeInput) -> UnsubscribeResult {
+    NotificationsManager::unsubscribe(input)
+}
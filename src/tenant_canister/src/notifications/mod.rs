@@ -0,0 +1,4 @@
+pub mod controllers;
+pub mod notifications_manager;
+
+pub use notifications_manager::NotificationsManager;
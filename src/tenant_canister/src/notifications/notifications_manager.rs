@@ -0,0 +1,497 @@
+use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap};
+use shared::consts::memory_ids::tenant_canister::{
+    NOTIFICATIONS_MEMORY_ID, SUBSCRIBERS_MEMORY_ID, SUBSCRIPTIONS_MEMORY_ID,
+};
+use shared::consts::notifications::{
+    DEFAULT_NOTIFICATION_LIMIT, MAX_NOTIFICATIONS_PER_USER, MAX_NOTIFICATION_LIMIT,
+    MAX_SUBSCRIPTIONS_PER_USER,
+};
+use shared::types::access_control::{DocumentPermission, Permission};
+use shared::types::comments::Comment;
+use shared::types::documents::Document;
+use shared::types::errors::AppError;
+use shared::types::notifications::{
+    GetUnreadNotificationCountResult, ListNotificationsInput, ListNotificationsResult,
+    ListSubscriptionsResult, MarkNotificationsReadInput, MarkNotificationsReadResult, Notification,
+    NotificationEvent, NotificationId, SubscribeInput, SubscribeResult, Subscription,
+    SubscriptionTarget, UnsubscribeInput, UnsubscribeResult,
+};
+use shared::types::projects::ProjectId;
+use shared::types::users::{User, UserId};
+use shared::types::workflows::Workflow;
+use shared::{log_debug, log_info, log_warn};
+use std::cell::RefCell;
+
+use crate::documents::DocumentManager;
+use crate::projects::projects_manager::ProjectsManager;
+use crate::users::user_manager::UserManager;
+use crate::workflows::workflows_manager::WorkflowsManager;
+
+type Memory = VirtualMemory<DefaultMemoryImpl>;
+
+/// User, target kind and target id
+type SubscriptionKey = (UserId, u8, u64);
+
+/// Target kind, target id and user
+type SubscriberKey = (u8, u64, UserId);
+
+thread_local! {
+    static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
+        RefCell::new(MemoryManager::init(DefaultMemoryImpl::default()));
+
+    static SUBSCRIPTIONS: RefCell<StableBTreeMap<SubscriptionKey, Subscription, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(SUBSCRIPTIONS_MEMORY_ID))),
+        )
+    );
+
+    static SUBSCRIBERS: RefCell<StableBTreeMap<SubscriberKey, (), Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(SUBSCRIBERS_MEMORY_ID))),
+        )
+    );
+
+    static NOTIFICATIONS: RefCell<StableBTreeMap<(UserId, NotificationId), Notification, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(NOTIFICATIONS_MEMORY_ID))),
+        )
+    );
+}
+
+/// Subscriptions to documents, projects and workflows, and the per-user inbox of
+/// notifications raised on their activity. Users are never notified of their own
+/// actions, nor of documents they can no longer read.
+pub struct NotificationsManager;
+
+impl NotificationsManager {
+    fn target_key(target: &SubscriptionTarget) -> (u8, u64) {
+        match target {
+            SubscriptionTarget::Document(document_id) => (0, *document_id),
+            SubscriptionTarget::Project(project_id) => (1, u64::from(*project_id)),
+            SubscriptionTarget::Workflow(workflow_id) => (2, u64::from(*workflow_id)),
+        }
+    }
+
+    fn subscription_key(user_id: UserId, target: &SubscriptionTarget) -> SubscriptionKey {
+        let (kind, target_id) = Self::target_key(target);
+        (user_id, kind, target_id)
+    }
+
+    fn user_subscriptions(user_id: UserId) -> Vec<Subscription> {
+        SUBSCRIPTIONS.with(|subscriptions| {
+            subscriptions
+                .borrow()
+                .range((user_id, u8::MIN, u64::MIN)..=(user_id, u8::MAX, u64::MAX))
+                .map(|(_, subscription)| subscription)
+                .collect()
+        })
+    }
+
+    /// Users subscribed to any of `targets`, without duplicates.
+    fn subscribers(targets: &[SubscriptionTarget]) -> Vec<UserId> {
+        let mut user_ids: Vec<UserId> = SUBSCRIBERS.with(|subscribers| {
+            let subscribers = subscribers.borrow();
+            targets
+                .iter()
+                .flat_map(|target| {
+                    let (kind, target_id) = Self::target_key(target);
+                    subscribers
+                        .keys_range((kind, target_id, UserId::MIN)..=(kind, target_id, UserId::MAX))
+                        .map(|(_, _, user_id)| user_id)
+                        .collect::<Vec<_>>()
+                })
+                .collect()
+        });
+        user_ids.sort();
+        user_ids.dedup();
+        user_ids
+    }
+
+    fn user_notifications(user_id: UserId) -> Vec<Notification> {
+        NOTIFICATIONS.with(|notifications| {
+            notifications
+                .borrow()
+                .range((user_id, NotificationId::MIN)..=(user_id, NotificationId::MAX))
+                .map(|(_, notification)| notification)
+                .collect()
+        })
+    }
+
+    /// Appends a notification to a user's inbox, dropping the oldest ones beyond
+    /// `MAX_NOTIFICATIONS_PER_USER`.
+    fn deliver(
+        user_id: UserId,
+        project_id: ProjectId,
+        actor: Option<UserId>,
+        event: NotificationEvent,
+    ) {
+        NOTIFICATIONS.with(|notifications| {
+            let mut notifications = notifications.borrow_mut();
+            let range = (user_id, NotificationId::MIN)..=(user_id, NotificationId::MAX);
+            let id = notifications
+                .range(range.clone())
+                .last()
+                .map(|((_, id), _)| id + 1)
+                .unwrap_or(1);
+            notifications.insert(
+                (user_id, id),
+                Notification {
+                    id,
+                    user_id,
+                    project_id,
+                    event,
+                    actor,
+                    created_at: ic_cdk::api::time(),
+                    read_at: None,
+                },
+            );
+
+            let count = notifications.range(range.clone()).count();
+            if count > MAX_NOTIFICATIONS_PER_USER {
+                let expired: Vec<(UserId, NotificationId)> = notifications
+                    .range(range)
+                    .take(count - MAX_NOTIFICATIONS_PER_USER)
+                    .map(|(key, _)| key)
+                    .collect();
+                for key in expired {
+                    notifications.remove(&key);
+                }
+            }
+        });
+    }
+
+    /// Subscribers of a document and its project, plus `extra_recipients`, that can
+    /// still read the document. The actor is left out.
+    fn document_recipients(
+        document: &Document,
+        actor: UserId,
+        extra_recipients: &[UserId],
+    ) -> Vec<UserId> {
+        let mut user_ids = Self::subscribers(&[
+            SubscriptionTarget::Document(document.id),
+            SubscriptionTarget::Project(document.project_id),
+        ]);
+        user_ids.extend_from_slice(extra_recipients);
+        user_ids.sort();
+        user_ids.dedup();
+        let read = Permission::Document(DocumentPermission::Read);
+        user_ids
+            .into_iter()
+            .filter(|user_id| *user_id != actor)
+            .filter(|user_id| DocumentManager::has_permission(*user_id, document, &read))
+            .collect()
+    }
+
+    /// Notifies subscribers of activity on a document. `extra_recipients` are notified
+    /// whether or not they are subscribed, e.g. the approvers of a review request.
+    pub fn notify_document(
+        document: &Document,
+        actor: UserId,
+        event: NotificationEvent,
+        extra_recipients: &[UserId],
+    ) {
+        let recipients = Self::document_recipients(document, actor, extra_recipients);
+        for user_id in &recipients {
+            Self::deliver(*user_id, document.project_id, Some(actor), event.clone());
+        }
+        log_debug!(
+            "notifications: Document notification delivered [document_id={}, actor={}, event={:?}, recipients={}]",
+            document.id,
+            actor,
+            event,
+            recipients.len()
+        );
+    }
+
+    /// Notifies subscribers of a new comment. Users mentioned in it are notified even
+    /// when not subscribed.
+    pub fn notify_comment(document: &Document, comment: &Comment) {
+        let recipients = Self::document_recipients(document, comment.created_by, &comment.mentions);
+        for user_id in &recipients {
+            Self::deliver(
+                *user_id,
+                document.project_id,
+                Some(comment.created_by),
+                NotificationEvent::CommentAdded {
+                    document_id: comment.document_id,
+                    revision_id: comment.revision_id,
+                    comment_id: comment.id,
+                    mentioned: comment.mentions.contains(user_id),
+                },
+            );
+        }
+        log_debug!(
+            "notifications: Comment notification delivered [comment_id={}, document_id={}, recipients={}]",
+            comment.id,
+            document.id,
+            recipients.len()
+        );
+    }
+
+    /// Notifies subscribers of a workflow and its project that are still project
+    /// members. The actor is left out.
+    pub fn notify_workflow(workflow: &Workflow, actor: Option<UserId>, event: NotificationEvent) {
+        let recipients: Vec<UserId> = Self::subscribers(&[
+            SubscriptionTarget::Workflow(workflow.id),
+            SubscriptionTarget::Project(workflow.project_id),
+        ])
+        .into_iter()
+        .filter(|user_id| Some(*user_id) != actor)
+        .filter(|user_id| ProjectsManager::is_member(workflow.project_id, *user_id))
+        .collect();
+        for user_id in &recipients {
+            Self::deliver(*user_id, workflow.project_id, actor, event.clone());
+        }
+        log_debug!(
+            "notifications: Workflow notification delivered [workflow_id={}, actor={:?}, event={:?}, recipients={}]",
+            workflow.id,
+            actor,
+            event,
+            recipients.len()
+        );
+    }
+
+    /// Checks that the target exists and that the user can follow it.
+    fn validate_target(user: &User, target: &SubscriptionTarget) -> Result<(), AppError> {
+        let allowed = match target {
+            SubscriptionTarget::Document(document_id) => {
+                let document = DocumentManager::get_by_id(*document_id)
+                    .ok_or_else(|| AppError::EntityNotFound("Document not found".to_string()))?;
+                DocumentManager::has_permission(
+                    user.id,
+                    &document,
+                    &Permission::Document(DocumentPermission::Read),
+                )
+            }
+            SubscriptionTarget::Project(project_id) => {
+                ProjectsManager::get_by_id(*project_id)
+                    .ok_or_else(|| AppError::EntityNotFound("Project not found".to_string()))?;
+                ProjectsManager::is_member(*project_id, user.id)
+            }
+            SubscriptionTarget::Workflow(workflow_id) => {
+                let workflow = WorkflowsManager::get_workflow_by_id(workflow_id)
+                    .ok_or_else(|| AppError::EntityNotFound("Workflow not found".to_string()))?;
+                ProjectsManager::is_member(workflow.project_id, user.id)
+            }
+        };
+        if !allowed {
+            log_warn!(
+                "access_control: Permission denied for subscription [user_id={}, target={:?}]",
+                user.id,
+                target
+            );
+            return Err(AppError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    /// Subscribes the caller to a document, project or workflow. Subscribing again is a
+    /// no-op.
+    pub fn subscribe(input: SubscribeInput) -> SubscribeResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Subscription attempt [principal={}, target={:?}]",
+            principal,
+            input.target
+        );
+
+        let user = match UserManager::authenticate(principal, "subscription") {
+            Ok(user) => user,
+            Err(e) => return SubscribeResult::Err(e),
+        };
+        if let Err(e) = Self::validate_target(&user, &input.target) {
+            log_warn!(
+                "notifications: Failed to subscribe [target={:?}, principal={}] - {:?}",
+                input.target,
+                principal,
+                e
+            );
+            return SubscribeResult::Err(e);
+        }
+
+        let key = Self::subscription_key(user.id, &input.target);
+        if SUBSCRIPTIONS.with(|subscriptions| subscriptions.borrow().contains_key(&key)) {
+            return SubscribeResult::Ok;
+        }
+        if Self::user_subscriptions(user.id).len() >= MAX_SUBSCRIPTIONS_PER_USER {
+            log_warn!(
+                "notifications: Subscription limit reached [user_id={}, limit={}, principal={}]",
+                user.id,
+                MAX_SUBSCRIPTIONS_PER_USER,
+                principal
+            );
+            return SubscribeResult::Err(AppError::QuotaExceeded(format!(
+                "Users can hold at most {} subscriptions",
+                MAX_SUBSCRIPTIONS_PER_USER
+            )));
+        }
+        SUBSCRIPTIONS.with(|subscriptions| {
+            subscriptions.borrow_mut().insert(
+                key,
+                Subscription {
+                    user_id: user.id,
+                    target: input.target.clone(),
+                    created_at: ic_cdk::api::time(),
+                },
+            );
+        });
+        SUBSCRIBERS.with(|subscribers| {
+            let (kind, target_id) = Self::target_key(&input.target);
+            subscribers
+                .borrow_mut()
+                .insert((kind, target_id, user.id), ());
+        });
+        log_info!(
+            "notifications: Subscribed [user_id={}, target={:?}, principal={}]",
+            user.id,
+            input.target,
+            principal
+        );
+        SubscribeResult::Ok
+    }
+
+    pub fn unsubscribe(input: UnsubscribeInput) -> UnsubscribeResult {
+        let principal = ic_cdk::api::msg_caller();
+        let user = match UserManager::authenticate(principal, "unsubscription") {
+            Ok(user) => user,
+            Err(e) => return UnsubscribeResult::Err(e),
+        };
+        let key = Self::subscription_key(user.id, &input.target);
+        if SUBSCRIPTIONS
+            .with(|subscriptions| subscriptions.borrow_mut().remove(&key))
+            .is_none()
+        {
+            log_warn!(
+                "notifications: Subscription not found [user_id={}, target={:?}, principal={}]",
+                user.id,
+                input.target,
+                principal
+            );
+            return UnsubscribeResult::Err(AppError::EntityNotFound(
+                "Subscription not found".to_string(),
+            ));
+        }
+        SUBSCRIBERS.with(|subscribers| {
+            let (kind, target_id) = Self::target_key(&input.target);
+            subscribers.borrow_mut().remove(&(kind, target_id, user.id));
+        });
+        log_info!(
+            "notifications: Unsubscribed [user_id={}, target={:?}, principal={}]",
+            user.id,
+            input.target,
+            principal
+        );
+        UnsubscribeResult::Ok
+    }
+
+    pub fn list_subscriptions() -> ListSubscriptionsResult {
+        let principal = ic_cdk::api::msg_caller();
+        match UserManager::authenticate(principal, "subscription listing") {
+            Ok(user) => ListSubscriptionsResult::Ok(Self::user_subscriptions(user.id)),
+            Err(e) => ListSubscriptionsResult::Err(e),
+        }
+    }
+
+    /// The caller's notifications, newest first.
+    pub fn list_notifications(input: ListNotificationsInput) -> ListNotificationsResult {
+        let principal = ic_cdk::api::msg_caller();
+        let user = match UserManager::authenticate(principal, "notification listing") {
+            Ok(user) => user,
+            Err(e) => return ListNotificationsResult::Err(e),
+        };
+        let limit = input.limit.unwrap_or(DEFAULT_NOTIFICATION_LIMIT);
+        if limit == 0 || limit > MAX_NOTIFICATION_LIMIT {
+            log_warn!(
+                "notifications: Invalid notification limit [limit={}, principal={}]",
+                limit,
+                principal
+            );
+            return ListNotificationsResult::Err(AppError::ValidationError(format!(
+                "Limit must be between 1 and {}",
+                MAX_NOTIFICATION_LIMIT
+            )));
+        }
+        let before = input.before.unwrap_or(NotificationId::MAX);
+        ListNotificationsResult::Ok(NOTIFICATIONS.with(|notifications| {
+            notifications
+                .borrow()
+                .range((user.id, NotificationId::MIN)..(user.id, before))
+                .rev()
+                .map(|(_, notification)| notification)
+                .filter(|notification| !input.unread_only || notification.read_at.is_none())
+                .take(limit as usize)
+                .collect()
+        }))
+    }
+
+    /// Marks the given notifications of the caller as read, or all of them when no ids
+    /// are given. Returns how many were unread.
+    pub fn mark_notifications_read(
+        input: MarkNotificationsReadInput,
+    ) -> MarkNotificationsReadResult {
+        let principal = ic_cdk::api::msg_caller();
+        let user = match UserManager::authenticate(principal, "notification update") {
+            Ok(user) => user,
+            Err(e) => return MarkNotificationsReadResult::Err(e),
+        };
+        let notifications: Vec<Notification> = match &input.ids {
+            None => Self::user_notifications(user.id),
+            Some(ids) => {
+                let mut notifications = Vec::with_capacity(ids.len());
+                for id in ids {
+                    match NOTIFICATIONS
+                        .with(|notifications| notifications.borrow().get(&(user.id, *id)))
+                    {
+                        Some(notification) => notifications.push(notification),
+                        None => {
+                            log_warn!(
+                                "notifications: Notification not found [notification_id={}, user_id={}, principal={}]",
+                                id,
+                                user.id,
+                                principal
+                            );
+                            return MarkNotificationsReadResult::Err(AppError::EntityNotFound(
+                                format!("Notification {} not found", id),
+                            ));
+                        }
+                    }
+                }
+                notifications
+            }
+        };
+
+        let now = ic_cdk::api::time();
+        let mut marked = 0;
+        NOTIFICATIONS.with(|stored| {
+            let mut stored = stored.borrow_mut();
+            for mut notification in notifications {
+                if notification.read_at.is_some() {
+                    continue;
+                }
+                notification.read_at = Some(now);
+                stored.insert((user.id, notification.id), notification);
+                marked += 1;
+            }
+        });
+        log_debug!(
+            "notifications: Notifications marked as read [user_id={}, count={}]",
+            user.id,
+            marked
+        );
+        MarkNotificationsReadResult::Ok(marked)
+    }
+
+    pub fn get_unread_notification_count() -> GetUnreadNotificationCountResult {
+        let principal = ic_cdk::api::msg_caller();
+        match UserManager::authenticate(principal, "unread notification count") {
+            Ok(user) => GetUnreadNotificationCountResult::Ok(
+                Self::user_notifications(user.id)
+                    .iter()
+                    .filter(|notification| notification.read_at.is_none())
+                    .count() as u32,
+            ),
+            Err(e) => GetUnreadNotificationCountResult::Err(e),
+        }
+    }
+}
@@ -0,0 +1,7 @@
+use crate::previews::PreviewsManager;
+use shared::types::revisions::{GetContentOutlineInput, GetContentOutlineResult};
+
+#[ic_cdk_macros::query]
+pub fn get_content_outline(input: GetContentOutlineInput) -> GetContentOutlineResult {
+    PreviewsManager::get_content_outline(input)
+}
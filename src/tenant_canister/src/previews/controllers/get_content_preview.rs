@@ -0,0 +1,7 @@
+use crate::previews::PreviewsManager;
+use shared::types::revisions::{GetContentPreviewInput, GetContentPreviewResult};
+
+#[ic_cdk_macros::query]
+pub fn get_content_preview(input: GetContentPreviewInput) -> GetContentPreviewResult {
+    PreviewsManager::get_content_preview(input)
+}
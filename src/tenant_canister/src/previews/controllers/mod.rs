@@ -0,0 +1,2 @@
+pub mod get_content_outline;
+pub mod get_content_preview;
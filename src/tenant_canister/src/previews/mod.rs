@@ -0,0 +1,4 @@
+pub mod controllers;
+pub mod previews_manager;
+
+pub use previews_manager::PreviewsManager;
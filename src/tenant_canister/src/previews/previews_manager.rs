@@ -0,0 +1,155 @@
+use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap};
+use shared::consts::http::MAX_HTTP_BODY_SIZE;
+use shared::consts::memory_ids::tenant_canister::CONTENT_PREVIEWS_MEMORY_ID;
+use shared::consts::revisions::MAX_MARKDOWN_PREVIEW_SIZE;
+use shared::types::errors::AppError;
+use shared::types::revisions::{
+    ContentOutline, ContentPreview, GetContentOutlineInput, GetContentOutlineResult,
+    GetContentPreviewInput, GetContentPreviewResult, Revision, RevisionContent, RevisionContentId,
+    RevisionContentType,
+};
+use shared::utils::markdown::render_markdown;
+use shared::{log_debug, log_warn};
+use std::cell::RefCell;
+
+use crate::revisions::revisions_manager::RevisionsManager;
+
+type Memory = VirtualMemory<DefaultMemoryImpl>;
+
+thread_local! {
+    static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
+        RefCell::new(MemoryManager::init(DefaultMemoryImpl::default()));
+
+    // content -> rendered preview, content is immutable so entries never go stale
+    static CONTENT_PREVIEWS: RefCell<StableBTreeMap<RevisionContentId, ContentPreview, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(CONTENT_PREVIEWS_MEMORY_ID))),
+        )
+    );
+}
+
+/// HTML previews and outlines of Markdown content. Previews are rendered when a
+/// revision is created and cached per content ID. Queries cannot write to the cache,
+/// so content stored before previews existed is rendered on every request.
+pub struct PreviewsManager;
+
+impl PreviewsManager {
+    fn render(content: &RevisionContent) -> Result<ContentPreview, AppError> {
+        if content.content_type != RevisionContentType::Markdown {
+            return Err(AppError::ValidationError(
+                "Only Markdown content can be previewed".to_string(),
+            ));
+        }
+        if content.encryption.is_some() {
+            return Err(AppError::ValidationError(
+                "Encrypted content can only be rendered by clients".to_string(),
+            ));
+        }
+        let size = RevisionsManager::get_content_size(content);
+        if size > MAX_MARKDOWN_PREVIEW_SIZE {
+            return Err(AppError::ValidationError(format!(
+                "Content of {} bytes exceeds the preview limit of {} bytes",
+                size, MAX_MARKDOWN_PREVIEW_SIZE
+            )));
+        }
+        let bytes = RevisionsManager::get_content_bytes(content).ok_or_else(|| {
+            AppError::InternalError(format!("Data of content {} is incomplete", content.id))
+        })?;
+
+        let rendered = render_markdown(&String::from_utf8_lossy(&bytes));
+        if rendered.html.len() as u64 > MAX_HTTP_BODY_SIZE {
+            return Err(AppError::ValidationError(format!(
+                "Rendered preview exceeds {} bytes",
+                MAX_HTTP_BODY_SIZE
+            )));
+        }
+        Ok(ContentPreview {
+            content_id: content.id,
+            html: rendered.html,
+            outline: ContentOutline {
+                content_id: content.id,
+                title: rendered.title,
+                headings: rendered.headings,
+            },
+        })
+    }
+
+    /// Cached preview of a content item, rendered if it is not cached. Callers check
+    /// access first.
+    pub fn get_preview(content_id: RevisionContentId) -> Result<ContentPreview, AppError> {
+        if let Some(preview) = CONTENT_PREVIEWS.with(|previews| previews.borrow().get(&content_id))
+        {
+            return Ok(preview);
+        }
+        let content = RevisionsManager::get_revision_content_by_id(content_id)
+            .ok_or_else(|| AppError::EntityNotFound("Content not found".to_string()))?;
+        Self::render(&content)
+    }
+
+    /// Renders and caches the previews of the Markdown content of a new revision.
+    pub fn cache_revision_previews(revision: &Revision) {
+        for content_id in &revision.contents {
+            if CONTENT_PREVIEWS.with(|previews| previews.borrow().contains_key(content_id)) {
+                continue;
+            }
+            let Some(content) = RevisionsManager::get_revision_content_by_id(*content_id) else {
+                continue;
+            };
+            if content.content_type != RevisionContentType::Markdown || content.encryption.is_some()
+            {
+                continue;
+            }
+            match Self::render(&content) {
+                Ok(preview) => CONTENT_PREVIEWS.with(|previews| {
+                    previews.borrow_mut().insert(*content_id, preview);
+                }),
+                Err(e) => log_debug!(
+                    "content_preview: Preview not cached [content_id={}, revision_id={}] - {:?}",
+                    content_id,
+                    revision.id,
+                    e
+                ),
+            }
+        }
+    }
+
+    /// Drops the cached preview of content whose data was purged.
+    pub fn remove_preview(content_id: RevisionContentId) {
+        CONTENT_PREVIEWS.with(|previews| previews.borrow_mut().remove(&content_id));
+    }
+
+    /// Preview of content the caller may download, directly or through a share link.
+    pub fn authorized_preview(
+        content_id: RevisionContentId,
+        share_token: Option<&str>,
+    ) -> Result<ContentPreview, AppError> {
+        let principal = ic_cdk::api::msg_caller();
+        let result =
+            RevisionsManager::authorize_content_download(content_id, share_token, principal)
+                .and_then(|()| Self::get_preview(content_id));
+        if let Err(e) = &result {
+            log_warn!(
+                "content_preview: Preview not available [content_id={}, principal={}] - {:?}",
+                content_id,
+                principal,
+                e
+            );
+        }
+        result
+    }
+
+    pub fn get_content_preview(input: GetContentPreviewInput) -> GetContentPreviewResult {
+        match Self::authorized_preview(input.content_id, input.share_token.as_deref()) {
+            Ok(preview) => GetContentPreviewResult::Ok(preview),
+            Err(e) => GetContentPreviewResult::Err(e),
+        }
+    }
+
+    pub fn get_content_outline(input: GetContentOutlineInput) -> GetContentOutlineResult {
+        match Self::authorized_preview(input.content_id, input.share_token.as_deref()) {
+            Ok(preview) => GetContentOutlineResult::Ok(preview.outline),
+            Err(e) => GetContentOutlineResult::Err(e),
+        }
+    }
+}
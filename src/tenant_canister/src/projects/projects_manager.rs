@@ -10,7 +10,7 @@ use shared::types::projects::{
     CreateInitProjectInput, CreateProjectInput, CreateProjectResult, ListProjectMembersInput,
     ListProjectMembersResult, ListProjectsResult, Project, ProjectId,
 };
-use shared::types::users::GetUserResult;
+use shared::types::users::{GetUserResult, UserId};
 use shared::utils::pagination::paginate;
 
 use crate::users::user_manager::UserManager;
@@ -49,6 +49,16 @@ impl ProjectsManager {
         })
     }
 
+    pub fn get_by_id(id: ProjectId) -> Option<Project> {
+        PROJECTS.with(|projects| projects.borrow().get(&id))
+    }
+
+    pub fn is_member(id: ProjectId, user_id: UserId) -> bool {
+        Self::get_by_id(id)
+            .map(|project| project.members.contains(&user_id))
+            .unwrap_or(false)
+    }
+
     fn insert(id: ProjectId, project: Project) {
         PROJECTS.with(|projects| {
             projects.borrow_mut().insert(id, project);
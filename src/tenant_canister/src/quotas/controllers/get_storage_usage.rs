@@ -0,0 +1,7 @@
+use crate::quotas::quotas_manager::QuotasManager;
+use shared::types::quotas::GetStorageUsageResult;
+
+#[ic_cdk_macros::query]
+pub fn get_storage_usage() -> GetStorageUsageResult {
+    QuotasManager::get_storage_usage()
+}
@@ -0,0 +1,2 @@
+pub mod get_storage_usage;
+pub mod set_storage_quota;
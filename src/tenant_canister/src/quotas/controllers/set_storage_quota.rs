@@ -0,0 +1,7 @@
+use crate::quotas::quotas_manager::QuotasManager;
+use shared::types::quotas::{SetStorageQuotaInput, SetStorageQuotaResult};
+
+#[ic_cdk_macros::update]
+pub fn set_storage_quota(input: SetStorageQuotaInput) -> SetStorageQuotaResult {
+    QuotasManager::set_storage_quota(input)
+}
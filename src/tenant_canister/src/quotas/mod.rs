@@ -0,0 +1,2 @@
+pub mod controllers;
+pub mod quotas_manager;
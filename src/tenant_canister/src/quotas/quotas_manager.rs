@@ -0,0 +1,218 @@
+use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap, StableCell};
+use shared::consts::memory_ids::tenant_canister::{
+    PROJECT_STORAGE_USAGE_MEMORY_ID, STORAGE_QUOTA_MEMORY_ID,
+};
+use shared::consts::quotas::DEFAULT_STORAGE_QUOTA;
+use shared::types::access_control::{OrganizationPermission, Permission};
+use shared::types::errors::AppError;
+use shared::types::projects::ProjectId;
+use shared::types::quotas::{
+    GetStorageUsageResult, ProjectStorageUsage, SetStorageQuotaInput, SetStorageQuotaResult,
+    StorageUsage,
+};
+use shared::types::users::User;
+use shared::{log_debug, log_info, log_warn};
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use crate::access_control::AccessControlManager;
+use crate::revisions::revisions_manager::RevisionsManager;
+use crate::uploads::uploads_manager::UploadsManager;
+use crate::users::user_manager::UserManager;
+
+type Memory = VirtualMemory<DefaultMemoryImpl>;
+
+thread_local! {
+    static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
+        RefCell::new(MemoryManager::init(DefaultMemoryImpl::default()));
+
+    static STORAGE_QUOTA: RefCell<StableCell<u64, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(STORAGE_QUOTA_MEMORY_ID))),
+            DEFAULT_STORAGE_QUOTA,
+        ).expect("Failed to initialize storage quota stable cell")
+    );
+
+    // project -> bytes of content stored in its revisions
+    static PROJECT_STORAGE_USAGE: RefCell<StableBTreeMap<ProjectId, u64, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(PROJECT_STORAGE_USAGE_MEMORY_ID))),
+        )
+    );
+}
+
+pub struct QuotasManager;
+
+impl QuotasManager {
+    pub fn get_quota() -> u64 {
+        STORAGE_QUOTA.with(|cell| *cell.borrow().get())
+    }
+
+    fn get_used_bytes() -> u64 {
+        PROJECT_STORAGE_USAGE.with(|usage| usage.borrow().values().sum())
+    }
+
+    /// Accounts newly stored content to a project.
+    pub fn record_usage(project_id: ProjectId, bytes: u64) {
+        if bytes == 0 {
+            return;
+        }
+        PROJECT_STORAGE_USAGE.with(|usage| {
+            let mut usage = usage.borrow_mut();
+            let current = usage.get(&project_id).unwrap_or_default();
+            usage.insert(project_id, current.saturating_add(bytes));
+        });
+    }
+
+    /// Gives back the usage of content whose data was removed.
+    pub fn release_usage(project_id: ProjectId, bytes: u64) {
+        if bytes == 0 {
+            return;
+        }
+        PROJECT_STORAGE_USAGE.with(|usage| {
+            let mut usage = usage.borrow_mut();
+            let current = usage.get(&project_id).unwrap_or_default();
+            usage.insert(project_id, current.saturating_sub(bytes));
+        });
+    }
+
+    /// Fails with `QuotaExceeded` if storing `bytes` more, on top of stored content and
+    /// pending uploads, would exceed the tenant quota.
+    pub fn check_quota(bytes: u64) -> Result<(), AppError> {
+        let quota = Self::get_quota();
+        let used = Self::get_used_bytes();
+        let pending = UploadsManager::get_pending_upload_bytes();
+        if used.saturating_add(pending).saturating_add(bytes) > quota {
+            log_warn!(
+                "storage_quota: Quota exceeded [quota={}, used={}, pending={}, requested={}]",
+                quota,
+                used,
+                pending,
+                bytes
+            );
+            return Err(AppError::QuotaExceeded(format!(
+                "Storing {} bytes would exceed the storage quota of {} bytes ({} bytes used, {} bytes in pending uploads)",
+                bytes, quota, used, pending
+            )));
+        }
+        Ok(())
+    }
+
+    /// Accounts content stored before usage accounting was introduced, attributing each
+    /// content item to the project of the first revision that contains it.
+    pub fn init_usage() {
+        if PROJECT_STORAGE_USAGE.with(|usage| !usage.borrow().is_empty()) {
+            return;
+        }
+        let mut seen = HashSet::new();
+        for revision in RevisionsManager::get_all() {
+            let bytes: u64 = revision
+                .contents
+                .iter()
+                .filter(|content_id| seen.insert(**content_id))
+                .filter_map(|content_id| RevisionsManager::get_revision_content_by_id(*content_id))
+                .map(|content| RevisionsManager::get_content_size(&content))
+                .sum();
+            Self::record_usage(revision.project_id, bytes);
+        }
+        if !seen.is_empty() {
+            log_info!(
+                "storage_quota: Initialized storage usage [contents={}, used_bytes={}]",
+                seen.len(),
+                Self::get_used_bytes()
+            );
+        }
+    }
+
+    fn authorize(
+        user: &User,
+        permission: OrganizationPermission,
+        operation: &str,
+    ) -> Result<(), AppError> {
+        if !AccessControlManager::has_organization_permission(
+            user.id,
+            &Permission::Organization(permission),
+        ) {
+            log_warn!(
+                "access_control: Permission denied for {} [user_id={}]",
+                operation,
+                user.id
+            );
+            return Err(AppError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    pub fn set_storage_quota(input: SetStorageQuotaInput) -> SetStorageQuotaResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Storage quota update attempt [principal={}, quota={}]",
+            principal,
+            input.quota
+        );
+
+        let user = match UserManager::authenticate(principal, "storage quota update") {
+            Ok(user) => user,
+            Err(e) => return SetStorageQuotaResult::Err(e),
+        };
+        if let Err(e) = Self::authorize(
+            &user,
+            OrganizationPermission::ManageBilling,
+            "storage quota update",
+        ) {
+            return SetStorageQuotaResult::Err(e);
+        }
+        if input.quota == 0 {
+            log_warn!(
+                "storage_quota: Storage quota must be greater than zero [principal={}]",
+                principal
+            );
+            return SetStorageQuotaResult::Err(AppError::ValidationError(
+                "Storage quota must be greater than zero".to_string(),
+            ));
+        }
+        STORAGE_QUOTA.with(|cell| {
+            cell.borrow_mut()
+                .set(input.quota)
+                .expect("Failed to set storage quota in stable memory");
+        });
+        log_info!(
+            "storage_quota: Storage quota updated [quota={}, used={}, user_id={}, principal={}]",
+            input.quota,
+            Self::get_used_bytes(),
+            user.id,
+            principal
+        );
+        SetStorageQuotaResult::Ok
+    }
+
+    pub fn get_storage_usage() -> GetStorageUsageResult {
+        let principal = ic_cdk::api::msg_caller();
+        let user = match UserManager::authenticate(principal, "storage usage") {
+            Ok(user) => user,
+            Err(e) => return GetStorageUsageResult::Err(e),
+        };
+        if let Err(e) = Self::authorize(&user, OrganizationPermission::Read, "storage usage") {
+            return GetStorageUsageResult::Err(e);
+        }
+
+        let projects: Vec<ProjectStorageUsage> = PROJECT_STORAGE_USAGE.with(|usage| {
+            usage
+                .borrow()
+                .iter()
+                .map(|(project_id, bytes)| ProjectStorageUsage { project_id, bytes })
+                .collect()
+        });
+        let quota = Self::get_quota();
+        let used_bytes = projects.iter().map(|project| project.bytes).sum::<u64>();
+        let pending_upload_bytes = UploadsManager::get_pending_upload_bytes();
+        GetStorageUsageResult::Ok(StorageUsage {
+            quota,
+            used_bytes,
+            pending_upload_bytes,
+            available_bytes: quota.saturating_sub(used_bytes + pending_upload_bytes),
+            projects,
+        })
+    }
+}
@@ -0,0 +1,7 @@
+use crate::retention::RetentionManager;
+use shared::types::retention::GetRetentionPolicyResult;
+
+#[ic_cdk_macros::query]
+pub fn get_retention_policy() -> GetRetentionPolicyResult {
+    RetentionManager::get_retention_policy()
+}
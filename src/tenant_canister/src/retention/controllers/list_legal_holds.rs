@@ -0,0 +1,7 @@
+use crate::retention::RetentionManager;
+use shared::types::retention::ListLegalHoldsResult;
+
+#[ic_cdk_macros::query]
+pub fn list_legal_holds() -> ListLegalHoldsResult {
+    RetentionManager::list_legal_holds()
+}
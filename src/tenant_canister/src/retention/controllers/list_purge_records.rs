@@ -0,0 +1,7 @@
+use crate::retention::RetentionManager;
+use shared::types::retention::{ListPurgeRecordsInput, ListPurgeRecordsResult};
+
+#[ic_cdk_macros::query]
+pub fn list_purge_records(input: ListPurgeRecordsInput) -> ListPurgeRecordsResult {
+    RetentionManager::list_purge_records(input)
+}
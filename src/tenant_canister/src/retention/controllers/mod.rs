@@ -0,0 +1,5 @@
+pub mod get_retention_policy;
+pub mod list_legal_holds;
+pub mod list_purge_records;
+pub mod set_legal_hold;
+pub mod set_retention_policy;
@@ -0,0 +1,7 @@
+use crate::retention::RetentionManager;
+use shared::types::retention::{SetLegalHoldInput, SetLegalHoldResult};
+
+#[ic_cdk_macros::update]
+pub fn set_legal_hold(input: SetLegalHoldInput) -> SetLegalHoldResult {
+    RetentionManager::set_legal_hold(input)
+}
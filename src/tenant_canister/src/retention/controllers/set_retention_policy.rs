@@ -0,0 +1,7 @@
+use crate::retention::RetentionManager;
+use shared::types::retention::{SetRetentionPolicyInput, SetRetentionPolicyResult};
+
+#[ic_cdk_macros::update]
+pub fn set_retention_policy(input: SetRetentionPolicyInput) -> SetRetentionPolicyResult {
+    RetentionManager::set_retention_policy(input)
+}
@@ -0,0 +1,4 @@
+pub mod controllers;
+pub mod retention_manager;
+
+pub use retention_manager::RetentionManager;
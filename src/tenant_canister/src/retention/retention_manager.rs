@@ -0,0 +1,434 @@
+use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap, StableCell};
+use shared::consts::memory_ids::tenant_canister::{
+    DOCUMENT_LEGAL_HOLDS_MEMORY_ID, PROJECT_LEGAL_HOLDS_MEMORY_ID, PURGE_RECORDS_MEMORY_ID,
+    RETENTION_POLICY_MEMORY_ID,
+};
+use shared::consts::retention::{
+    MAX_LEGAL_HOLD_REASON_LENGTH, MAX_RETENTION_RULES, MAX_RETENTION_YEARS,
+};
+use shared::types::access_control::{OrganizationPermission, Permission};
+use shared::types::documents::{Document, DocumentId};
+use shared::types::errors::AppError;
+use shared::types::projects::ProjectId;
+use shared::types::retention::{
+    GetRetentionPolicyResult, LegalHold, LegalHoldTarget, ListLegalHoldsResult,
+    ListPurgeRecordsInput, ListPurgeRecordsResult, PurgeRecord, PurgeRecordId, RetentionPolicy,
+    RetentionRule, SetLegalHoldInput, SetLegalHoldResult, SetRetentionPolicyInput,
+    SetRetentionPolicyResult,
+};
+use shared::types::users::User;
+use shared::{log_debug, log_info, log_warn};
+use std::cell::RefCell;
+
+use crate::access_control::AccessControlManager;
+use crate::documents::DocumentManager;
+use crate::projects::projects_manager::ProjectsManager;
+use crate::users::user_manager::UserManager;
+
+type Memory = VirtualMemory<DefaultMemoryImpl>;
+
+thread_local! {
+    static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
+        RefCell::new(MemoryManager::init(DefaultMemoryImpl::default()));
+
+    static RETENTION_POLICY: RefCell<StableCell<RetentionPolicy, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(RETENTION_POLICY_MEMORY_ID))),
+            RetentionPolicy {
+                rules: Vec::new(),
+                default_retention_years: None,
+                updated_at: None,
+                updated_by: None,
+            },
+        ).expect("Failed to initialize retention policy stable cell")
+    );
+
+    static DOCUMENT_LEGAL_HOLDS: RefCell<StableBTreeMap<DocumentId, LegalHold, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(DOCUMENT_LEGAL_HOLDS_MEMORY_ID))),
+        )
+    );
+
+    static PROJECT_LEGAL_HOLDS: RefCell<StableBTreeMap<ProjectId, LegalHold, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(PROJECT_LEGAL_HOLDS_MEMORY_ID))),
+        )
+    );
+
+    static PURGE_RECORDS: RefCell<StableBTreeMap<PurgeRecordId, PurgeRecord, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(PURGE_RECORDS_MEMORY_ID))),
+        )
+    );
+}
+
+/// Retention rules per document type and legal holds. Revisions are kept for the
+/// retention period of their document's type after they were superseded or made
+/// obsolete; the retention job in `RevisionsManager` then purges their content and
+/// records the purge here. A legal hold on a document or its project blocks deletion
+/// and purging while it is in place.
+pub struct RetentionManager;
+
+impl RetentionManager {
+    fn authorize(
+        user: &User,
+        permission: OrganizationPermission,
+        operation: &str,
+    ) -> Result<(), AppError> {
+        if !AccessControlManager::has_organization_permission(
+            user.id,
+            &Permission::Organization(permission),
+        ) {
+            log_warn!(
+                "access_control: Permission denied for {} [user_id={}]",
+                operation,
+                user.id
+            );
+            return Err(AppError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    pub fn get_policy() -> RetentionPolicy {
+        RETENTION_POLICY.with(|cell| cell.borrow().get().clone())
+    }
+
+    /// Years the revisions of a document are retained after they were retired, None
+    /// when they are kept indefinitely.
+    pub fn retention_years(policy: &RetentionPolicy, document: &Document) -> Option<u32> {
+        document
+            .document_type
+            .as_ref()
+            .and_then(|document_type| {
+                policy
+                    .rules
+                    .iter()
+                    .find(|rule| &rule.document_type == document_type)
+            })
+            .map(|rule| rule.retention_years)
+            .or(policy.default_retention_years)
+    }
+
+    pub fn is_project_on_hold(project_id: ProjectId) -> bool {
+        PROJECT_LEGAL_HOLDS.with(|holds| holds.borrow().contains_key(&project_id))
+    }
+
+    /// Whether the document is held, directly or through its project.
+    pub fn is_on_hold(document: &Document) -> bool {
+        DOCUMENT_LEGAL_HOLDS.with(|holds| holds.borrow().contains_key(&document.id))
+            || Self::is_project_on_hold(document.project_id)
+    }
+
+    /// Stores the audit record of a purge, assigning its ID.
+    pub fn record_purge(mut record: PurgeRecord) -> PurgeRecordId {
+        PURGE_RECORDS.with(|records| {
+            let mut records = records.borrow_mut();
+            record.id = records
+                .last_key_value()
+                .map_or(0, |(id, _)| id.saturating_add(1));
+            let id = record.id;
+            records.insert(id, record);
+            id
+        })
+    }
+
+    pub fn get_retention_policy() -> GetRetentionPolicyResult {
+        let principal = ic_cdk::api::msg_caller();
+        match UserManager::authenticate(principal, "retention policy read") {
+            Ok(_) => GetRetentionPolicyResult::Ok(Self::get_policy()),
+            Err(e) => GetRetentionPolicyResult::Err(e),
+        }
+    }
+
+    fn validate_retention_years(retention_years: u32) -> Result<(), AppError> {
+        if retention_years == 0 || retention_years > MAX_RETENTION_YEARS {
+            return Err(AppError::ValidationError(format!(
+                "Retention period must be between 1 and {} years",
+                MAX_RETENTION_YEARS
+            )));
+        }
+        Ok(())
+    }
+
+    /// Replaces the retention policy. Shortening a retention period takes effect on the
+    /// next run of the retention job, including for revisions retired long ago.
+    pub fn set_retention_policy(input: SetRetentionPolicyInput) -> SetRetentionPolicyResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Retention policy update attempt [principal={}, rules={}, default_retention_years={:?}]",
+            principal,
+            input.rules.len(),
+            input.default_retention_years
+        );
+
+        let user = match UserManager::authenticate(principal, "retention policy update") {
+            Ok(user) => user,
+            Err(e) => return SetRetentionPolicyResult::Err(e),
+        };
+        if let Err(e) = Self::authorize(
+            &user,
+            OrganizationPermission::ConfigureSettings,
+            "retention policy update",
+        ) {
+            return SetRetentionPolicyResult::Err(e);
+        }
+        if input.rules.len() > MAX_RETENTION_RULES {
+            log_warn!(
+                "retention: Too many retention rules [rules={}, principal={}]",
+                input.rules.len(),
+                principal
+            );
+            return SetRetentionPolicyResult::Err(AppError::ValidationError(format!(
+                "A retention policy can have at most {} rules",
+                MAX_RETENTION_RULES
+            )));
+        }
+        let mut rules: Vec<RetentionRule> = Vec::new();
+        for rule in input.rules {
+            let document_type =
+                match DocumentManager::normalize_document_type(Some(rule.document_type)) {
+                    Ok(Some(document_type)) => document_type,
+                    Ok(None) => {
+                        log_warn!(
+                            "retention: Retention rule without a document type [principal={}]",
+                            principal
+                        );
+                        return SetRetentionPolicyResult::Err(AppError::ValidationError(
+                            "Retention rules must name a document type".to_string(),
+                        ));
+                    }
+                    Err(e) => {
+                        log_warn!(
+                            "retention: Invalid retention rule document type [principal={}] - {:?}",
+                            principal,
+                            e
+                        );
+                        return SetRetentionPolicyResult::Err(e);
+                    }
+                };
+            if let Err(e) = Self::validate_retention_years(rule.retention_years) {
+                log_warn!(
+                    "retention: Invalid retention period [document_type='{}', retention_years={}, principal={}]",
+                    document_type,
+                    rule.retention_years,
+                    principal
+                );
+                return SetRetentionPolicyResult::Err(e);
+            }
+            if rules.iter().any(|rule| rule.document_type == document_type) {
+                log_warn!(
+                    "retention: Duplicate retention rule [document_type='{}', principal={}]",
+                    document_type,
+                    principal
+                );
+                return SetRetentionPolicyResult::Err(AppError::ValidationError(format!(
+                    "More than one retention rule for document type '{}'",
+                    document_type
+                )));
+            }
+            rules.push(RetentionRule {
+                document_type,
+                retention_years: rule.retention_years,
+            });
+        }
+        if let Some(retention_years) = input.default_retention_years {
+            if let Err(e) = Self::validate_retention_years(retention_years) {
+                log_warn!(
+                    "retention: Invalid default retention period [retention_years={}, principal={}]",
+                    retention_years,
+                    principal
+                );
+                return SetRetentionPolicyResult::Err(e);
+            }
+        }
+
+        let policy = RetentionPolicy {
+            rules,
+            default_retention_years: input.default_retention_years,
+            updated_at: Some(ic_cdk::api::time()),
+            updated_by: Some(user.id),
+        };
+        RETENTION_POLICY.with(|cell| {
+            cell.borrow_mut()
+                .set(policy.clone())
+                .expect("Failed to set retention policy in stable memory");
+        });
+        log_info!(
+            "retention: Retention policy updated [rules={:?}, default_retention_years={:?}, user_id={}, principal={}]",
+            policy
+                .rules
+                .iter()
+                .map(|rule| (rule.document_type.as_str(), rule.retention_years))
+                .collect::<Vec<_>>(),
+            policy.default_retention_years,
+            user.id,
+            principal
+        );
+        SetRetentionPolicyResult::Ok(policy)
+    }
+
+    /// Places or releases a legal hold on a document or a project.
+    pub fn set_legal_hold(input: SetLegalHoldInput) -> SetLegalHoldResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Legal hold update attempt [principal={}, target={:?}, hold={}]",
+            principal,
+            input.target,
+            input.hold
+        );
+
+        let user = match UserManager::authenticate(principal, "legal hold update") {
+            Ok(user) => user,
+            Err(e) => return SetLegalHoldResult::Err(e),
+        };
+        if let Err(e) = Self::authorize(
+            &user,
+            OrganizationPermission::ConfigureSettings,
+            "legal hold update",
+        ) {
+            return SetLegalHoldResult::Err(e);
+        }
+        let reason = input.reason.trim().to_string();
+        if reason.is_empty() {
+            log_warn!(
+                "legal_hold: Legal hold reason is missing [target={:?}, principal={}]",
+                input.target,
+                principal
+            );
+            return SetLegalHoldResult::Err(AppError::ValidationError(
+                "A reason is required to place or release a legal hold".to_string(),
+            ));
+        }
+        if reason.chars().count() > MAX_LEGAL_HOLD_REASON_LENGTH {
+            log_warn!(
+                "legal_hold: Legal hold reason too long [target={:?}, length={}, principal={}]",
+                input.target,
+                reason.chars().count(),
+                principal
+            );
+            return SetLegalHoldResult::Err(AppError::ValidationError(format!(
+                "Legal hold reason must not be longer than {} characters",
+                MAX_LEGAL_HOLD_REASON_LENGTH
+            )));
+        }
+
+        let hold = LegalHold {
+            target: input.target.clone(),
+            reason: reason.clone(),
+            placed_at: ic_cdk::api::time(),
+            placed_by: user.id,
+        };
+        let changed = match input.target {
+            LegalHoldTarget::Document(document_id) => {
+                if DocumentManager::get_by_id(document_id).is_none() {
+                    log_warn!(
+                        "legal_hold: Document not found [document_id={}, principal={}]",
+                        document_id,
+                        principal
+                    );
+                    return SetLegalHoldResult::Err(AppError::EntityNotFound(
+                        "Document not found".to_string(),
+                    ));
+                }
+                DOCUMENT_LEGAL_HOLDS.with(|holds| {
+                    let mut holds = holds.borrow_mut();
+                    if input.hold {
+                        holds.insert(document_id, hold).is_none()
+                    } else {
+                        holds.remove(&document_id).is_some()
+                    }
+                })
+            }
+            LegalHoldTarget::Project(project_id) => {
+                if ProjectsManager::get_by_id(project_id).is_none() {
+                    log_warn!(
+                        "legal_hold: Project not found [project_id={}, principal={}]",
+                        project_id,
+                        principal
+                    );
+                    return SetLegalHoldResult::Err(AppError::EntityNotFound(
+                        "Project not found".to_string(),
+                    ));
+                }
+                PROJECT_LEGAL_HOLDS.with(|holds| {
+                    let mut holds = holds.borrow_mut();
+                    if input.hold {
+                        holds.insert(project_id, hold).is_none()
+                    } else {
+                        holds.remove(&project_id).is_some()
+                    }
+                })
+            }
+        };
+        if !input.hold && !changed {
+            log_warn!(
+                "legal_hold: No legal hold to release [target={:?}, principal={}]",
+                input.target,
+                principal
+            );
+            return SetLegalHoldResult::Err(AppError::EntityNotFound(
+                "No legal hold is in place".to_string(),
+            ));
+        }
+
+        log_info!(
+            "legal_hold: Legal hold {} [target={:?}, reason='{}', user_id={}, principal={}]",
+            if input.hold { "placed" } else { "released" },
+            input.target,
+            reason,
+            user.id,
+            principal
+        );
+        SetLegalHoldResult::Ok
+    }
+
+    pub fn list_legal_holds() -> ListLegalHoldsResult {
+        let principal = ic_cdk::api::msg_caller();
+        let user = match UserManager::authenticate(principal, "legal hold listing") {
+            Ok(user) => user,
+            Err(e) => return ListLegalHoldsResult::Err(e),
+        };
+        if let Err(e) = Self::authorize(
+            &user,
+            OrganizationPermission::ViewAuditLogs,
+            "legal hold listing",
+        ) {
+            return ListLegalHoldsResult::Err(e);
+        }
+        let mut holds: Vec<LegalHold> =
+            PROJECT_LEGAL_HOLDS.with(|holds| holds.borrow().values().collect());
+        holds
+            .extend(DOCUMENT_LEGAL_HOLDS.with(|holds| holds.borrow().values().collect::<Vec<_>>()));
+        ListLegalHoldsResult::Ok(holds)
+    }
+
+    /// Purges recorded by the retention job, most recent first.
+    pub fn list_purge_records(input: ListPurgeRecordsInput) -> ListPurgeRecordsResult {
+        let principal = ic_cdk::api::msg_caller();
+        let user = match UserManager::authenticate(principal, "purge record listing") {
+            Ok(user) => user,
+            Err(e) => return ListPurgeRecordsResult::Err(e),
+        };
+        if let Err(e) = Self::authorize(
+            &user,
+            OrganizationPermission::ViewAuditLogs,
+            "purge record listing",
+        ) {
+            return ListPurgeRecordsResult::Err(e);
+        }
+        ListPurgeRecordsResult::Ok(PURGE_RECORDS.with(|records| {
+            records
+                .borrow()
+                .iter()
+                .rev()
+                .map(|(_, record)| record)
+                .filter(|record| {
+                    input
+                        .document_id
+                        .is_none_or(|document_id| record.document_id == document_id)
+                })
+                .collect::<Vec<_>>()
+        }))
+    }
+}
@@ -0,0 +1,7 @@
+use crate::reviews::ReviewsManager;
+use shared::types::reviews::{GetDocumentReviewInput, GetDocumentReviewResult};
+
+#[ic_cdk_macros::query]
+pub fn get_document_review(input: GetDocumentReviewInput) -> GetDocumentReviewResult {
+    ReviewsManager::get_document_review(input)
+}
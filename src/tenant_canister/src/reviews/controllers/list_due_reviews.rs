@@ -0,0 +1,7 @@
+use crate::reviews::ReviewsManager;
+use shared::types::reviews::{ListDueReviewsInput, ListDueReviewsResult};
+
+#[ic_cdk_macros::query]
+pub fn list_due_reviews(input: ListDueReviewsInput) -> ListDueReviewsResult {
+    ReviewsManager::list_due_reviews(input)
+}
@@ -0,0 +1,3 @@
+pub mod get_document_review;
+pub mod list_due_reviews;
+pub mod set_review_schedule;
@@ -0,0 +1,7 @@
+use crate::reviews::ReviewsManager;
+use shared::types::reviews::{SetReviewScheduleInput, SetReviewScheduleResult};
+
+#[ic_cdk_macros::update]
+pub fn set_review_schedule(input: SetReviewScheduleInput) -> SetReviewScheduleResult {
+    ReviewsManager::set_review_schedule(input)
+}
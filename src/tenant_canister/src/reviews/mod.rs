@@ -0,0 +1,4 @@
+pub mod controllers;
+pub mod reviews_manager;
+
+pub use reviews_manager::ReviewsManager;
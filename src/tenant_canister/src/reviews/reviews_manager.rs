@@ -0,0 +1,309 @@
+use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap};
+use shared::consts::memory_ids::tenant_canister::REVIEW_SCHEDULES_MEMORY_ID;
+use shared::consts::reviews::{MAX_REVIEW_INTERVAL_MONTHS, REVIEW_CHECK_INTERVAL_SECONDS};
+use shared::types::access_control::{DocumentPermission, Permission};
+use shared::types::documents::{Document, DocumentId};
+use shared::types::errors::AppError;
+use shared::types::reviews::{
+    DocumentReview, GetDocumentReviewInput, GetDocumentReviewResult, ListDueReviewsInput,
+    ListDueReviewsResult, ReviewSchedule, SetReviewScheduleInput, SetReviewScheduleResult,
+};
+use shared::types::revisions::{Revision, RevisionStatus};
+use shared::utils::time::{add_months, NANOS_PER_DAY};
+use shared::{log_debug, log_info, log_warn};
+use std::cell::RefCell;
+use std::time::Duration;
+
+use crate::approvals::approvals_manager::ApprovalsManager;
+use crate::documents::DocumentManager;
+use crate::projects::projects_manager::ProjectsManager;
+use crate::revisions::revisions_manager::RevisionsManager;
+use crate::users::user_manager::UserManager;
+
+type Memory = VirtualMemory<DefaultMemoryImpl>;
+
+thread_local! {
+    static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
+        RefCell::new(MemoryManager::init(DefaultMemoryImpl::default()));
+
+    static REVIEW_SCHEDULES: RefCell<StableBTreeMap<DocumentId, ReviewSchedule, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(REVIEW_SCHEDULES_MEMORY_ID))),
+        )
+    );
+}
+
+/// Periodic reviews of released documents. A document with a review interval is due
+/// for review that many months after its released revision was released; releasing a
+/// new revision starts the interval again. A timer flags documents whose review is
+/// overdue.
+pub struct ReviewsManager;
+
+impl ReviewsManager {
+    fn get_schedule(document_id: DocumentId) -> Option<ReviewSchedule> {
+        REVIEW_SCHEDULES.with(|schedules| schedules.borrow().get(&document_id))
+    }
+
+    fn insert_schedule(schedule: ReviewSchedule) {
+        REVIEW_SCHEDULES.with(|schedules| {
+            schedules
+                .borrow_mut()
+                .insert(schedule.document_id, schedule);
+        });
+    }
+
+    fn released_revision(document: &Document) -> Option<Revision> {
+        document
+            .revisions
+            .iter()
+            .rev()
+            .filter_map(|revision_id| RevisionsManager::get_by_id(*revision_id))
+            .find(|revision| ApprovalsManager::get_status(revision) == RevisionStatus::Released)
+    }
+
+    fn document_review(document: &Document, schedule: &ReviewSchedule) -> DocumentReview {
+        let released = Self::released_revision(document);
+        let released_at = released.as_ref().and_then(|revision| revision.released_at);
+        DocumentReview {
+            document_id: document.id,
+            project_id: document.project_id,
+            title: document.title.clone(),
+            interval_months: schedule.interval_months,
+            owner: schedule.owner,
+            released_revision_id: released.map(|revision| revision.id),
+            released_at,
+            next_review_due: released_at
+                .map(|released_at| add_months(released_at, schedule.interval_months)),
+            overdue_since: schedule.overdue_since,
+        }
+    }
+
+    /// Whether the overdue flag of a schedule changed.
+    fn refresh_overdue(schedule: &mut ReviewSchedule, review: &DocumentReview, now: u64) -> bool {
+        let overdue = review.next_review_due.is_some_and(|due| due <= now);
+        let overdue_since = match (overdue, schedule.overdue_since) {
+            (true, Some(since)) => Some(since),
+            (true, None) => Some(now),
+            (false, _) => None,
+        };
+        let changed = overdue_since != schedule.overdue_since;
+        schedule.overdue_since = overdue_since;
+        changed
+    }
+
+    /// Sets or removes the review interval of a document. Requires permission to
+    /// update the document; the owner must be a member of its project.
+    pub fn set_review_schedule(input: SetReviewScheduleInput) -> SetReviewScheduleResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Review schedule update attempt [principal={}, document_id={}, interval_months={:?}]",
+            principal,
+            input.document_id,
+            input.interval_months
+        );
+
+        let user = match UserManager::authenticate(principal, "review schedule update") {
+            Ok(user) => user,
+            Err(e) => return SetReviewScheduleResult::Err(e),
+        };
+        let document = match DocumentManager::get_by_id(input.document_id) {
+            Some(document) => document,
+            None => {
+                log_warn!(
+                    "document_review: Document not found [document_id={}, principal={}]",
+                    input.document_id,
+                    principal
+                );
+                return SetReviewScheduleResult::Err(AppError::EntityNotFound(
+                    "Document not found".to_string(),
+                ));
+            }
+        };
+        if !DocumentManager::has_permission(
+            user.id,
+            &document,
+            &Permission::Document(DocumentPermission::Update),
+        ) {
+            log_warn!(
+                "access_control: Permission denied for review schedule update [user_id={}, document_id={}]",
+                user.id,
+                document.id
+            );
+            return SetReviewScheduleResult::Err(AppError::Unauthorized);
+        }
+
+        let Some(interval_months) = input.interval_months else {
+            REVIEW_SCHEDULES.with(|schedules| schedules.borrow_mut().remove(&document.id));
+            log_info!(
+                "document_review: Review schedule removed [document_id={}, user_id={}, principal={}]",
+                document.id,
+                user.id,
+                principal
+            );
+            return SetReviewScheduleResult::Ok(None);
+        };
+        if interval_months == 0 || interval_months > MAX_REVIEW_INTERVAL_MONTHS {
+            log_warn!(
+                "document_review: Invalid review interval [document_id={}, interval_months={}, principal={}]",
+                document.id,
+                interval_months,
+                principal
+            );
+            return SetReviewScheduleResult::Err(AppError::ValidationError(format!(
+                "Review interval must be between 1 and {} months",
+                MAX_REVIEW_INTERVAL_MONTHS
+            )));
+        }
+        let existing = Self::get_schedule(document.id);
+        let owner = input
+            .owner
+            .or(existing.as_ref().map(|schedule| schedule.owner))
+            .unwrap_or(document.created_by);
+        if !ProjectsManager::is_member(document.project_id, owner) {
+            log_warn!(
+                "document_review: Review owner is not a project member [document_id={}, owner={}, principal={}]",
+                document.id,
+                owner,
+                principal
+            );
+            return SetReviewScheduleResult::Err(AppError::ValidationError(
+                "Review owner must be a member of the document's project".to_string(),
+            ));
+        }
+
+        let now = ic_cdk::api::time();
+        let mut schedule = ReviewSchedule {
+            document_id: document.id,
+            interval_months,
+            owner,
+            updated_at: now,
+            updated_by: user.id,
+            overdue_since: existing.and_then(|schedule| schedule.overdue_since),
+        };
+        let mut review = Self::document_review(&document, &schedule);
+        Self::refresh_overdue(&mut schedule, &review, now);
+        review.overdue_since = schedule.overdue_since;
+        Self::insert_schedule(schedule);
+
+        log_info!(
+            "document_review: Review schedule set [document_id={}, interval_months={}, owner={}, next_review_due={:?}, user_id={}, principal={}]",
+            document.id,
+            interval_months,
+            owner,
+            review.next_review_due,
+            user.id,
+            principal
+        );
+        SetReviewScheduleResult::Ok(Some(review))
+    }
+
+    pub fn get_document_review(input: GetDocumentReviewInput) -> GetDocumentReviewResult {
+        let principal = ic_cdk::api::msg_caller();
+        let user = match UserManager::authenticate(principal, "document review status") {
+            Ok(user) => user,
+            Err(e) => return GetDocumentReviewResult::Err(e),
+        };
+        let document = match DocumentManager::get_by_id(input.document_id) {
+            Some(document) => document,
+            None => {
+                log_warn!(
+                    "document_review: Document not found [document_id={}, principal={}]",
+                    input.document_id,
+                    principal
+                );
+                return GetDocumentReviewResult::Err(AppError::EntityNotFound(
+                    "Document not found".to_string(),
+                ));
+            }
+        };
+        if !DocumentManager::has_permission(
+            user.id,
+            &document,
+            &Permission::Document(DocumentPermission::Read),
+        ) {
+            log_warn!(
+                "access_control: Permission denied for document review status [user_id={}, document_id={}]",
+                user.id,
+                document.id
+            );
+            return GetDocumentReviewResult::Err(AppError::Unauthorized);
+        }
+        GetDocumentReviewResult::Ok(
+            Self::get_schedule(document.id)
+                .map(|schedule| Self::document_review(&document, &schedule)),
+        )
+    }
+
+    /// Reviews of documents the caller can read that are overdue, or due within the
+    /// given number of days, earliest first.
+    pub fn list_due_reviews(input: ListDueReviewsInput) -> ListDueReviewsResult {
+        let principal = ic_cdk::api::msg_caller();
+        let user = match UserManager::authenticate(principal, "due review listing") {
+            Ok(user) => user,
+            Err(e) => return ListDueReviewsResult::Err(e),
+        };
+        let horizon = ic_cdk::api::time()
+            .saturating_add(u64::from(input.due_within_days.unwrap_or_default()) * NANOS_PER_DAY);
+        let read = Permission::Document(DocumentPermission::Read);
+        let schedules: Vec<ReviewSchedule> =
+            REVIEW_SCHEDULES.with(|schedules| schedules.borrow().values().collect());
+
+        let mut reviews: Vec<DocumentReview> = schedules
+            .iter()
+            .filter(|schedule| input.owner.is_none_or(|owner| schedule.owner == owner))
+            .filter_map(|schedule| {
+                let document = DocumentManager::get_by_id(schedule.document_id)?;
+                let in_scope = input
+                    .project_id
+                    .is_none_or(|project_id| document.project_id == project_id)
+                    && DocumentManager::has_permission(user.id, &document, &read);
+                in_scope.then(|| Self::document_review(&document, schedule))
+            })
+            .filter(|review| review.next_review_due.is_some_and(|due| due <= horizon))
+            .collect();
+        reviews.sort_by_key(|review| (review.next_review_due, review.document_id));
+        ListDueReviewsResult::Ok(reviews)
+    }
+
+    /// Flags documents whose review became overdue and clears the flag of documents
+    /// that have been released again since.
+    pub fn flag_overdue_reviews() {
+        let now = ic_cdk::api::time();
+        let schedules: Vec<ReviewSchedule> =
+            REVIEW_SCHEDULES.with(|schedules| schedules.borrow().values().collect());
+        for mut schedule in schedules {
+            let Some(document) = DocumentManager::get_by_id(schedule.document_id) else {
+                continue;
+            };
+            let review = Self::document_review(&document, &schedule);
+            if !Self::refresh_overdue(&mut schedule, &review, now) {
+                continue;
+            }
+            match schedule.overdue_since {
+                Some(_) => log_warn!(
+                    "document_review: Review overdue [document_id={}, project_id={}, owner={}, next_review_due={:?}]",
+                    document.id,
+                    document.project_id,
+                    schedule.owner,
+                    review.next_review_due
+                ),
+                None => log_info!(
+                    "document_review: Review no longer overdue [document_id={}, released_revision_id={:?}]",
+                    document.id,
+                    review.released_revision_id
+                ),
+            }
+            Self::insert_schedule(schedule);
+        }
+    }
+
+    /// Checks review schedules hourly, so a document is flagged overdue within an hour
+    /// of its review falling due.
+    pub fn start_review_timer() {
+        ic_cdk_timers::set_timer_interval(
+            Duration::from_secs(REVIEW_CHECK_INTERVAL_SECONDS),
+            Self::flag_overdue_reviews,
+        );
+    }
+}
@@ -0,0 +1,7 @@
+use crate::revisions::revisions_manager::RevisionsManager;
+use shared::types::revisions::GetContentTypePolicyResult;
+
+#[ic_cdk_macros::query]
+pub fn get_content_type_policy() -> GetContentTypePolicyResult {
+    RevisionsManager::get_content_type_policy()
+}
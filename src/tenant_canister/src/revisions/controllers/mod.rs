@@ -1,9 +1,11 @@
 pub mod create_revision;
 pub mod download_revision_content;
-pub mod finish_revision_content_upload;
+pub mod get_content_type_policy;
 pub mod get_diff_revisions;
 pub mod get_revision_content;
 pub mod get_revision_content_chunk;
 pub mod list_revision_contents;
 pub mod list_revisions;
-pub mod store_revision_content_chunk;
+pub mod rollback_document;
+pub mod set_content_type_policy;
+pub mod verify_content;
@@ -0,0 +1,7 @@
+use crate::revisions::revisions_manager::RevisionsManager;
+use shared::types::revisions::{RollbackDocumentInput, RollbackDocumentResult};
+
+#[ic_cdk_macros::update]
+pub fn rollback_document(input: RollbackDocumentInput) -> RollbackDocumentResult {
+    RevisionsManager::rollback_document(input)
+}
@@ -0,0 +1,7 @@
+use crate::revisions::revisions_manager::RevisionsManager;
+use shared::types::revisions::{SetContentTypePolicyInput, SetContentTypePolicyResult};
+
+#[ic_cdk_macros::update]
+pub fn set_content_type_policy(input: SetContentTypePolicyInput) -> SetContentTypePolicyResult {
+    RevisionsManager::set_content_type_policy(input)
+}
@@ -0,0 +1,7 @@
+use crate::revisions::revisions_manager::RevisionsManager;
+use shared::types::revisions::{VerifyContentInput, VerifyContentResult};
+
+#[ic_cdk_macros::query]
+pub fn verify_content(input: VerifyContentInput) -> VerifyContentResult {
+    RevisionsManager::verify_content(input)
+}
@@ -1,36 +1,79 @@
 use shared::consts::memory_ids::tenant_canister::{
-    REVISIONS_MEMORY_ID, REVISION_CHUNK_REFS_MEMORY_ID, REVISION_CONTENT_CHUNKS_MEMORY_ID,
-    REVISION_CONTENT_MEMORY_ID, REVISION_CONTENT_METADATA_MEMORY_ID,
+    CHUNK_USAGE_MEMORY_ID, CONTENT_REVISION_INDEX_MEMORY_ID, CONTENT_TYPE_POLICY_MEMORY_ID,
+    LEGACY_REVISION_CHUNK_REFS_MEMORY_ID, LEGACY_REVISION_CONTENT_CHUNKS_MEMORY_ID,
+    LEGACY_REVISION_CONTENT_METADATA_MEMORY_ID, REVISIONS_MEMORY_ID, REVISION_CHUNK_REFS_MEMORY_ID,
+    REVISION_CONTENT_CHUNKS_MEMORY_ID, REVISION_CONTENT_INDEX_MEMORY_ID,
+    REVISION_CONTENT_MEMORY_ID, REVISION_CONTENT_METADATA_MEMORY_ID, STORAGE_MIGRATION_MEMORY_ID,
 };
-use shared::types::documents::DocumentId;
+use shared::consts::retention::{MAX_PURGES_PER_RUN, RETENTION_CHECK_INTERVAL_SECONDS};
+use shared::consts::revisions::{
+    DEFAULT_ALLOWED_MIME_TYPES, MAX_ALLOWED_MIME_TYPES, MAX_DIFF_CONTENT_SIZE, MAX_DOCUMENT_SIZE,
+    MAX_ROLLBACK_REASON_LENGTH, MAX_VERIFY_CHUNKS, MIGRATION_BATCH_BYTES, MIGRATION_BATCH_SIZE,
+};
+use shared::consts::search::SEARCH_MIGRATION_BATCH_BYTES;
+use shared::types::access_control::{
+    DocumentPermission, OrganizationPermission, Permission, RevisionPermission,
+};
+use shared::types::documents::{Document, DocumentId};
+use shared::types::encryption::ContentEncryption;
 use shared::types::errors::AppError;
+use shared::types::imports::ImportOrigin;
+use shared::types::notifications::NotificationEvent;
+use shared::types::retention::{ContentPurge, PurgeRecord, PurgedContent, RetentionPolicy};
+use shared::types::revisions::{
+    ChunkDigest, ChunkUsage, ChunkVerification, ContentDiff, ContentDiffSide, ContentVerification,
+    CreateRevisionInput, CreateRevisionResult, DiffChangeKind, DiffRevisionsInput,
+    DiffRevisionsResult, DownloadRevisionContentInput, DownloadRevisionContentResult,
+    GetRevisionContentChunkInput, GetRevisionContentChunkResult, GetRevisionContentInput,
+    GetRevisionContentResult, ListRevisionContentsInput, ListRevisionContentsResult,
+    ListRevisionsInput, ListRevisionsResult, RevisionContent, RevisionContentChunk,
+    RevisionContentData, RevisionContentId, RevisionContentMetadata, RevisionContentType,
+    RevisionDiff, StorageMigration, StorageMigrationPhase, VerifyContentInput, VerifyContentResult,
+};
 use shared::types::revisions::{
-    CreateRevisionInput, CreateRevisionResult, DiffRevisionsInput, DiffRevisionsResult,
-    DownloadRevisionContentInput, DownloadRevisionContentResult, FinishRevisionContentUploadInput,
-    FinishRevisionContentUploadResult, GetRevisionContentChunkInput, GetRevisionContentChunkResult,
-    GetRevisionContentInput, GetRevisionContentResult, ListRevisionContentsInput,
-    ListRevisionContentsResult, ListRevisionsInput, ListRevisionsResult, RevisionContent,
-    RevisionContentChunk, RevisionContentData, RevisionContentId, RevisionContentMetadata,
-    RevisionContentType, StoreRevisionContentChunkInput, StoreRevisionContentChunkResult,
+    ContentTypePolicy, GetContentTypePolicyResult, Revision, RevisionId, RevisionRollback,
+    RevisionStatus, RollbackDocumentInput, RollbackDocumentResult, SetContentTypePolicyInput,
+    SetContentTypePolicyResult,
+};
+use shared::types::uploads::{StorageReport, UploadSession};
+use shared::types::users::{GetUserResult, UserId};
+use shared::utils::diff::diff_lines;
+use shared::utils::mime::{
+    is_kind_compatible, is_mime_allowed, matches_declared_mime, normalize_mime, sniff_mime,
 };
-use shared::types::revisions::{Revision, RevisionId};
-use shared::types::users::GetUserResult;
 use shared::utils::pagination::paginate;
+use shared::utils::time::add_months;
 use shared::{log_debug, log_error, log_info, log_warn};
 
+use crate::access_control::AccessControlManager;
 use crate::documents;
+use crate::documents::DocumentManager;
+use crate::encryption::EncryptionManager;
+use crate::notifications::NotificationsManager;
+use crate::previews::PreviewsManager;
+use crate::quotas::quotas_manager::QuotasManager;
+use crate::retention::RetentionManager;
+use crate::search::SearchManager;
+use crate::share_links::share_links_manager::ShareLinksManager;
+use crate::uploads::uploads_manager::UploadsManager;
 use crate::users::user_manager::UserManager;
 use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
-use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap};
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap, StableCell};
 use sha2::{Digest, Sha256};
 use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 
 type Memory = VirtualMemory<DefaultMemoryImpl>;
-type RevisionContentMetadataStore = StableBTreeMap<String, RevisionContentMetadata, Memory>; // key: "revision_id_content_index"
+type RevisionContentMetadataStore =
+    StableBTreeMap<(RevisionId, u32), RevisionContentMetadata, Memory>; // key: (revision_id, content_index)
 type RevisionContentStore = StableBTreeMap<RevisionContentId, RevisionContent, Memory>;
-type RevisionContentChunkStore = StableBTreeMap<String, Vec<u8>, Memory>; // key: SHA-256 checksum -> chunk data
-type ChunkReferenceStore = StableBTreeMap<String, String, Memory>; // key: "revision_id_content_index_chunk_id" -> checksum
+type RevisionContentIndex = StableBTreeMap<(RevisionId, u32), RevisionContentId, Memory>; // key: (revision_id, content_index)
+type ContentRevisionIndex = StableBTreeMap<(RevisionContentId, RevisionId), (), Memory>; // key: (content_id, revision_id)
+type RevisionContentChunkStore = StableBTreeMap<ChunkDigest, Vec<u8>, Memory>; // key: SHA-256 digest -> chunk data
+type ChunkReferenceStore = StableBTreeMap<(RevisionId, u32, u32), ChunkDigest, Memory>; // key: (revision_id, content_index, chunk_id)
+type ChunkUsageStore = StableBTreeMap<ChunkDigest, ChunkUsage, Memory>; // key: SHA-256 digest -> reference count and size
 
 thread_local! {
     static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
@@ -55,6 +98,20 @@ thread_local! {
         )
     );
 
+    // Contents of each revision, in order
+    static REVISION_CONTENT_INDEX: RefCell<RevisionContentIndex> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(REVISION_CONTENT_INDEX_MEMORY_ID))),
+        )
+    );
+
+    // Revisions containing each content item
+    static CONTENT_REVISION_INDEX: RefCell<ContentRevisionIndex> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(CONTENT_REVISION_INDEX_MEMORY_ID))),
+        )
+    );
+
     // Deduplicated chunk storage (content-addressable)
     static REVISION_CONTENT_CHUNKS: RefCell<RevisionContentChunkStore> = RefCell::new(
         StableBTreeMap::init(
@@ -69,6 +126,58 @@ thread_local! {
         )
     );
 
+    // Reference counts of the deduplicated chunks
+    static CHUNK_USAGE: RefCell<ChunkUsageStore> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(CHUNK_USAGE_MEMORY_ID))),
+        )
+    );
+
+    // MIME types accepted for new content
+    static CONTENT_TYPE_POLICY: RefCell<StableCell<ContentTypePolicy, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(CONTENT_TYPE_POLICY_MEMORY_ID))),
+            ContentTypePolicy {
+                allowed_mime_types: DEFAULT_ALLOWED_MIME_TYPES
+                    .iter()
+                    .map(|mime| mime.to_string())
+                    .collect(),
+            },
+        ).expect("Failed to initialize content type policy stable cell")
+    );
+
+    // String keyed stores of earlier versions, emptied by `start_storage_migration`
+    static LEGACY_REVISION_CONTENT_METADATA: RefCell<StableBTreeMap<String, RevisionContentMetadata, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(LEGACY_REVISION_CONTENT_METADATA_MEMORY_ID))),
+        )
+    );
+
+    static LEGACY_REVISION_CONTENT_CHUNKS: RefCell<StableBTreeMap<String, Vec<u8>, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(LEGACY_REVISION_CONTENT_CHUNKS_MEMORY_ID))),
+        )
+    );
+
+    static LEGACY_CHUNK_REFERENCES: RefCell<StableBTreeMap<String, String, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(LEGACY_REVISION_CHUNK_REFS_MEMORY_ID))),
+        )
+    );
+
+    // Progress of moving content stored by earlier versions, see `start_storage_migration`
+    static STORAGE_MIGRATION: RefCell<StableCell<StorageMigration, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(STORAGE_MIGRATION_MEMORY_ID))),
+            StorageMigration {
+                phase: StorageMigrationPhase::Chunks,
+                cursor: 0,
+                hash: None,
+                hashed_chunks: 0,
+            },
+        ).expect("Failed to initialize storage migration stable cell")
+    );
+
     static NEXT_REVISION_ID: AtomicU64 = const { AtomicU64::new(0) };
     static NEXT_CONTENT_ID: AtomicU64 = const { AtomicU64::new(0) };
 }
@@ -108,18 +217,61 @@ impl RevisionsManager {
     }
 
     pub fn list_revision_contents_by_revision_id(revision_id: RevisionId) -> Vec<RevisionContent> {
-        if let Some(revision) = Self::get_by_id(revision_id) {
-            revision
-                .contents
-                .iter()
-                .filter_map(|&content_id| Self::get_revision_content_by_id(content_id))
+        let mut content_ids: Vec<RevisionContentId> = REVISION_CONTENT_INDEX.with(|index| {
+            index
+                .borrow()
+                .values_range((revision_id, 0)..=(revision_id, u32::MAX))
                 .collect()
-        } else {
-            vec![]
+        });
+        if content_ids.is_empty() && Self::is_migrating() {
+            // Not indexed by the storage migration yet
+            content_ids = Self::get_by_id(revision_id)
+                .map(|revision| revision.contents)
+                .unwrap_or_default();
         }
+        content_ids
+            .into_iter()
+            .filter_map(Self::get_revision_content_by_id)
+            .collect()
+    }
+
+    fn index_revision_contents(revision_id: RevisionId, revision: &Revision) {
+        REVISION_CONTENT_INDEX.with(|index| {
+            let mut index = index.borrow_mut();
+            for (content_index, content_id) in revision.contents.iter().enumerate() {
+                index.insert((revision_id, content_index as u32), *content_id);
+            }
+        });
+        CONTENT_REVISION_INDEX.with(|index| {
+            let mut index = index.borrow_mut();
+            for content_id in &revision.contents {
+                index.insert((*content_id, revision_id), ());
+            }
+        });
+    }
+
+    /// IDs of the revisions containing a content item, in ascending order.
+    fn get_revision_ids_containing(content_id: RevisionContentId) -> Vec<RevisionId> {
+        let revision_ids: Vec<RevisionId> = CONTENT_REVISION_INDEX.with(|index| {
+            index
+                .borrow()
+                .keys_range((content_id, 0)..=(content_id, RevisionId::MAX))
+                .map(|(_, revision_id)| revision_id)
+                .collect()
+        });
+        if revision_ids.is_empty() && Self::is_migrating() {
+            // Not indexed by the storage migration yet
+            return Self::get_all()
+                .into_iter()
+                .filter(|revision| revision.contents.contains(&content_id))
+                .map(|revision| revision.id)
+                .collect();
+        }
+        revision_ids
     }
 
     pub fn insert(revision_id: RevisionId, revision: Revision) {
+        Self::index_revision_contents(revision_id, &revision);
         REVISIONS.with(|revisions| {
             revisions.borrow_mut().insert(revision_id, revision);
         });
@@ -129,6 +281,33 @@ impl RevisionsManager {
         REVISIONS.with(|revisions| revisions.borrow().get(&revision_id))
     }
 
+    pub fn set_import_origin(revision_id: RevisionId, origin: ImportOrigin) {
+        REVISIONS.with(|revisions| {
+            let mut revisions_mut = revisions.borrow_mut();
+            if let Some(mut revision) = revisions_mut.get(&revision_id) {
+                revision.imported = Some(origin);
+                revisions_mut.insert(revision_id, revision);
+            }
+        });
+    }
+
+    pub fn update_status(revision_id: RevisionId, status: RevisionStatus) {
+        REVISIONS.with(|revisions| {
+            let mut revisions_mut = revisions.borrow_mut();
+            if let Some(mut revision) = revisions_mut.get(&revision_id) {
+                match status {
+                    RevisionStatus::Released => revision.released_at = Some(ic_cdk::api::time()),
+                    RevisionStatus::Superseded | RevisionStatus::Obsolete => {
+                        revision.retired_at = Some(ic_cdk::api::time())
+                    }
+                    _ => {}
+                }
+                revision.status = Some(status);
+                revisions_mut.insert(revision_id, revision);
+            }
+        });
+    }
+
     pub fn get_revision_range(
         document_id: DocumentId,
         start_index: usize,
@@ -157,207 +336,836 @@ impl RevisionsManager {
         }
     }
 
-    // Helper function to calculate SHA-256 checksum
-    fn calculate_checksum(data: &[u8]) -> String {
+    // Helper function to calculate the SHA-256 digest chunks are addressed by
+    fn calculate_digest(data: &[u8]) -> ChunkDigest {
+        Sha256::digest(data).into()
+    }
+
+    fn parse_digest(checksum: &str) -> Option<ChunkDigest> {
+        hex::decode(checksum).ok()?.try_into().ok()
+    }
+
+    // Parses the "revision_id_content_index_chunk_id" keys of earlier versions
+    fn parse_chunk_key(key: &str) -> Option<(RevisionId, u32, u32)> {
+        let mut parts = key.split('_');
+        let key = (
+            parts.next()?.parse().ok()?,
+            parts.next()?.parse().ok()?,
+            parts.next()?.parse().ok()?,
+        );
+        parts.next().is_none().then_some(key)
+    }
+
+    /// Full bytes of a content item, reassembling chunked content. None if data is missing.
+    pub fn get_content_bytes(content: &RevisionContent) -> Option<Vec<u8>> {
+        match content.content_data.as_ref()? {
+            RevisionContentData::Direct { bytes } => Some(bytes.clone()),
+            RevisionContentData::Chunked {
+                total_chunks,
+                revision_id,
+                content_index,
+                ..
+            } => {
+                let mut bytes = Vec::new();
+                for chunk_id in 0..*total_chunks {
+                    let chunk =
+                        Self::get_revision_content_chunk(*revision_id, *content_index, chunk_id)?;
+                    bytes.extend(chunk.data);
+                }
+                Some(bytes)
+            }
+        }
+    }
+
+    /// SHA-256 checksum (hex) and size of a content item, as recorded when it was stored.
+    /// Purged content keeps the checksum and size its data had. None until the storage
+    /// migration has recorded the checksum of content stored by earlier versions.
+    pub fn get_recorded_checksum(content: &RevisionContent) -> Option<(String, u64)> {
+        if let Some(purge) = &content.purged {
+            return Some((purge.sha256.clone(), purge.size));
+        }
+        content
+            .sha256
+            .clone()
+            .map(|sha256| (sha256, Self::get_content_size(content)))
+    }
+
+    /// Recorded checksum and size of a content item, hashing its data if none is recorded.
+    pub fn get_content_checksum(content: &RevisionContent) -> (String, u64) {
+        Self::get_recorded_checksum(content).unwrap_or_else(|| Self::hash_content(content))
+    }
+
+    /// Hashes the stored data of a content item, reading chunks one at a time.
+    fn hash_content(content: &RevisionContent) -> (String, u64) {
         let mut hasher = Sha256::new();
-        hasher.update(data);
+        let mut size = 0u64;
+        match &content.content_data {
+            Some(RevisionContentData::Direct { bytes }) => {
+                hasher.update(bytes);
+                size = bytes.len() as u64;
+            }
+            Some(RevisionContentData::Chunked {
+                total_chunks,
+                revision_id,
+                content_index,
+                ..
+            }) => {
+                for chunk_id in 0..*total_chunks {
+                    if let Some(chunk) =
+                        Self::get_revision_content_chunk(*revision_id, *content_index, chunk_id)
+                    {
+                        hasher.update(&chunk.data);
+                        size += chunk.data.len() as u64;
+                    }
+                }
+            }
+            None => {}
+        }
+        (hex::encode(hasher.finalize()), size)
+    }
+
+    /// SHA-256 (hex) over the checksums of a revision's content items, in order.
+    /// This is the hash signers attest to.
+    pub fn get_revision_hash(revision: &Revision) -> String {
+        let checksums: Vec<String> = revision
+            .contents
+            .iter()
+            .filter_map(|content_id| Self::get_revision_content_by_id(*content_id))
+            .map(|content| Self::get_content_checksum(&content).0)
+            .collect();
+        Self::hash_checksums(checksums.iter().map(String::as_str))
+    }
+
+    /// Revision hash over already computed content checksums, see `get_revision_hash`.
+    pub fn hash_checksums<'a>(checksums: impl IntoIterator<Item = &'a str>) -> String {
+        let mut hasher = Sha256::new();
+        for checksum in checksums {
+            hasher.update(checksum.as_bytes());
+        }
         hex::encode(hasher.finalize())
     }
 
-    // Chunking functions for revision content with deduplication
-    pub fn store_revision_content_chunk(
-        revision_id: RevisionId,
-        content_index: u32,
-        mut chunk: RevisionContentChunk,
-        content_type: RevisionContentType,
-        file_name: Option<String>,
-    ) -> Result<(), String> {
-        if chunk.data.is_empty() {
-            return Err("Chunk data cannot be empty.".to_string());
+    /// Pairs up the content items of two revisions by file name, or for unnamed items
+    /// by their position among the unnamed items of the same content type.
+    fn match_contents(
+        original: &Revision,
+        updated: &Revision,
+    ) -> Vec<(Option<RevisionContent>, Option<RevisionContent>)> {
+        fn keyed(revision: &Revision) -> Vec<(String, RevisionContent)> {
+            let mut unnamed_counts: Vec<(String, usize)> = Vec::new();
+            RevisionsManager::list_revision_contents_by_revision_id(revision.id)
+                .into_iter()
+                .map(|content| {
+                    let key = match &content.file_name {
+                        Some(file_name) => format!("name:{}", file_name),
+                        None => {
+                            let content_type = format!("{:?}", content.content_type);
+                            let index = match unnamed_counts
+                                .iter_mut()
+                                .find(|(existing, _)| *existing == content_type)
+                            {
+                                Some((_, count)) => {
+                                    *count += 1;
+                                    *count
+                                }
+                                None => {
+                                    unnamed_counts.push((content_type.clone(), 0));
+                                    0
+                                }
+                            };
+                            format!("unnamed:{}:{}", content_type, index)
+                        }
+                    };
+                    (key, content)
+                })
+                .collect()
         }
 
-        // Calculate checksum if not provided
-        let checksum = if let Some(ref existing_checksum) = chunk.checksum {
-            existing_checksum.clone()
-        } else {
-            let calculated = Self::calculate_checksum(&chunk.data);
-            chunk.checksum = Some(calculated.clone());
-            calculated
-        };
+        let original_contents = keyed(original);
+        let mut updated_contents = keyed(updated);
+        let mut pairs = Vec::new();
+        for (key, content) in original_contents {
+            let counterpart = updated_contents
+                .iter()
+                .position(|(updated_key, _)| *updated_key == key)
+                .map(|index| updated_contents.remove(index).1);
+            pairs.push((Some(content), counterpart));
+        }
+        pairs.extend(
+            updated_contents
+                .into_iter()
+                .map(|(_, content)| (None, Some(content))),
+        );
+        pairs
+    }
 
-        let chunk_key = format!("{}_{}_{}", revision_id, content_index, chunk.chunk_id);
+    fn build_content_diffs(original: &Revision, updated: &Revision) -> Vec<ContentDiff> {
+        Self::match_contents(original, updated)
+            .into_iter()
+            .filter_map(|(original_content, updated_content)| {
+                let reference = updated_content.as_ref().or(original_content.as_ref())?;
+                let file_name = reference.file_name.clone();
+                let content_type = reference.content_type.clone();
+                let side = |content: &Option<RevisionContent>| {
+                    content.as_ref().map(|content| {
+                        let (checksum, size) = Self::get_content_checksum(content);
+                        ContentDiffSide {
+                            content_id: content.id,
+                            checksum,
+                            size,
+                        }
+                    })
+                };
+                let original_side = side(&original_content);
+                let updated_side = side(&updated_content);
+
+                let kind = match (&original_side, &updated_side) {
+                    (Some(a), Some(b)) if a.checksum == b.checksum => DiffChangeKind::Unchanged,
+                    (Some(_), Some(_)) => DiffChangeKind::Changed,
+                    (Some(_), None) => DiffChangeKind::Removed,
+                    (None, _) => DiffChangeKind::Added,
+                };
 
-        // Check if this chunk content is already stored (deduplication)
-        let chunk_already_exists =
-            REVISION_CONTENT_CHUNKS.with(|storage| storage.borrow().contains_key(&checksum));
+                // Encrypted Markdown is ciphertext to the canister, and large or purged
+                // content is only compared by checksum
+                let is_text = matches!(content_type, RevisionContentType::Markdown)
+                    && [&original_content, &updated_content]
+                        .into_iter()
+                        .flatten()
+                        .all(|content| {
+                            content.encryption.is_none()
+                                && content.purged.is_none()
+                                && Self::get_content_size(content) <= MAX_DIFF_CONTENT_SIZE
+                        });
+                let lines = if is_text {
+                    let text = |content: &Option<RevisionContent>| {
+                        content
+                            .as_ref()
+                            .and_then(Self::get_content_bytes)
+                            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+                            .unwrap_or_default()
+                    };
+                    diff_lines(&text(&original_content), &text(&updated_content))
+                } else {
+                    vec![]
+                };
 
-        if !chunk_already_exists {
-            // Store the chunk data using checksum as key (content-addressable storage)
-            REVISION_CONTENT_CHUNKS.with(|storage| {
-                storage.borrow_mut().insert(checksum.clone(), chunk.data);
-            });
+                Some(ContentDiff {
+                    file_name,
+                    content_type,
+                    kind,
+                    original: original_side,
+                    updated: updated_side,
+                    lines,
+                })
+            })
+            .collect()
+    }
+
+    // Chunking functions for revision content with deduplication
+    /// Stores chunk data content-addressed by its SHA-256 digest and returns the digest.
+    /// Takes a reference on the chunk that must be given up with `release_chunk_data`.
+    pub fn store_chunk_data(data: Vec<u8>) -> ChunkDigest {
+        let digest = Self::calculate_digest(&data);
+        let size = data.len() as u64;
+        REVISION_CONTENT_CHUNKS.with(|storage| {
+            let mut storage = storage.borrow_mut();
+            if !storage.contains_key(&digest) {
+                storage.insert(digest, data);
+            }
+        });
+        Self::retain_chunk(&digest, Some(size));
+        digest
+    }
+
+    fn retain_chunk(digest: &ChunkDigest, size: Option<u64>) {
+        CHUNK_USAGE.with(|usage| {
+            let mut usage = usage.borrow_mut();
+            let entry = match usage.get(digest) {
+                Some(mut entry) => {
+                    entry.references += 1;
+                    entry
+                }
+                None => ChunkUsage {
+                    references: 1,
+                    size: size
+                        .or_else(|| Self::get_chunk_data(digest).map(|data| data.len() as u64))
+                        .unwrap_or_default(),
+                },
+            };
+            usage.insert(*digest, entry);
+        });
+    }
+
+    /// Gives up a reference on a chunk, removing its data once nothing refers to it.
+    pub fn release_chunk_data(digest: &ChunkDigest) {
+        let removed = CHUNK_USAGE.with(|usage| {
+            let mut usage = usage.borrow_mut();
+            match usage.get(digest) {
+                Some(mut entry) if entry.references > 1 => {
+                    entry.references -= 1;
+                    usage.insert(*digest, entry);
+                    false
+                }
+                _ => {
+                    usage.remove(digest);
+                    true
+                }
+            }
+        });
+        if removed {
+            REVISION_CONTENT_CHUNKS.with(|storage| storage.borrow_mut().remove(digest));
+            log_debug!(
+                "chunk_cleanup: Removed unreferenced chunk [checksum={}]",
+                hex::encode(digest)
+            );
         }
+    }
 
-        // Store reference from revision chunk to checksum
-        CHUNK_REFERENCES.with(|storage| {
-            storage.borrow_mut().insert(chunk_key, checksum);
+    /// Fresh canisters have nothing stored by earlier versions.
+    pub fn skip_storage_migration() {
+        Self::set_storage_migration(StorageMigration {
+            phase: StorageMigrationPhase::Done,
+            cursor: 0,
+            hash: None,
+            hashed_chunks: 0,
         });
+    }
 
-        // Update or create metadata
-        let metadata_key = format!("{}_{}", revision_id, content_index);
-        let metadata = RevisionContentMetadata {
-            revision_id,
-            content_index,
-            total_chunks: chunk.total_chunks,
-            total_size: 0, // Will be calculated when complete
-            content_type,
-            file_name,
-            is_complete: false,
+    fn get_storage_migration() -> StorageMigration {
+        STORAGE_MIGRATION.with(|cell| cell.borrow().get().clone())
+    }
+
+    fn set_storage_migration(migration: StorageMigration) {
+        STORAGE_MIGRATION.with(|cell| {
+            cell.borrow_mut()
+                .set(migration)
+                .expect("Failed to set storage migration in stable memory");
+        });
+    }
+
+    /// Whether content stored by earlier versions is still being moved. Storage cleanup
+    /// and retention wait for the migration, since chunks are not fully reference
+    /// counted until it is done.
+    pub fn is_migrating() -> bool {
+        Self::get_storage_migration().phase != StorageMigrationPhase::Done
+    }
+
+    /// Moves content stored under the string keys of earlier versions to the binary keyed
+    /// stores, indexes the contents of existing revisions, records their checksums and
+    /// builds the search index. Each batch runs in its own message, so the upgrade itself
+    /// stays cheap; reads fall back to the legacy stores until the migration is done.
+    pub fn start_storage_migration() {
+        let migration = Self::get_storage_migration();
+        if migration.phase == StorageMigrationPhase::Done {
+            return;
+        }
+        log_info!(
+            "storage_migration: Resuming revision content migration [phase={:?}, cursor={}]",
+            migration.phase,
+            migration.cursor
+        );
+        ic_cdk_timers::set_timer(Duration::ZERO, Self::run_storage_migration_batch);
+    }
+
+    fn run_storage_migration_batch() {
+        let mut migration = Self::get_storage_migration();
+        let phase_done = match migration.phase {
+            StorageMigrationPhase::Chunks => Self::migrate_legacy_chunks(),
+            StorageMigrationPhase::ChunkReferences => Self::migrate_legacy_chunk_references(),
+            StorageMigrationPhase::Metadata => Self::migrate_legacy_metadata(),
+            StorageMigrationPhase::ContentIndex => Self::index_contents_batch(&mut migration),
+            StorageMigrationPhase::Checksums => Self::record_checksums_batch(&mut migration),
+            StorageMigrationPhase::SearchIndex => Self::index_search_batch(&mut migration),
+            StorageMigrationPhase::Done => return,
         };
+        if phase_done {
+            migration.phase = match migration.phase {
+                StorageMigrationPhase::Chunks => StorageMigrationPhase::ChunkReferences,
+                StorageMigrationPhase::ChunkReferences => StorageMigrationPhase::Metadata,
+                StorageMigrationPhase::Metadata => StorageMigrationPhase::ContentIndex,
+                StorageMigrationPhase::ContentIndex => StorageMigrationPhase::Checksums,
+                StorageMigrationPhase::Checksums => StorageMigrationPhase::SearchIndex,
+                StorageMigrationPhase::SearchIndex | StorageMigrationPhase::Done => {
+                    StorageMigrationPhase::Done
+                }
+            };
+            migration.cursor = 0;
+            log_info!(
+                "storage_migration: Started next phase [phase={:?}]",
+                migration.phase
+            );
+        }
+        let done = migration.phase == StorageMigrationPhase::Done;
+        Self::set_storage_migration(migration);
+        if !done {
+            ic_cdk_timers::set_timer(Duration::ZERO, Self::run_storage_migration_batch);
+        }
+    }
 
-        REVISION_CONTENT_METADATA.with(|storage| {
-            storage.borrow_mut().insert(metadata_key, metadata);
+    /// Moves a batch of chunk data to the digest keyed store. Returns true once the
+    /// legacy store is empty.
+    fn migrate_legacy_chunks() -> bool {
+        let mut bytes = 0u64;
+        let batch: Vec<(String, Vec<u8>)> = LEGACY_REVISION_CONTENT_CHUNKS.with(|storage| {
+            storage
+                .borrow()
+                .iter()
+                .take_while(|(_, data)| {
+                    let within_budget = bytes < MIGRATION_BATCH_BYTES;
+                    bytes += data.len() as u64;
+                    within_budget
+                })
+                .take(MIGRATION_BATCH_SIZE)
+                .collect()
         });
+        for (checksum, data) in batch {
+            match Self::parse_digest(&checksum) {
+                Some(digest) => {
+                    REVISION_CONTENT_CHUNKS
+                        .with(|storage| storage.borrow_mut().insert(digest, data));
+                }
+                None => log_warn!(
+                    "storage_migration: Skipping chunk with invalid checksum [checksum={}]",
+                    checksum
+                ),
+            }
+            LEGACY_REVISION_CONTENT_CHUNKS.with(|storage| storage.borrow_mut().remove(&checksum));
+        }
+        LEGACY_REVISION_CONTENT_CHUNKS.with(|storage| storage.borrow().is_empty())
+    }
 
-        Ok(())
+    /// Moves a batch of chunk references to the binary keyed store, counting a reference
+    /// on each chunk. Returns true once the legacy store is empty.
+    fn migrate_legacy_chunk_references() -> bool {
+        let batch: Vec<(String, String)> = LEGACY_CHUNK_REFERENCES
+            .with(|storage| storage.borrow().iter().take(MIGRATION_BATCH_SIZE).collect());
+        // Chunks counted for the first time are read to record their size
+        let mut bytes = 0u64;
+        for (key, checksum) in batch {
+            if bytes >= MIGRATION_BATCH_BYTES {
+                break;
+            }
+            match (Self::parse_chunk_key(&key), Self::parse_digest(&checksum)) {
+                (Some(chunk_key), Some(digest)) => {
+                    let counted = CHUNK_USAGE.with(|usage| usage.borrow().contains_key(&digest));
+                    let size = if counted {
+                        0
+                    } else {
+                        Self::get_chunk_data(&digest)
+                            .map(|data| data.len() as u64)
+                            .unwrap_or_default()
+                    };
+                    bytes += size;
+                    CHUNK_REFERENCES.with(|storage| storage.borrow_mut().insert(chunk_key, digest));
+                    Self::retain_chunk(&digest, Some(size));
+                }
+                _ => log_warn!(
+                    "storage_migration: Skipping invalid chunk reference [key={}, checksum={}]",
+                    key,
+                    checksum
+                ),
+            }
+            LEGACY_CHUNK_REFERENCES.with(|storage| storage.borrow_mut().remove(&key));
+        }
+        LEGACY_CHUNK_REFERENCES.with(|storage| storage.borrow().is_empty())
     }
 
-    pub fn get_revision_content_chunk(
-        revision_id: RevisionId,
-        content_index: u32,
-        chunk_id: u32,
-    ) -> Option<RevisionContentChunk> {
-        let chunk_key = format!("{}_{}_{}", revision_id, content_index, chunk_id);
-        let metadata_key = format!("{}_{}", revision_id, content_index);
+    /// Moves a batch of chunked content metadata to the binary keyed store. Returns true
+    /// once the legacy store is empty.
+    fn migrate_legacy_metadata() -> bool {
+        let batch: Vec<(String, RevisionContentMetadata)> = LEGACY_REVISION_CONTENT_METADATA
+            .with(|storage| storage.borrow().iter().take(MIGRATION_BATCH_SIZE).collect());
+        for (key, metadata) in batch {
+            REVISION_CONTENT_METADATA.with(|storage| {
+                storage
+                    .borrow_mut()
+                    .insert((metadata.revision_id, metadata.content_index), metadata)
+            });
+            LEGACY_REVISION_CONTENT_METADATA.with(|storage| storage.borrow_mut().remove(&key));
+        }
+        LEGACY_REVISION_CONTENT_METADATA.with(|storage| storage.borrow().is_empty())
+    }
 
-        // First get the checksum reference for this chunk
-        let checksum = CHUNK_REFERENCES.with(|storage| storage.borrow().get(&chunk_key))?;
+    /// Indexes the contents of a batch of revisions. Returns true once all revisions
+    /// are indexed.
+    fn index_contents_batch(migration: &mut StorageMigration) -> bool {
+        let revisions: Vec<(RevisionId, Revision)> = REVISIONS.with(|revisions| {
+            revisions
+                .borrow()
+                .range(migration.cursor..)
+                .take(MIGRATION_BATCH_SIZE)
+                .collect()
+        });
+        for (revision_id, revision) in &revisions {
+            Self::index_revision_contents(*revision_id, revision);
+        }
+        match revisions.last() {
+            Some((revision_id, _)) if revisions.len() == MIGRATION_BATCH_SIZE => {
+                migration.cursor = revision_id + 1;
+                false
+            }
+            _ => true,
+        }
+    }
 
-        // Then get the actual data using the checksum
-        REVISION_CONTENT_CHUNKS.with(|storage| {
-            storage.borrow().get(&checksum).map(|data| {
-                let metadata = REVISION_CONTENT_METADATA
-                    .with(|meta_storage| meta_storage.borrow().get(&metadata_key));
-
-                if let Some(meta) = metadata {
-                    RevisionContentChunk {
-                        chunk_id,
-                        total_chunks: meta.total_chunks,
-                        data,
-                        checksum: Some(checksum),
+    /// Adds a batch of revisions to the search index, at most
+    /// `SEARCH_MIGRATION_BATCH_BYTES` of Markdown text. Returns true once all revisions
+    /// are indexed.
+    fn index_search_batch(migration: &mut StorageMigration) -> bool {
+        let mut bytes = 0u64;
+        for _ in 0..MIGRATION_BATCH_SIZE {
+            let next =
+                REVISIONS.with(|revisions| revisions.borrow().range(migration.cursor..).next());
+            let Some((revision_id, revision)) = next else {
+                return true;
+            };
+            if bytes >= SEARCH_MIGRATION_BATCH_BYTES {
+                return false;
+            }
+            bytes += SearchManager::index_revision(&revision);
+            migration.cursor = revision_id + 1;
+        }
+        false
+    }
+
+    /// Records the checksum of contents stored before checksums were kept, hashing at
+    /// most `MIGRATION_BATCH_BYTES` per batch. Large chunked content is hashed over
+    /// several batches. Returns true once all contents are done.
+    fn record_checksums_batch(migration: &mut StorageMigration) -> bool {
+        let mut bytes = 0u64;
+        let mut count = 0;
+        loop {
+            let next =
+                REVISION_CONTENT.with(|store| store.borrow().range(migration.cursor..).next());
+            let Some((content_id, mut content)) = next else {
+                return true;
+            };
+            if bytes >= MIGRATION_BATCH_BYTES || count >= MIGRATION_BATCH_SIZE {
+                return false;
+            }
+            count += 1;
+            bytes += match &content.content_data {
+                Some(RevisionContentData::Direct { bytes }) => bytes.len() as u64,
+                _ => 0,
+            };
+
+            if content.sha256.is_none() && content.purged.is_none() {
+                match content.content_data.clone() {
+                    Some(RevisionContentData::Direct { bytes }) => {
+                        content.sha256 = Some(hex::encode(Sha256::digest(&bytes)));
+                        Self::insert_revision_content(content);
                     }
-                } else {
-                    // Fallback - shouldn't happen in normal operation
-                    RevisionContentChunk {
-                        chunk_id,
-                        total_chunks: 1,
-                        data,
-                        checksum: Some(checksum),
+                    Some(RevisionContentData::Chunked {
+                        total_chunks,
+                        revision_id,
+                        content_index,
+                        ..
+                    }) => {
+                        let mut hash = migration.hash.take().unwrap_or_default();
+                        while migration.hashed_chunks < total_chunks {
+                            if bytes >= MIGRATION_BATCH_BYTES {
+                                // Continue with the remaining chunks in the next batch
+                                migration.hash = Some(hash);
+                                return false;
+                            }
+                            let Some(data) = Self::get_chunk_reference(
+                                revision_id,
+                                content_index,
+                                migration.hashed_chunks,
+                            )
+                            .and_then(|digest| Self::get_chunk_data(&digest)) else {
+                                break;
+                            };
+                            hash.update(&data);
+                            bytes += data.len() as u64;
+                            migration.hashed_chunks += 1;
+                        }
+                        if migration.hashed_chunks == total_chunks {
+                            content.sha256 = Some(hex::encode(hash.finalize()));
+                            Self::insert_revision_content(content);
+                        } else {
+                            log_warn!(
+                                "storage_migration: Skipping checksum of content with missing chunks [content_id={}, chunk_id={}]",
+                                content_id,
+                                migration.hashed_chunks
+                            );
+                        }
                     }
+                    None => {}
                 }
+            }
+            migration.cursor = content_id + 1;
+            migration.hash = None;
+            migration.hashed_chunks = 0;
+        }
+    }
+
+    /// Removes chunked contents that were never completed and whose revision is older
+    /// than `ttl`, releasing their chunks. Returns the number of removed contents.
+    pub fn remove_incomplete_contents(ttl: u64, now: u64) -> u64 {
+        let abandoned: Vec<(RevisionId, u32)> = REVISION_CONTENT_METADATA.with(|storage| {
+            storage
+                .borrow()
+                .iter()
+                .filter(|(_, metadata)| !metadata.is_complete)
+                .filter(|(_, metadata)| {
+                    Self::get_by_id(metadata.revision_id)
+                        .is_none_or(|revision| revision.created_at.saturating_add(ttl) <= now)
+                })
+                .map(|(key, _)| key)
+                .collect()
+        });
+
+        for &(revision_id, content_index) in &abandoned {
+            let chunks = Self::remove_chunked_data(revision_id, content_index);
+            log_info!(
+                "chunk_cleanup: Removed incomplete content [revision_id={}, content_index={}, chunks={}]",
+                revision_id,
+                content_index,
+                chunks
+            );
+        }
+        abandoned.len() as u64
+    }
+
+    /// Removes the chunk references and metadata of chunked content, releasing its
+    /// chunks. Returns the number of removed chunk references.
+    fn remove_chunked_data(revision_id: RevisionId, content_index: u32) -> usize {
+        let chunk_refs: Vec<((RevisionId, u32, u32), ChunkDigest)> =
+            CHUNK_REFERENCES.with(|storage| {
+                storage
+                    .borrow()
+                    .range((revision_id, content_index, 0)..=(revision_id, content_index, u32::MAX))
+                    .collect()
+            });
+        CHUNK_REFERENCES.with(|storage| {
+            let mut storage = storage.borrow_mut();
+            for (key, _) in &chunk_refs {
+                storage.remove(key);
+            }
+        });
+        for (_, digest) in &chunk_refs {
+            Self::release_chunk_data(digest);
+        }
+        REVISION_CONTENT_METADATA
+            .with(|storage| storage.borrow_mut().remove(&(revision_id, content_index)));
+        chunk_refs.len()
+    }
+
+    fn get_orphan_chunks() -> Vec<ChunkDigest> {
+        REVISION_CONTENT_CHUNKS.with(|storage| {
+            CHUNK_USAGE.with(|usage| {
+                let usage = usage.borrow();
+                storage
+                    .borrow()
+                    .keys()
+                    .filter(|digest| !usage.contains_key(digest))
+                    .collect()
             })
         })
     }
 
-    pub fn finish_revision_content_upload(
-        revision_id: RevisionId,
-        content_index: u32,
-    ) -> Result<(), String> {
-        let metadata_key = format!("{}_{}", revision_id, content_index);
-
-        // Get metadata to check if all chunks are present
-        let metadata =
-            REVISION_CONTENT_METADATA.with(|storage| storage.borrow().get(&metadata_key));
-
-        let metadata = metadata.ok_or("No metadata found for this content")?;
-
-        // Collect chunk references and verify all chunks exist
-        let mut chunk_sizes = Vec::new();
-        for chunk_id in 0..metadata.total_chunks {
-            let chunk_key = format!("{}_{}_{}", revision_id, content_index, chunk_id);
-
-            // Get checksum reference
-            let checksum = CHUNK_REFERENCES.with(|storage| storage.borrow().get(&chunk_key));
-
-            match checksum {
-                Some(checksum) => {
-                    // Verify the actual chunk data exists
-                    let data_exists = REVISION_CONTENT_CHUNKS
-                        .with(|storage| storage.borrow().contains_key(&checksum));
-                    if !data_exists {
-                        return Err(format!(
-                            "Missing chunk data for checksum {} (chunk {} for revision {} content {})",
-                            checksum, chunk_id, revision_id, content_index
-                        ));
-                    }
-
-                    // Get chunk size for total calculation
-                    let chunk_size = REVISION_CONTENT_CHUNKS.with(|storage| {
-                        storage
-                            .borrow()
-                            .get(&checksum)
-                            .map(|data| data.len())
-                            .unwrap_or(0)
-                    });
-                    chunk_sizes.push(chunk_size);
-                }
-                None => {
-                    return Err(format!(
-                        "Missing chunk reference {} for revision {} content {}",
-                        chunk_id, revision_id, content_index
-                    ))
+    /// Removes chunk data nothing refers to. Returns the number of removed chunks and bytes.
+    pub fn remove_orphan_chunks() -> (u64, u64) {
+        let orphans = Self::get_orphan_chunks();
+        REVISION_CONTENT_CHUNKS.with(|storage| {
+            let mut storage = storage.borrow_mut();
+            orphans.iter().fold((0, 0), |(count, bytes), digest| {
+                match storage.remove(digest) {
+                    Some(data) => (count + 1, bytes + data.len() as u64),
+                    None => (count, bytes),
                 }
+            })
+        })
+    }
+
+    /// Fills in the chunk store figures of a storage report.
+    pub fn fill_storage_report(report: &mut StorageReport) {
+        CHUNK_USAGE.with(|usage| {
+            for entry in usage.borrow().values() {
+                report.chunk_count += 1;
+                report.bytes_stored += entry.size;
+                report.bytes_referenced += entry.size * entry.references;
             }
+        });
+        report.bytes_saved_by_dedup = report.bytes_referenced - report.bytes_stored;
+        for digest in Self::get_orphan_chunks() {
+            report.chunk_count += 1;
+            report.orphan_count += 1;
+            let size = Self::get_chunk_data(&digest)
+                .map(|data| data.len() as u64)
+                .unwrap_or_default();
+            report.orphan_bytes += size;
+            report.bytes_stored += size;
         }
+    }
 
-        // Calculate total size from chunk sizes (no need to assemble - downloads use chunks directly)
-        let content_size: u64 = chunk_sizes.iter().sum::<usize>() as u64;
+    fn get_chunk_size(digest: &ChunkDigest) -> Option<u64> {
+        CHUNK_USAGE
+            .with(|usage| usage.borrow().get(digest))
+            .map(|entry| entry.size)
+            .or_else(|| Self::get_chunk_data(digest).map(|data| data.len() as u64))
+    }
 
-        // Create RevisionContent entity for chunked content
-        let content_id = Self::get_next_content_id();
-        let revision_content = RevisionContent {
-            id: content_id,
-            file_name: metadata.file_name.clone(),
-            content_type: metadata.content_type.clone(),
-            content_data: Some(RevisionContentData::Chunked {
-                total_size: content_size,
-                total_chunks: metadata.total_chunks,
+    /// Size in bytes of a content item.
+    pub fn get_content_size(content: &RevisionContent) -> u64 {
+        match &content.content_data {
+            Some(RevisionContentData::Direct { bytes }) => bytes.len() as u64,
+            Some(RevisionContentData::Chunked { total_size, .. }) => *total_size,
+            None => 0,
+        }
+    }
+
+    /// Bytes `start..end` of a content item, reading only the chunks that overlap the
+    /// range. None if a chunk is missing.
+    pub fn read_content_range(content: &RevisionContent, start: u64, end: u64) -> Option<Vec<u8>> {
+        match content.content_data.as_ref()? {
+            RevisionContentData::Direct { bytes } => {
+                let end = (end as usize).min(bytes.len());
+                let start = (start as usize).min(end);
+                Some(bytes[start..end].to_vec())
+            }
+            RevisionContentData::Chunked {
+                total_chunks,
                 revision_id,
                 content_index,
-            }),
-        };
+                ..
+            } => {
+                let mut bytes = Vec::with_capacity(end.saturating_sub(start) as usize);
+                let mut chunk_start = 0u64;
+                for chunk_id in 0..*total_chunks {
+                    if chunk_start >= end {
+                        break;
+                    }
+                    let digest = Self::get_chunk_reference(*revision_id, *content_index, chunk_id)?;
+                    let chunk_end = chunk_start + Self::get_chunk_size(&digest)?;
+                    if chunk_end > start {
+                        let data = Self::get_chunk_data(&digest)?;
+                        let from = start.saturating_sub(chunk_start) as usize;
+                        let to = (end.min(chunk_end) - chunk_start) as usize;
+                        bytes.extend_from_slice(data.get(from..to)?);
+                    }
+                    chunk_start = chunk_end;
+                }
+                Some(bytes)
+            }
+        }
+    }
 
-        // Store the RevisionContent entity
-        Self::insert_revision_content(revision_content);
+    pub fn get_chunk_data(digest: &ChunkDigest) -> Option<Vec<u8>> {
+        REVISION_CONTENT_CHUNKS
+            .with(|storage| storage.borrow().get(digest))
+            // Not moved by the storage migration yet
+            .or_else(|| {
+                LEGACY_REVISION_CONTENT_CHUNKS
+                    .with(|storage| storage.borrow().get(&hex::encode(digest)))
+            })
+    }
 
-        // Update the revision to include this content ID
-        REVISIONS.with(|revisions| {
-            let mut revisions_mut = revisions.borrow_mut();
-            if let Some(mut revision) = revisions_mut.get(&revision_id) {
-                revision.contents.push(content_id);
-                revisions_mut.insert(revision_id, revision);
+    /// Stores a finished upload as chunked content at `content_index` of a revision.
+    /// `digests` are the stored chunks in order.
+    pub fn insert_uploaded_content(
+        session: &UploadSession,
+        content_id: RevisionContentId,
+        revision_id: RevisionId,
+        content_index: u32,
+        digests: Vec<ChunkDigest>,
+    ) -> RevisionContentId {
+        for digest in &digests {
+            Self::retain_chunk(digest, None);
+        }
+        CHUNK_REFERENCES.with(|storage| {
+            let mut storage = storage.borrow_mut();
+            for (chunk_id, digest) in digests.into_iter().enumerate() {
+                storage.insert((revision_id, content_index, chunk_id as u32), digest);
             }
         });
-
-        // Update metadata to mark as complete
-        let updated_metadata = RevisionContentMetadata {
-            revision_id,
-            content_index,
-            total_chunks: metadata.total_chunks,
-            total_size: content_size,
-            content_type: metadata.content_type.clone(),
-            file_name: metadata.file_name.clone(),
-            is_complete: true,
-        };
-
         REVISION_CONTENT_METADATA.with(|storage| {
-            storage.borrow_mut().insert(metadata_key, updated_metadata);
+            storage.borrow_mut().insert(
+                (revision_id, content_index),
+                RevisionContentMetadata {
+                    revision_id,
+                    content_index,
+                    total_chunks: session.total_chunks,
+                    total_size: session.total_size,
+                    content_type: session.content_type.clone(),
+                    file_name: session.file_name.clone(),
+                    is_complete: true,
+                },
+            );
         });
+        Self::insert_revision_content(RevisionContent {
+            id: content_id,
+            file_name: session.file_name.clone(),
+            content_type: session.content_type.clone(),
+            mime_type: session.mime_type.clone(),
+            encryption: session.encryption.clone(),
+            content_data: Some(RevisionContentData::Chunked {
+                total_size: session.total_size,
+                total_chunks: session.total_chunks,
+                revision_id,
+                content_index,
+            }),
+            purged: None,
+            // Verified against the uploaded chunks by `finish_upload`
+            sha256: Some(session.expected_sha256.clone()),
+        })
+    }
 
-        // Keep chunk pieces for chunked content downloads
-        // No cleanup needed as chunks will be used for individual downloads
+    fn get_chunk_reference(
+        revision_id: RevisionId,
+        content_index: u32,
+        chunk_id: u32,
+    ) -> Option<ChunkDigest> {
+        CHUNK_REFERENCES
+            .with(|storage| {
+                storage
+                    .borrow()
+                    .get(&(revision_id, content_index, chunk_id))
+            })
+            // Not moved by the storage migration yet
+            .or_else(|| {
+                let key = format!("{}_{}_{}", revision_id, content_index, chunk_id);
+                LEGACY_CHUNK_REFERENCES
+                    .with(|storage| storage.borrow().get(&key))
+                    .and_then(|checksum| Self::parse_digest(&checksum))
+            })
+    }
 
-        Ok(())
+    fn get_content_metadata(
+        revision_id: RevisionId,
+        content_index: u32,
+    ) -> Option<RevisionContentMetadata> {
+        REVISION_CONTENT_METADATA
+            .with(|storage| storage.borrow().get(&(revision_id, content_index)))
+            // Not moved by the storage migration yet
+            .or_else(|| {
+                let key = format!("{}_{}", revision_id, content_index);
+                LEGACY_REVISION_CONTENT_METADATA.with(|storage| storage.borrow().get(&key))
+            })
+    }
+
+    pub fn get_revision_content_chunk(
+        revision_id: RevisionId,
+        content_index: u32,
+        chunk_id: u32,
+    ) -> Option<RevisionContentChunk> {
+        // First get the digest reference for this chunk
+        let digest = Self::get_chunk_reference(revision_id, content_index, chunk_id)?;
+
+        // Then get the actual data using the digest
+        let data = Self::get_chunk_data(&digest)?;
+        let total_chunks = Self::get_content_metadata(revision_id, content_index)
+            .map(|meta| meta.total_chunks)
+            // Fallback - shouldn't happen in normal operation
+            .unwrap_or(1);
+        Some(RevisionContentChunk {
+            chunk_id,
+            total_chunks,
+            data,
+            checksum: Some(hex::encode(digest)),
+        })
     }
 
     // Helper function to create RevisionContent with proper ID and file name (for direct/small content)
@@ -365,17 +1173,201 @@ impl RevisionsManager {
         bytes: Vec<u8>,
         content_type: RevisionContentType,
         file_name: Option<String>,
+        mime_type: Option<String>,
+        encryption: Option<ContentEncryption>,
     ) -> RevisionContent {
         RevisionContent {
             id: Self::get_next_content_id(),
             file_name,
             content_type,
+            mime_type,
+            encryption,
+            sha256: Some(hex::encode(Sha256::digest(&bytes))),
             content_data: Some(RevisionContentData::Direct { bytes }),
+            purged: None,
+        }
+    }
+
+    pub fn get_content_type_policy_value() -> ContentTypePolicy {
+        CONTENT_TYPE_POLICY.with(|cell| cell.borrow().get().clone())
+    }
+
+    /// Checks a declared MIME type against the tenant policy and the declared content
+    /// kind. Returns the normalized MIME type.
+    pub fn validate_mime_type(
+        content_type: &RevisionContentType,
+        mime_type: &str,
+    ) -> Result<String, AppError> {
+        let mime_type = normalize_mime(mime_type);
+        if !mime_type.contains('/') {
+            return Err(AppError::ValidationError(format!(
+                "'{}' is not a valid MIME type",
+                mime_type
+            )));
+        }
+        if !is_mime_allowed(
+            &mime_type,
+            &Self::get_content_type_policy_value().allowed_mime_types,
+        ) {
+            return Err(AppError::ValidationError(format!(
+                "Content of type {} is not allowed",
+                mime_type
+            )));
+        }
+        if !is_kind_compatible(content_type, &mime_type) {
+            return Err(AppError::ValidationError(format!(
+                "Content of type {} cannot be stored as {:?}",
+                mime_type, content_type
+            )));
+        }
+        Ok(mime_type)
+    }
+
+    /// Verifies the leading bytes of a content item against its declared MIME type.
+    pub fn verify_mime_type(mime_type: &str, sample: &[u8]) -> Result<(), AppError> {
+        if matches_declared_mime(mime_type, sample) {
+            Ok(())
+        } else {
+            log_warn!(
+                "content_validation: Content does not match declared MIME type [mime_type={}, sniffed={:?}]",
+                mime_type,
+                sniff_mime(sample)
+            );
+            Err(AppError::ValidationError(format!(
+                "Content does not match its declared type {}",
+                mime_type
+            )))
+        }
+    }
+
+    /// Validates the declared MIME type of new direct content and returns it normalized.
+    /// Encrypted content cannot be checked against its declared type.
+    pub fn validate_direct_content(
+        content: &RevisionContent,
+        bytes: &[u8],
+    ) -> Result<String, AppError> {
+        if bytes.len() > MAX_DOCUMENT_SIZE as usize {
+            return Err(AppError::ValidationError(format!(
+                "Content of {} bytes exceeds the maximum of {} bytes, larger content must be uploaded in chunks",
+                bytes.len(),
+                MAX_DOCUMENT_SIZE
+            )));
+        }
+        let mime_type = content.mime_type.as_deref().ok_or_else(|| {
+            AppError::ValidationError("New content must declare its MIME type".to_string())
+        })?;
+        let mime_type = Self::validate_mime_type(&content.content_type, mime_type)?;
+        if content.encryption.is_none() {
+            Self::verify_mime_type(&mime_type, bytes)?;
+        }
+        Ok(mime_type)
+    }
+
+    pub fn get_content_type_policy() -> GetContentTypePolicyResult {
+        let principal = ic_cdk::api::msg_caller();
+        match UserManager::get_user_by_principal(principal) {
+            GetUserResult::Ok(_) => {
+                GetContentTypePolicyResult::Ok(Self::get_content_type_policy_value())
+            }
+            GetUserResult::Err(e) => {
+                log_warn!(
+                    "auth_check: Authentication failed for content type policy [principal={}] - {:?}",
+                    principal,
+                    e
+                );
+                GetContentTypePolicyResult::Err(e)
+            }
+        }
+    }
+
+    pub fn set_content_type_policy(input: SetContentTypePolicyInput) -> SetContentTypePolicyResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Content type policy update attempt [principal={}, allowed_mime_types={:?}]",
+            principal,
+            input.allowed_mime_types
+        );
+
+        let user = match UserManager::get_user_by_principal(principal) {
+            GetUserResult::Ok(u) => u,
+            GetUserResult::Err(e) => {
+                log_warn!(
+                    "auth_check: Authentication failed for content type policy update [principal={}] - {:?}",
+                    principal,
+                    e
+                );
+                return SetContentTypePolicyResult::Err(e);
+            }
+        };
+        if !AccessControlManager::has_organization_permission(
+            user.id,
+            &Permission::Organization(OrganizationPermission::ConfigureSettings),
+        ) {
+            log_warn!(
+                "access_control: Permission denied for content type policy update [user_id={}]",
+                user.id
+            );
+            return SetContentTypePolicyResult::Err(AppError::Unauthorized);
         }
+        if input.allowed_mime_types.is_empty()
+            || input.allowed_mime_types.len() > MAX_ALLOWED_MIME_TYPES
+        {
+            log_warn!(
+                "content_validation: Invalid number of allowed MIME types [count={}, principal={}]",
+                input.allowed_mime_types.len(),
+                principal
+            );
+            return SetContentTypePolicyResult::Err(AppError::ValidationError(format!(
+                "A policy must allow between 1 and {} MIME types",
+                MAX_ALLOWED_MIME_TYPES
+            )));
+        }
+        let mut allowed_mime_types = Vec::new();
+        for mime_type in &input.allowed_mime_types {
+            let mime_type = normalize_mime(mime_type);
+            let is_valid = mime_type
+                .split_once('/')
+                .is_some_and(|(top_level, sub_type)| !top_level.is_empty() && !sub_type.is_empty());
+            if !is_valid {
+                log_warn!(
+                    "content_validation: Invalid MIME type in policy [mime_type='{}', principal={}]",
+                    mime_type,
+                    principal
+                );
+                return SetContentTypePolicyResult::Err(AppError::ValidationError(format!(
+                    "'{}' is not a valid MIME type",
+                    mime_type
+                )));
+            }
+            if !allowed_mime_types.contains(&mime_type) {
+                allowed_mime_types.push(mime_type);
+            }
+        }
+        CONTENT_TYPE_POLICY.with(|cell| {
+            cell.borrow_mut()
+                .set(ContentTypePolicy {
+                    allowed_mime_types: allowed_mime_types.clone(),
+                })
+                .expect("Failed to set content type policy in stable memory");
+        });
+        log_info!(
+            "content_validation: Content type policy updated [allowed_mime_types={:?}, user_id={}, principal={}]",
+            allowed_mime_types,
+            user.id,
+            principal
+        );
+        SetContentTypePolicyResult::Ok
     }
 
     // Business logic methods
     pub fn create_revision(input: CreateRevisionInput) -> CreateRevisionResult {
+        Self::create_revision_with_rollback(input, None)
+    }
+
+    fn create_revision_with_rollback(
+        input: CreateRevisionInput,
+        rollback: Option<RevisionRollback>,
+    ) -> CreateRevisionResult {
         let caller = ic_cdk::api::msg_caller();
         log_debug!(
             "auth_check: Revision creation attempt [principal={}, document_id={}, project_id={}]",
@@ -424,63 +1416,107 @@ impl RevisionsManager {
                         );
                         return CreateRevisionResult::Err(e);
                     }
-                };
+                };
+
+                // Rollbacks are checked against the rollback permission by `rollback_document`
+                let permission = match rollback {
+                    Some(_) => RevisionPermission::Rollback,
+                    None => RevisionPermission::Create,
+                };
+                if !DocumentManager::has_permission(
+                    user.id,
+                    &document,
+                    &Permission::Revision(permission),
+                ) {
+                    log_warn!(
+                        "access_control: Permission denied for revision creation [user_id={}, document_id={}]",
+                        user.id,
+                        document.id
+                    );
+                    return CreateRevisionResult::Err(AppError::Unauthorized);
+                }
+
+                // Validate the contents before storing anything
+                for content in &input.contents {
+                    let validation = match &content.content_data {
+                        Some(RevisionContentData::Chunked { .. }) => {
+                            Err(AppError::ValidationError(
+                                "Chunked content must be uploaded with an upload session and referenced by its content ID".to_string(),
+                            ))
+                        }
+                        Some(RevisionContentData::Direct { bytes }) => {
+                            Self::validate_direct_content(content, bytes).map(|_| ())
+                        }
+                        None => Self::authorize_content_reference(
+                            user.id,
+                            input.document_id,
+                            content.id,
+                        ),
+                    }
+                    .and_then(|_| Self::validate_content_encryption(&document, content));
+                    if let Err(e) = validation {
+                        log_warn!(
+                            "revision_creation: Invalid content [content_id={}, document_id={}, principal={}] - {:?}",
+                            content.id,
+                            input.document_id,
+                            caller,
+                            e
+                        );
+                        return CreateRevisionResult::Err(e);
+                    }
+                }
+
+                let direct_bytes: u64 = input
+                    .contents
+                    .iter()
+                    .map(|content| match &content.content_data {
+                        Some(RevisionContentData::Direct { bytes }) => bytes.len() as u64,
+                        _ => 0,
+                    })
+                    .sum();
+                if let Err(e) = QuotasManager::check_quota(direct_bytes) {
+                    return CreateRevisionResult::Err(e);
+                }
 
                 // Store content separately and collect content IDs
+                let mut stored_bytes = 0u64;
                 let content_ids: Vec<u64> = input
                     .contents
                     .iter()
-                    .map(|content| {
-                        match &content.content_data {
-                            Some(content_data) => {
-                                // New content - create and store it
-                                let bytes = match content_data {
-                                    shared::types::revisions::RevisionContentData::Direct { bytes } => {
-                                        bytes.clone()
-                                    }
-                                    shared::types::revisions::RevisionContentData::Chunked { .. } => {
-                                        // This should not happen in normal create_revision flow
-                                        // as chunked content should be uploaded separately
-                                        vec![]
-                                    }
-                                };
-                                let revision_content = Self::create_revision_content_with_metadata(
-                                    bytes,
-                                    content.content_type.clone(),
-                                    content.file_name.clone(),
-                                );
-                                Self::insert_revision_content(revision_content)
-                            }
-                            None => {
-                                // Reference to existing content - validate access and reuse ID
-                                // SECURITY: Must validate that the user has access to this content
-                                // TODO: Implement proper access control validation
-                                log_debug!(
-                                    "revision_creation: Referencing existing content [content_id={}, principal={}]",
-                                    content.id,
-                                    caller
-                                );
-
-                                // For now, just verify the content exists (minimal validation)
-                                // This should be enhanced with proper access control checks
-                                match Self::get_revision_content_by_id(content.id) {
-                                    Some(_) => content.id,
-                                    None => {
-                                        log_warn!(
-                                            "revision_creation: Referenced content not found [content_id={}, principal={}]",
-                                            content.id,
-                                            caller
-                                        );
-                                        // Create empty content as fallback - this should be an error in production
-                                        let revision_content = Self::create_revision_content_with_metadata(
-                                            vec![],
-                                            content.content_type.clone(),
-                                            content.file_name.clone(),
-                                        );
-                                        Self::insert_revision_content(revision_content)
-                                    }
-                                }
-                            }
+                    .enumerate()
+                    .map(|(content_index, content)| match &content.content_data {
+                        Some(RevisionContentData::Direct { bytes }) => {
+                            // New content - create and store it
+                            stored_bytes += bytes.len() as u64;
+                            let revision_content = Self::create_revision_content_with_metadata(
+                                bytes.clone(),
+                                content.content_type.clone(),
+                                content.file_name.clone(),
+                                content.mime_type.as_deref().map(normalize_mime),
+                                content.encryption.clone(),
+                            );
+                            Self::insert_revision_content(revision_content)
+                        }
+                        _ if Self::get_revision_content_by_id(content.id).is_some() => {
+                            // Reference to existing content, validated above
+                            log_debug!(
+                                "revision_creation: Referencing existing content [content_id={}, principal={}]",
+                                content.id,
+                                caller
+                            );
+                            content.id
+                        }
+                        _ => {
+                            // Finished upload, validated above
+                            let content_id = UploadsManager::attach_upload(
+                                content.id,
+                                new_revision_id,
+                                content_index as u32,
+                            );
+                            stored_bytes += Self::get_revision_content_by_id(content_id)
+                                .map(|content| Self::get_content_size(&content))
+                                .unwrap_or_default();
+                            content_id
                         }
                     })
                     .collect();
@@ -493,6 +1529,12 @@ impl RevisionsManager {
                     contents: content_ids,
                     created_at: ic_cdk::api::time(),
                     created_by: user.id,
+                    status: Some(RevisionStatus::Draft),
+                    rollback,
+                    imported: None,
+                    released_at: None,
+                    retired_at: None,
+                    purged_at: None,
                 };
 
                 log_debug!(
@@ -503,7 +1545,20 @@ impl RevisionsManager {
                 );
 
                 Self::insert(new_revision_id, new_revision.clone());
+                QuotasManager::record_usage(input.project_id, stored_bytes);
                 documents::update_revision(input.document_id, version, new_revision_id);
+                SearchManager::index_revision(&new_revision);
+                PreviewsManager::cache_revision_previews(&new_revision);
+                NotificationsManager::notify_document(
+                    &document,
+                    user.id,
+                    NotificationEvent::RevisionCreated {
+                        document_id: new_revision.document_id,
+                        revision_id: new_revision.id,
+                        version: new_revision.version,
+                    },
+                    &[],
+                );
 
                 log_info!(
                     "revision_creation: Successfully created revision [id={}, document_id={}, version={}, user_id={}, principal={}, project_id={}, timestamp={}]",
@@ -542,6 +1597,140 @@ impl RevisionsManager {
         }
     }
 
+    /// Creates a new revision that references the contents of an earlier revision of
+    /// the document. Existing revisions are left untouched.
+    pub fn rollback_document(input: RollbackDocumentInput) -> RollbackDocumentResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Document rollback attempt [principal={}, document_id={}, target_revision_id={}]",
+            principal,
+            input.document_id,
+            input.target_revision_id
+        );
+
+        let user = match UserManager::get_user_by_principal(principal) {
+            GetUserResult::Ok(user) => user,
+            GetUserResult::Err(e) => {
+                log_warn!(
+                    "auth_check: Authentication failed for document rollback [principal={}] - {:?}",
+                    principal,
+                    e
+                );
+                return RollbackDocumentResult::Err(e);
+            }
+        };
+        let document = match DocumentManager::get_by_id(input.document_id) {
+            Some(document) => document,
+            None => {
+                log_warn!(
+                    "revision_rollback: Document not found [document_id={}, principal={}]",
+                    input.document_id,
+                    principal
+                );
+                return RollbackDocumentResult::Err(AppError::EntityNotFound(
+                    "Document not found".to_string(),
+                ));
+            }
+        };
+        let rollback_permission = Permission::Revision(RevisionPermission::Rollback);
+        if !DocumentManager::has_permission(user.id, &document, &rollback_permission) {
+            log_warn!(
+                "access_control: Permission denied for document rollback [user_id={}, document_id={}]",
+                user.id,
+                document.id
+            );
+            return RollbackDocumentResult::Err(AppError::Unauthorized);
+        }
+
+        let reason = input.reason.trim();
+        if reason.is_empty() {
+            log_warn!(
+                "revision_rollback: Rollback reason is missing [document_id={}, principal={}]",
+                document.id,
+                principal
+            );
+            return RollbackDocumentResult::Err(AppError::ValidationError(
+                "A rollback reason is required".to_string(),
+            ));
+        }
+        if reason.chars().count() > MAX_ROLLBACK_REASON_LENGTH {
+            log_warn!(
+                "revision_rollback: Rollback reason too long [document_id={}, length={}, principal={}]",
+                document.id,
+                reason.chars().count(),
+                principal
+            );
+            return RollbackDocumentResult::Err(AppError::ValidationError(format!(
+                "Rollback reason exceeds the maximum length of {} characters",
+                MAX_ROLLBACK_REASON_LENGTH
+            )));
+        }
+
+        let target = match Self::get_by_id(input.target_revision_id)
+            .filter(|revision| revision.document_id == document.id)
+        {
+            Some(target) => target,
+            None => {
+                log_warn!(
+                    "revision_rollback: Target revision not found for document [document_id={}, target_revision_id={}, principal={}]",
+                    document.id,
+                    input.target_revision_id,
+                    principal
+                );
+                return RollbackDocumentResult::Err(AppError::EntityNotFound(
+                    "Revision not found for this document".to_string(),
+                ));
+            }
+        };
+        if document.revisions.last() == Some(&target.id) {
+            log_warn!(
+                "revision_rollback: Target revision is already current [document_id={}, target_revision_id={}, principal={}]",
+                document.id,
+                target.id,
+                principal
+            );
+            return RollbackDocumentResult::Err(AppError::InvalidStateTransition(
+                "Target revision is already the current revision".to_string(),
+            ));
+        }
+
+        let contents: Vec<RevisionContent> = target
+            .contents
+            .iter()
+            .filter_map(|content_id| Self::get_revision_content_by_id(*content_id))
+            .map(|content| RevisionContent {
+                content_data: None,
+                ..content
+            })
+            .collect();
+        let create_input = CreateRevisionInput {
+            project_id: document.project_id,
+            document_id: document.id,
+            contents,
+        };
+        let rollback = RevisionRollback {
+            target_revision_id: target.id,
+            target_version: target.version,
+            reason: reason.to_string(),
+        };
+
+        let target_version = rollback.target_version;
+        match Self::create_revision_with_rollback(create_input, Some(rollback)) {
+            CreateRevisionResult::Ok(revision_id) => {
+                log_info!(
+                    "revision_rollback: Rolled back document [document_id={}, target_revision_id={}, target_version={}, new_revision_id={}, principal={}]",
+                    input.document_id,
+                    input.target_revision_id,
+                    target_version,
+                    revision_id,
+                    principal
+                );
+                RollbackDocumentResult::Ok(revision_id)
+            }
+            CreateRevisionResult::Err(e) => RollbackDocumentResult::Err(e),
+        }
+    }
+
     pub fn list_revisions(input: ListRevisionsInput) -> ListRevisionsResult {
         let principal = ic_cdk::api::msg_caller();
         log_debug!(
@@ -614,6 +1803,18 @@ impl RevisionsManager {
             input.updated
         );
 
+        let user = match UserManager::get_user_by_principal(principal) {
+            GetUserResult::Ok(u) => u,
+            GetUserResult::Err(e) => {
+                log_warn!(
+                    "auth_check: Authentication failed for revision diff [principal={}] - {:?}",
+                    principal,
+                    e
+                );
+                return DiffRevisionsResult::Err(e);
+            }
+        };
+
         log_debug!(
             "revision_diff: Validating start revision [revision_id={}]",
             input.original
@@ -707,6 +1908,19 @@ impl RevisionsManager {
             }
         };
 
+        if !DocumentManager::has_permission(
+            user.id,
+            &document,
+            &Permission::Document(DocumentPermission::Read),
+        ) {
+            log_warn!(
+                "access_control: Permission denied for revision diff [user_id={}, document_id={}]",
+                user.id,
+                document_id
+            );
+            return DiffRevisionsResult::Err(AppError::Unauthorized);
+        }
+
         let start_index = match document
             .revisions
             .iter()
@@ -767,58 +1981,25 @@ impl RevisionsManager {
         );
 
         let revisions = Self::get_revision_range(document_id, start_index, end_index);
+        let contents = Self::build_content_diffs(&start_revision, &end_revision);
 
         log_info!(
-            "revision_diff: Successfully generated diff [document_id={}, start_revision={}, end_revision={}, revision_count={}, principal={}]",
+            "revision_diff: Successfully generated diff [document_id={}, start_revision={}, end_revision={}, revision_count={}, content_count={}, principal={}]",
             document_id,
             input.original,
             input.updated,
             revisions.len(),
+            contents.len(),
             principal
         );
 
-        DiffRevisionsResult::Ok(revisions)
-    }
-
-    pub fn store_revision_content_chunk_operation(
-        input: StoreRevisionContentChunkInput,
-    ) -> StoreRevisionContentChunkResult {
-        let principal = ic_cdk::api::msg_caller();
-        log_debug!(
-            "chunk_upload: Storing content chunk [principal={}, revision_id={}, content_index={}, chunk_size={}]",
-            principal,
-            input.revision_id,
-            input.content_index,
-            input.chunk.data.len()
-        );
-
-        match Self::store_revision_content_chunk(
-            input.revision_id,
-            input.content_index,
-            input.chunk,
-            input.content_type,
-            input.file_name,
-        ) {
-            Ok(_) => {
-                log_debug!(
-                    "chunk_upload: Successfully stored chunk [revision_id={}, content_index={}, principal={}]",
-                    input.revision_id,
-                    input.content_index,
-                    principal
-                );
-                StoreRevisionContentChunkResult::Ok(())
-            }
-            Err(e) => {
-                log_error!(
-                    "chunk_upload: Failed to store chunk [revision_id={}, content_index={}, principal={}] - {}",
-                    input.revision_id,
-                    input.content_index,
-                    principal,
-                    e
-                );
-                StoreRevisionContentChunkResult::Err(AppError::InternalError(e))
-            }
-        }
+        DiffRevisionsResult::Ok(Box::new(RevisionDiff {
+            document_id,
+            original: start_revision,
+            updated: end_revision,
+            revisions,
+            contents,
+        }))
     }
 
     pub fn get_revision_content_chunk_operation(
@@ -862,40 +2043,6 @@ impl RevisionsManager {
         }
     }
 
-    pub fn finish_revision_content_upload_operation(
-        input: FinishRevisionContentUploadInput,
-    ) -> FinishRevisionContentUploadResult {
-        let principal = ic_cdk::api::msg_caller();
-        log_debug!(
-            "chunk_upload: Finalizing content upload [principal={}, revision_id={}, content_index={}]",
-            principal,
-            input.revision_id,
-            input.content_index
-        );
-
-        match Self::finish_revision_content_upload(input.revision_id, input.content_index) {
-            Ok(_) => {
-                log_info!(
-                    "chunk_upload: Successfully finalized content upload [revision_id={}, content_index={}, principal={}]",
-                    input.revision_id,
-                    input.content_index,
-                    principal
-                );
-                FinishRevisionContentUploadResult::Ok(())
-            }
-            Err(e) => {
-                log_error!(
-                    "chunk_upload: Failed to finalize content upload [revision_id={}, content_index={}, principal={}] - {}",
-                    input.revision_id,
-                    input.content_index,
-                    principal,
-                    e
-                );
-                FinishRevisionContentUploadResult::Err(AppError::InternalError(e))
-            }
-        }
-    }
-
     pub fn get_revision_content_operation(
         input: GetRevisionContentInput,
     ) -> GetRevisionContentResult {
@@ -966,6 +2113,223 @@ impl RevisionsManager {
         ListRevisionContentsResult::Ok(contents)
     }
 
+    fn purged_error() -> AppError {
+        AppError::EntityNotFound("Content was purged under the retention policy".to_string())
+    }
+
+    /// Content can be downloaded through a valid share link, or by users who can
+    /// read a document with a revision containing it.
+    fn get_revisions_containing(content_id: RevisionContentId) -> Vec<Revision> {
+        Self::get_revision_ids_containing(content_id)
+            .into_iter()
+            .filter_map(Self::get_by_id)
+            .collect()
+    }
+
+    /// Whether any of the revisions belongs to a document the user may read.
+    fn can_read_any(user_id: UserId, revisions: &[Revision]) -> bool {
+        let read = Permission::Document(DocumentPermission::Read);
+        revisions.iter().any(|revision| {
+            documents::get_by_id(revision.document_id)
+                .is_some_and(|document| DocumentManager::has_permission(user_id, &document, &read))
+        })
+    }
+
+    /// Checks the encryption of content added to a document. New content and uploads
+    /// carry the client's declaration, referenced content its stored encryption; content
+    /// of another document is encrypted with a different key.
+    fn validate_content_encryption(
+        document: &Document,
+        content: &RevisionContent,
+    ) -> Result<(), AppError> {
+        let (encryption, is_new) = match &content.content_data {
+            Some(_) => (content.encryption.clone(), true),
+            None => match Self::get_revision_content_by_id(content.id) {
+                Some(existing) => {
+                    if DocumentManager::is_encrypted(document)
+                        && !Self::get_revisions_containing(content.id)
+                            .iter()
+                            .any(|revision| revision.document_id == document.id)
+                    {
+                        return Err(AppError::ValidationError(
+                            "Encrypted documents can only reference their own content".to_string(),
+                        ));
+                    }
+                    (existing.encryption, false)
+                }
+                None => (UploadsManager::get_upload_encryption(content.id), true),
+            },
+        };
+        EncryptionManager::validate_content_encryption(document, encryption.as_ref(), is_new)
+    }
+
+    /// Content referenced by ID from a new revision must already belong to a revision
+    /// of the same document, or of a document the user may read, or be an upload the
+    /// user finished and has not attached yet.
+    fn authorize_content_reference(
+        user_id: UserId,
+        document_id: DocumentId,
+        content_id: RevisionContentId,
+    ) -> Result<(), AppError> {
+        match Self::get_revision_content_by_id(content_id) {
+            None => return UploadsManager::authorize_attachment(user_id, content_id),
+            Some(content) if content.purged.is_some() => return Err(Self::purged_error()),
+            Some(_) => {}
+        }
+        let revisions = Self::get_revisions_containing(content_id);
+        if revisions
+            .iter()
+            .any(|revision| revision.document_id == document_id)
+            || Self::can_read_any(user_id, &revisions)
+        {
+            Ok(())
+        } else {
+            Err(AppError::Unauthorized)
+        }
+    }
+
+    /// Rehashes direct content, or a bounded range of chunks of chunked content, and
+    /// compares it with the checksums recorded when it was stored.
+    pub fn verify_content(input: VerifyContentInput) -> VerifyContentResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "content_integrity: Verifying content [principal={}, content_id={}, start_chunk={:?}]",
+            principal,
+            input.content_id,
+            input.start_chunk
+        );
+
+        let user = match UserManager::get_user_by_principal(principal) {
+            GetUserResult::Ok(u) => u,
+            GetUserResult::Err(e) => {
+                log_warn!(
+                    "auth_check: Authentication failed for content verification [principal={}, content_id={}] - {:?}",
+                    principal,
+                    input.content_id,
+                    e
+                );
+                return VerifyContentResult::Err(e);
+            }
+        };
+        let content = match Self::get_revision_content_by_id(input.content_id) {
+            Some(content) => content,
+            None => {
+                log_warn!(
+                    "content_integrity: Content not found [content_id={}, principal={}]",
+                    input.content_id,
+                    principal
+                );
+                return VerifyContentResult::Err(AppError::EntityNotFound(
+                    "Content not found".to_string(),
+                ));
+            }
+        };
+        if !Self::can_read_any(user.id, &Self::get_revisions_containing(content.id)) {
+            log_warn!(
+                "access_control: Permission denied for content verification [user_id={}, content_id={}]",
+                user.id,
+                content.id
+            );
+            return VerifyContentResult::Err(AppError::Unauthorized);
+        }
+
+        let recorded = Self::get_recorded_checksum(&content);
+        let sha256 = recorded.as_ref().map(|(sha256, _)| sha256.clone());
+        let size = recorded
+            .as_ref()
+            .map_or_else(|| Self::get_content_size(&content), |(_, size)| *size);
+        let mut chunks = Vec::new();
+        let mut next_chunk = None;
+        let is_valid = match &content.content_data {
+            Some(RevisionContentData::Direct { bytes }) => sha256
+                .as_ref()
+                .is_none_or(|sha256| hex::encode(Self::calculate_digest(bytes)) == *sha256),
+            Some(RevisionContentData::Chunked {
+                total_chunks,
+                revision_id,
+                content_index,
+                ..
+            }) => {
+                let start = input.start_chunk.unwrap_or(0).min(*total_chunks);
+                let count = input
+                    .chunk_count
+                    .unwrap_or(MAX_VERIFY_CHUNKS)
+                    .min(MAX_VERIFY_CHUNKS);
+                let end = start.saturating_add(count).min(*total_chunks);
+                for chunk_id in start..end {
+                    let digest = Self::get_chunk_reference(*revision_id, *content_index, chunk_id);
+                    let data = digest.as_ref().and_then(Self::get_chunk_data);
+                    chunks.push(match (digest, data) {
+                        (Some(digest), Some(data)) => ChunkVerification {
+                            chunk_id,
+                            is_valid: Self::calculate_digest(&data) == digest,
+                            checksum: hex::encode(digest),
+                            size: data.len() as u64,
+                        },
+                        (digest, _) => ChunkVerification {
+                            chunk_id,
+                            checksum: digest.map(hex::encode).unwrap_or_default(),
+                            size: 0,
+                            is_valid: false,
+                        },
+                    });
+                }
+                next_chunk = (end < *total_chunks).then_some(end);
+                chunks.iter().all(|chunk| chunk.is_valid)
+            }
+            // Purged content has no data left to check
+            None => true,
+        };
+        if !is_valid {
+            log_error!(
+                "content_integrity: Integrity check failed [content_id={}, sha256={:?}, invalid_chunks={}]",
+                content.id,
+                sha256,
+                chunks.iter().filter(|chunk| !chunk.is_valid).count()
+            );
+        }
+        VerifyContentResult::Ok(ContentVerification {
+            content_id: content.id,
+            sha256,
+            size,
+            chunks,
+            next_chunk,
+            is_valid,
+        })
+    }
+
+    /// Checks that the caller may download a content item, either through a share link
+    /// or as a user that can read one of the revisions containing it.
+    pub fn authorize_content_download(
+        content_id: RevisionContentId,
+        share_token: Option<&str>,
+        principal: candid::Principal,
+    ) -> Result<(), AppError> {
+        if Self::get_revision_content_by_id(content_id)
+            .is_some_and(|content| content.purged.is_some())
+        {
+            return Err(Self::purged_error());
+        }
+        if let Some(token) = share_token {
+            return ShareLinksManager::authorize_content(token, content_id);
+        }
+
+        let user = match UserManager::get_user_by_principal(principal) {
+            GetUserResult::Ok(u) => u,
+            GetUserResult::Err(e) => return Err(e),
+        };
+        let revisions = Self::get_revisions_containing(content_id);
+        if revisions.is_empty() {
+            return Err(AppError::EntityNotFound("Content not found".to_string()));
+        }
+
+        if Self::can_read_any(user.id, &revisions) {
+            Ok(())
+        } else {
+            Err(AppError::Unauthorized)
+        }
+    }
+
     pub fn download_revision_content_operation(
         input: DownloadRevisionContentInput,
     ) -> DownloadRevisionContentResult {
@@ -977,6 +2341,20 @@ impl RevisionsManager {
             input.chunk_id
         );
 
+        if let Err(e) = Self::authorize_content_download(
+            input.content_id,
+            input.share_token.as_deref(),
+            principal,
+        ) {
+            log_warn!(
+                "content_download: Access denied [content_id={}, principal={}] - {:?}",
+                input.content_id,
+                principal,
+                e
+            );
+            return DownloadRevisionContentResult::Err(e);
+        }
+
         match Self::get_revision_content_by_id(input.content_id) {
             Some(content) => match &content.content_data {
                 Some(content_data) => match content_data {
@@ -1085,4 +2463,189 @@ impl RevisionsManager {
             }
         }
     }
+
+    /// Retention period in years of a retired revision whose period has ended, None
+    /// while the revision is retained or its document is under legal hold.
+    fn expired_retention(
+        policy: &RetentionPolicy,
+        document: &Document,
+        revision: &Revision,
+        now: u64,
+    ) -> Option<u32> {
+        if !matches!(
+            revision.status,
+            Some(RevisionStatus::Superseded | RevisionStatus::Obsolete)
+        ) {
+            return None;
+        }
+        let retired_at = revision.retired_at?;
+        let retention_years = RetentionManager::retention_years(policy, document)?;
+        let expired = add_months(retired_at, retention_years.saturating_mul(12)) <= now;
+        (expired && !RetentionManager::is_on_hold(document)).then_some(retention_years)
+    }
+
+    /// Purges the content of a revision whose retention period ended. Data shared with
+    /// a revision that is still retained is kept; checksums and sizes stay on the
+    /// content, so the revision hash signers attested to can still be shown.
+    fn purge_revision(
+        mut revision: Revision,
+        document: &Document,
+        retention_years: u32,
+        releasable: &HashSet<RevisionId>,
+        now: u64,
+    ) -> PurgeRecord {
+        let revision_hash = Self::get_revision_hash(&revision);
+        let mut contents: Vec<PurgedContent> = Vec::new();
+        for content_id in &revision.contents {
+            if contents
+                .iter()
+                .any(|purged| purged.content_id == *content_id)
+            {
+                continue;
+            }
+            let Some(mut content) = Self::get_revision_content_by_id(*content_id) else {
+                continue;
+            };
+            let (sha256, size) = Self::get_content_checksum(&content);
+            let revision_ids = Self::get_revision_ids_containing(*content_id);
+            let removable = content.purged.is_none()
+                && !revision_ids.is_empty()
+                && revision_ids.iter().all(|id| releasable.contains(id));
+            if removable {
+                if let Some(RevisionContentData::Chunked {
+                    revision_id,
+                    content_index,
+                    ..
+                }) = &content.content_data
+                {
+                    Self::remove_chunked_data(*revision_id, *content_index);
+                }
+                content.content_data = None;
+                content.purged = Some(ContentPurge {
+                    purged_at: now,
+                    sha256: sha256.clone(),
+                    size,
+                });
+                Self::insert_revision_content(content);
+                // Usage is accounted to the project of the first revision with the content
+                let project_id = revision_ids
+                    .first()
+                    .and_then(|id| Self::get_by_id(*id))
+                    .map_or(revision.project_id, |first| first.project_id);
+                QuotasManager::release_usage(project_id, size);
+                PreviewsManager::remove_preview(*content_id);
+            }
+            contents.push(PurgedContent {
+                content_id: *content_id,
+                sha256,
+                size,
+                removed: removable,
+            });
+        }
+
+        revision.purged_at = Some(now);
+        REVISIONS.with(|revisions| revisions.borrow_mut().insert(revision.id, revision.clone()));
+        PurgeRecord {
+            id: 0,
+            document_id: revision.document_id,
+            project_id: revision.project_id,
+            revision_id: revision.id,
+            version: revision.version,
+            document_type: document.document_type.clone(),
+            retention_years,
+            retired_at: revision.retired_at.unwrap_or_default(),
+            purged_at: now,
+            revision_hash,
+            bytes_removed: contents
+                .iter()
+                .filter(|content| content.removed)
+                .map(|content| content.size)
+                .sum(),
+            contents,
+        }
+    }
+
+    /// Purges the content of retired revisions whose retention period under the
+    /// retention policy has ended, keeping their metadata and hashes. Every purge is
+    /// recorded with `RetentionManager`. Revisions of documents under legal hold are
+    /// skipped; a run purges at most `MAX_PURGES_PER_RUN` revisions.
+    pub fn enforce_retention() {
+        if Self::is_migrating() {
+            return;
+        }
+        let policy = RetentionManager::get_policy();
+        if policy.rules.is_empty() && policy.default_retention_years.is_none() {
+            return;
+        }
+        let now = ic_cdk::api::time();
+
+        let mut documents: HashMap<DocumentId, Option<Document>> = HashMap::new();
+        // Revisions whose content may go: purged already, or purged by this run
+        let mut releasable = HashSet::new();
+        let mut due = Vec::new();
+        for revision in Self::get_all() {
+            if revision.purged_at.is_some() {
+                releasable.insert(revision.id);
+                continue;
+            }
+            let document = documents
+                .entry(revision.document_id)
+                .or_insert_with(|| DocumentManager::get_by_id(revision.document_id));
+            let Some(document) = document else {
+                continue;
+            };
+            if let Some(retention_years) =
+                Self::expired_retention(&policy, document, &revision, now)
+            {
+                due.push((revision, retention_years));
+            }
+        }
+        if due.is_empty() {
+            return;
+        }
+        let deferred = due.len().saturating_sub(MAX_PURGES_PER_RUN);
+        due.truncate(MAX_PURGES_PER_RUN);
+        // Deferred revisions keep shared content until their own run checks them again
+        releasable.extend(due.iter().map(|(revision, _)| revision.id));
+
+        let mut purged = Vec::new();
+        for (revision, retention_years) in due {
+            let Some(Some(document)) = documents.get(&revision.document_id) else {
+                continue;
+            };
+            let record =
+                Self::purge_revision(revision, document, retention_years, &releasable, now);
+            log_info!(
+                "retention: Purged revision content [revision_id={}, document_id={}, version={}, document_type={:?}, retention_years={}, retired_at={}, revision_hash={}, contents={}, bytes_removed={}]",
+                record.revision_id,
+                record.document_id,
+                record.version,
+                record.document_type,
+                record.retention_years,
+                record.retired_at,
+                record.revision_hash,
+                record.contents.len(),
+                record.bytes_removed
+            );
+            purged.push(record.revision_id);
+            RetentionManager::record_purge(record);
+        }
+        SearchManager::remove_revisions(&purged);
+        if deferred > 0 {
+            log_info!(
+                "retention: Purge limit reached, remaining revisions follow on the next run [purged={}, deferred={}]",
+                purged.len(),
+                deferred
+            );
+        }
+    }
+
+    /// Runs `enforce_retention` hourly. Purges that did not fit in one run's
+    /// `MAX_PURGES_PER_RUN` are picked up by the next run.
+    pub fn start_retention_timer() {
+        ic_cdk_timers::set_timer_interval(
+            Duration::from_secs(RETENTION_CHECK_INTERVAL_SECONDS),
+            Self::enforce_retention,
+        );
+    }
 }
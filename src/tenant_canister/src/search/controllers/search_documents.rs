@@ -0,0 +1,7 @@
+use crate::search::search_manager::SearchManager;
+use shared::types::search::{SearchDocumentsInput, SearchDocumentsResult};
+
+#[ic_cdk_macros::query]
+pub fn search_documents(input: SearchDocumentsInput) -> SearchDocumentsResult {
+    SearchManager::search_documents(input)
+}
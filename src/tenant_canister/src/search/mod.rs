@@ -0,0 +1,4 @@
+pub mod controllers;
+pub mod search_manager;
+
+pub use search_manager::SearchManager;
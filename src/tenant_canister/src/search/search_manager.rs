@@ -0,0 +1,364 @@
+use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap};
+use shared::consts::memory_ids::tenant_canister::{
+    SEARCH_INDEX_MEMORY_ID, SEARCH_REVISIONS_MEMORY_ID, SEARCH_REVISION_TERMS_MEMORY_ID,
+};
+use shared::consts::search::{
+    DEFAULT_SEARCH_LIMIT, MAX_SEARCH_LIMIT, MAX_TOKEN_LENGTH, MIN_TOKEN_LENGTH,
+    SNIPPET_CONTEXT_LENGTH,
+};
+use shared::types::access_control::{DocumentPermission, Permission};
+use shared::types::errors::AppError;
+use shared::types::revisions::{Revision, RevisionContentData, RevisionContentType, RevisionId};
+use shared::types::search::{
+    SearchDocumentsInput, SearchDocumentsResult, SearchHit, SearchIndexKey, SearchIndexedRevision,
+    SearchRevisionTermKey,
+};
+use shared::types::users::GetUserResult;
+use shared::{log_debug, log_info, log_warn};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::documents::DocumentManager;
+use crate::revisions::revisions_manager::RevisionsManager;
+use crate::users::user_manager::UserManager;
+
+type Memory = VirtualMemory<DefaultMemoryImpl>;
+
+thread_local! {
+    static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
+        RefCell::new(MemoryManager::init(DefaultMemoryImpl::default()));
+
+    // (term, revision) -> number of occurrences of the term in the revision
+    static SEARCH_INDEX: RefCell<StableBTreeMap<SearchIndexKey, u32, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(SEARCH_INDEX_MEMORY_ID))),
+        )
+    );
+
+    // (revision, term) for each entry of the inverted index
+    static SEARCH_REVISION_TERMS: RefCell<StableBTreeMap<SearchRevisionTermKey, (), Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(SEARCH_REVISION_TERMS_MEMORY_ID))),
+        )
+    );
+
+    static SEARCH_REVISIONS: RefCell<StableBTreeMap<RevisionId, SearchIndexedRevision, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(SEARCH_REVISIONS_MEMORY_ID))),
+        )
+    );
+}
+
+/// A token together with its position (in chars) in the source text.
+struct TokenSpan {
+    start: usize,
+    end: usize,
+    token: String,
+}
+
+pub struct SearchManager;
+
+impl SearchManager {
+    /// Splits text into lowercase alphanumeric tokens. Markdown syntax is punctuation
+    /// and therefore never ends up in the index.
+    fn tokenize_spans(chars: &[char]) -> Vec<TokenSpan> {
+        let mut spans = Vec::new();
+        let mut start = None;
+        for index in 0..=chars.len() {
+            let is_word = index < chars.len() && chars[index].is_alphanumeric();
+            match (is_word, start) {
+                (true, None) => start = Some(index),
+                (false, Some(token_start)) => {
+                    let length = index - token_start;
+                    if (MIN_TOKEN_LENGTH..=MAX_TOKEN_LENGTH).contains(&length) {
+                        spans.push(TokenSpan {
+                            start: token_start,
+                            end: index,
+                            token: chars[token_start..index]
+                                .iter()
+                                .collect::<String>()
+                                .to_lowercase(),
+                        });
+                    }
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        spans
+    }
+
+    fn tokenize(text: &str) -> Vec<String> {
+        let chars: Vec<char> = text.chars().collect();
+        Self::tokenize_spans(&chars)
+            .into_iter()
+            .map(|span| span.token)
+            .collect()
+    }
+
+    /// Concatenated Markdown content of a revision. Uploads, chunked and encrypted content
+    /// are not indexed.
+    fn get_markdown_text(revision: &Revision) -> String {
+        revision
+            .contents
+            .iter()
+            .filter_map(|content_id| RevisionsManager::get_revision_content_by_id(*content_id))
+            .filter(|content| matches!(content.content_type, RevisionContentType::Markdown))
+            .filter(|content| content.encryption.is_none())
+            .filter_map(|content| match content.content_data {
+                Some(RevisionContentData::Direct { bytes }) => {
+                    Some(String::from_utf8_lossy(&bytes).into_owned())
+                }
+                _ => None,
+            })
+            .collect::<Vec<String>>()
+            .join("\n\n")
+    }
+
+    /// Adds the Markdown content of a revision to the index. Returns the size of the
+    /// indexed text in bytes.
+    pub fn index_revision(revision: &Revision) -> u64 {
+        let text = Self::get_markdown_text(revision);
+        let tokens = Self::tokenize(&text);
+        if tokens.is_empty() {
+            return text.len() as u64;
+        }
+
+        let mut frequencies: HashMap<String, u32> = HashMap::new();
+        for token in &tokens {
+            *frequencies.entry(token.clone()).or_insert(0) += 1;
+        }
+
+        SEARCH_INDEX.with(|index| {
+            let mut index = index.borrow_mut();
+            for (term, frequency) in &frequencies {
+                index.insert(
+                    SearchIndexKey {
+                        term: term.clone(),
+                        revision_id: revision.id,
+                    },
+                    *frequency,
+                );
+            }
+        });
+        SEARCH_REVISION_TERMS.with(|terms| {
+            let mut terms = terms.borrow_mut();
+            for term in frequencies.keys() {
+                terms.insert(
+                    SearchRevisionTermKey {
+                        revision_id: revision.id,
+                        term: term.clone(),
+                    },
+                    (),
+                );
+            }
+        });
+        SEARCH_REVISIONS.with(|revisions| {
+            revisions.borrow_mut().insert(
+                revision.id,
+                SearchIndexedRevision {
+                    revision_id: revision.id,
+                    document_id: revision.document_id,
+                    project_id: revision.project_id,
+                    token_count: tokens.len() as u32,
+                },
+            );
+        });
+
+        log_debug!(
+            "search_index: Indexed revision [revision_id={}, document_id={}, tokens={}, terms={}]",
+            revision.id,
+            revision.document_id,
+            tokens.len(),
+            frequencies.len()
+        );
+        text.len() as u64
+    }
+
+    /// Removes revisions from the index, e.g. once their content has been purged.
+    pub fn remove_revisions(revision_ids: &[RevisionId]) {
+        for revision_id in revision_ids {
+            let start = SearchRevisionTermKey {
+                revision_id: *revision_id,
+                term: String::new(),
+            };
+            let keys: Vec<SearchRevisionTermKey> = SEARCH_REVISION_TERMS.with(|terms| {
+                terms
+                    .borrow()
+                    .keys_range(start..)
+                    .take_while(|key| key.revision_id == *revision_id)
+                    .collect()
+            });
+            SEARCH_INDEX.with(|index| {
+                let mut index = index.borrow_mut();
+                for key in &keys {
+                    index.remove(&SearchIndexKey {
+                        term: key.term.clone(),
+                        revision_id: key.revision_id,
+                    });
+                }
+            });
+            SEARCH_REVISION_TERMS.with(|terms| {
+                let mut terms = terms.borrow_mut();
+                for key in &keys {
+                    terms.remove(key);
+                }
+            });
+        }
+        SEARCH_REVISIONS.with(|revisions| {
+            let mut revisions = revisions.borrow_mut();
+            for revision_id in revision_ids {
+                revisions.remove(revision_id);
+            }
+        });
+    }
+
+    /// Returns the revisions containing `term` with the number of occurrences.
+    fn get_postings(term: &str) -> Vec<(RevisionId, u32)> {
+        let start = SearchIndexKey {
+            term: term.to_string(),
+            revision_id: RevisionId::MIN,
+        };
+        let end = SearchIndexKey {
+            term: term.to_string(),
+            revision_id: RevisionId::MAX,
+        };
+        SEARCH_INDEX.with(|index| {
+            index
+                .borrow()
+                .range(start..=end)
+                .map(|(key, frequency)| (key.revision_id, frequency))
+                .collect()
+        })
+    }
+
+    fn build_snippet(text: &str, terms: &[String]) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        let (start, end) = Self::tokenize_spans(&chars)
+            .into_iter()
+            .find(|span| terms.contains(&span.token))
+            .map(|span| (span.start, span.end))
+            .unwrap_or((0, 0));
+
+        let from = start.saturating_sub(SNIPPET_CONTEXT_LENGTH / 2);
+        let to = (end + SNIPPET_CONTEXT_LENGTH / 2).min(chars.len());
+        let excerpt = chars[from..to]
+            .iter()
+            .collect::<String>()
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ");
+
+        let prefix = if from > 0 { "..." } else { "" };
+        let suffix = if to < chars.len() { "..." } else { "" };
+        format!("{}{}{}", prefix, excerpt, suffix)
+    }
+
+    pub fn search_documents(input: SearchDocumentsInput) -> SearchDocumentsResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Search attempt [principal={}, project_id={}, query='{}']",
+            principal,
+            input.project_id,
+            input.query
+        );
+
+        let user = match UserManager::get_user_by_principal(principal) {
+            GetUserResult::Ok(u) => u,
+            GetUserResult::Err(e) => {
+                log_warn!(
+                    "auth_check: Authentication failed for search [principal={}] - {:?}",
+                    principal,
+                    e
+                );
+                return SearchDocumentsResult::Err(e);
+            }
+        };
+
+        let mut terms = Self::tokenize(&input.query);
+        terms.sort();
+        terms.dedup();
+        if terms.is_empty() {
+            return SearchDocumentsResult::Err(AppError::ValidationError(
+                "Search query does not contain any searchable terms".to_string(),
+            ));
+        }
+
+        let limit = input
+            .limit
+            .unwrap_or(DEFAULT_SEARCH_LIMIT)
+            .clamp(1, MAX_SEARCH_LIMIT) as usize;
+        let include_all_revisions = input.include_all_revisions.unwrap_or(false);
+        let total_revisions = SEARCH_REVISIONS.with(|revisions| revisions.borrow().len()) as f64;
+
+        // revision -> (summed tf-idf, number of matched terms)
+        let mut scores: HashMap<RevisionId, (f64, usize)> = HashMap::new();
+        for term in &terms {
+            let postings = Self::get_postings(term);
+            if postings.is_empty() {
+                continue;
+            }
+            let idf = (1.0 + total_revisions / postings.len() as f64).ln();
+            for (revision_id, frequency) in postings {
+                let token_count = SEARCH_REVISIONS
+                    .with(|revisions| revisions.borrow().get(&revision_id))
+                    .map(|indexed| indexed.token_count.max(1))
+                    .unwrap_or(1);
+                let entry = scores.entry(revision_id).or_insert((0.0, 0));
+                entry.0 += frequency as f64 / token_count as f64 * idf;
+                entry.1 += 1;
+            }
+        }
+
+        let read = Permission::Document(DocumentPermission::Read);
+        let mut ranked: Vec<(RevisionId, f64)> = scores
+            .into_iter()
+            .filter_map(|(revision_id, (score, matched))| {
+                let indexed =
+                    SEARCH_REVISIONS.with(|revisions| revisions.borrow().get(&revision_id))?;
+                if indexed.project_id != input.project_id {
+                    return None;
+                }
+                let document = DocumentManager::get_by_id(indexed.document_id)?;
+                if !include_all_revisions && document.revisions.last() != Some(&revision_id) {
+                    return None;
+                }
+                if !DocumentManager::has_permission(user.id, &document, &read) {
+                    return None;
+                }
+                // Favour revisions matching more of the query terms
+                Some((revision_id, score * matched as f64 / terms.len() as f64))
+            })
+            .collect();
+
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(b.0.cmp(&a.0)));
+        ranked.truncate(limit);
+
+        let hits: Vec<SearchHit> = ranked
+            .into_iter()
+            .filter_map(|(revision_id, score)| {
+                let revision = RevisionsManager::get_by_id(revision_id)?;
+                let document = DocumentManager::get_by_id(revision.document_id)?;
+                Some(SearchHit {
+                    document_id: document.id,
+                    document_title: document.title,
+                    revision_id,
+                    version: revision.version,
+                    project_id: revision.project_id,
+                    score,
+                    snippet: Self::build_snippet(&Self::get_markdown_text(&revision), &terms),
+                })
+            })
+            .collect();
+
+        log_info!(
+            "search: Search completed [principal={}, project_id={}, terms={}, hits={}]",
+            principal,
+            input.project_id,
+            terms.len(),
+            hits.len()
+        );
+        SearchDocumentsResult::Ok(hits)
+    }
+}
@@ -0,0 +1,10 @@
+use crate::share_links::share_links_manager::ShareLinksManager;
+use shared::types::share_links::{CreateShareLinkInput, CreateShareLinkResult};
+
+#[ic_cdk_macros::update]
+pub async fn create_share_link(input: CreateShareLinkInput) -> CreateShareLinkResult {
+    match ShareLinksManager::create_share_link(input).await {
+        Ok(link) => CreateShareLinkResult::Ok(link),
+        Err(e) => CreateShareLinkResult::Err(e),
+    }
+}
@@ -0,0 +1,7 @@
+use crate::share_links::share_links_manager::ShareLinksManager;
+use shared::types::share_links::{GetSharedRevisionInput, GetSharedRevisionResult};
+
+#[ic_cdk_macros::query]
+pub fn get_shared_revision(input: GetSharedRevisionInput) -> GetSharedRevisionResult {
+    ShareLinksManager::get_shared_revision(input)
+}
@@ -0,0 +1,7 @@
+use crate::share_links::share_links_manager::ShareLinksManager;
+use shared::types::share_links::{ListShareLinksInput, ListShareLinksResult};
+
+#[ic_cdk_macros::query]
+pub fn list_share_links(input: ListShareLinksInput) -> ListShareLinksResult {
+    ShareLinksManager::list_share_links(input)
+}
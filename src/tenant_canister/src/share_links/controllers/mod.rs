@@ -0,0 +1,4 @@
+pub mod create_share_link;
+pub mod get_shared_revision;
+pub mod list_share_links;
+pub mod revoke_share_link;
@@ -0,0 +1,7 @@
+use crate::share_links::share_links_manager::ShareLinksManager;
+use shared::types::share_links::{RevokeShareLinkInput, RevokeShareLinkResult};
+
+#[ic_cdk_macros::update]
+pub fn revoke_share_link(input: RevokeShareLinkInput) -> RevokeShareLinkResult {
+    ShareLinksManager::revoke_share_link(input)
+}
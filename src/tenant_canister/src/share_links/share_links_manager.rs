@@ -0,0 +1,330 @@
+use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap};
+use shared::consts::memory_ids::tenant_canister::SHARE_LINKS_MEMORY_ID;
+use shared::consts::share_links::MAX_SHARE_LINK_TTL;
+use shared::types::access_control::{DocumentPermission, Permission};
+use shared::types::documents::Document;
+use shared::types::errors::AppError;
+use shared::types::revisions::{RevisionContentId, RevisionStatus};
+use shared::types::share_links::{
+    CreateShareLinkInput, GetSharedRevisionInput, GetSharedRevisionResult, ListShareLinksInput,
+    ListShareLinksResult, RevokeShareLinkInput, RevokeShareLinkResult, ShareLink, ShareLinkId,
+    SharedRevision,
+};
+use shared::types::users::User;
+use shared::utils::random::random;
+use shared::{log_debug, log_info, log_warn};
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::documents::DocumentManager;
+use crate::revisions::revisions_manager::RevisionsManager;
+use crate::users::user_manager::UserManager;
+
+type Memory = VirtualMemory<DefaultMemoryImpl>;
+
+thread_local! {
+    static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
+        RefCell::new(MemoryManager::init(DefaultMemoryImpl::default()));
+
+    static SHARE_LINKS: RefCell<StableBTreeMap<ShareLinkId, ShareLink, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(SHARE_LINKS_MEMORY_ID))),
+        )
+    );
+
+    static NEXT_ID: AtomicU64 = const { AtomicU64::new(0) };
+}
+
+pub struct ShareLinksManager;
+
+impl ShareLinksManager {
+    fn get_next_id() -> ShareLinkId {
+        NEXT_ID.with(|id| id.fetch_add(1, Ordering::SeqCst))
+    }
+
+    fn insert(id: ShareLinkId, link: ShareLink) {
+        SHARE_LINKS.with(|links| {
+            links.borrow_mut().insert(id, link);
+        });
+    }
+
+    fn get_by_token(token: &str) -> Option<ShareLink> {
+        SHARE_LINKS.with(|links| {
+            links
+                .borrow()
+                .iter()
+                .find(|(_, link)| link.token == token)
+                .map(|(_, link)| link)
+        })
+    }
+
+    fn authorize_share(user: &User, document: &Document, operation: &str) -> Result<(), AppError> {
+        let share = Permission::Document(DocumentPermission::Share);
+        if DocumentManager::has_permission(user.id, document, &share) {
+            return Ok(());
+        }
+        log_warn!(
+            "access_control: Permission denied for {} [user_id={}, document_id={}]",
+            operation,
+            user.id,
+            document.id
+        );
+        Err(AppError::Unauthorized)
+    }
+
+    /// Resolves a token to a link that is neither revoked nor expired.
+    pub fn get_valid_link(token: &str) -> Result<ShareLink, AppError> {
+        let link = Self::get_by_token(token).ok_or_else(|| {
+            log_warn!("share_link_access: Security event - invalid share token used");
+            AppError::Unauthorized
+        })?;
+        if link.revoked_at.is_some() {
+            log_warn!(
+                "share_link_access: Revoked share link used [id={}, document_id={}]",
+                link.id,
+                link.document_id
+            );
+            return Err(AppError::Unauthorized);
+        }
+        if link.expires_at <= ic_cdk::api::time() {
+            log_warn!(
+                "share_link_access: Expired share link used [id={}, document_id={}]",
+                link.id,
+                link.document_id
+            );
+            return Err(AppError::Unauthorized);
+        }
+        Ok(link)
+    }
+
+    /// Checks that `token` grants access to the given content of its shared revision.
+    pub fn authorize_content(token: &str, content_id: RevisionContentId) -> Result<(), AppError> {
+        let link = Self::get_valid_link(token)?;
+        let revision = RevisionsManager::get_by_id(link.revision_id)
+            .ok_or_else(|| AppError::EntityNotFound("Revision not found".to_string()))?;
+        if !revision.contents.contains(&content_id) {
+            log_warn!(
+                "share_link_access: Content outside of shared revision requested [id={}, revision_id={}, content_id={}]",
+                link.id,
+                link.revision_id,
+                content_id
+            );
+            return Err(AppError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    pub async fn create_share_link(input: CreateShareLinkInput) -> Result<ShareLink, AppError> {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Share link creation attempt [principal={}, revision_id={}, expires_at={}]",
+            principal,
+            input.revision_id,
+            input.expires_at
+        );
+
+        let user = UserManager::authenticate(principal, "share link creation")?;
+        let revision = RevisionsManager::get_by_id(input.revision_id)
+            .ok_or_else(|| AppError::EntityNotFound("Revision not found".to_string()))?;
+        let document = DocumentManager::get_by_id(revision.document_id)
+            .ok_or_else(|| AppError::EntityNotFound("Document not found".to_string()))?;
+        Self::authorize_share(&user, &document, "share link creation")?;
+        if revision.status != Some(RevisionStatus::Released) {
+            return Err(AppError::InvalidStateTransition(
+                "Only released revisions can be shared".to_string(),
+            ));
+        }
+
+        let now = ic_cdk::api::time();
+        if input.expires_at <= now {
+            return Err(AppError::ValidationError(
+                "Expiry must be in the future".to_string(),
+            ));
+        }
+        if input.expires_at - now > MAX_SHARE_LINK_TTL {
+            return Err(AppError::ValidationError(
+                "Share links cannot be valid for more than 90 days".to_string(),
+            ));
+        }
+
+        let token = random(32).await;
+        let id = Self::get_next_id();
+        let link = ShareLink {
+            id,
+            token,
+            document_id: document.id,
+            revision_id: revision.id,
+            project_id: document.project_id,
+            created_at: ic_cdk::api::time(),
+            created_by: user.id,
+            expires_at: input.expires_at,
+            revoked_at: None,
+            revoked_by: None,
+        };
+        Self::insert(id, link.clone());
+        log_info!(
+            "share_link_creation: Created share link [id={}, document_id={}, revision_id={}, expires_at={}, user_id={}, principal={}]",
+            link.id,
+            link.document_id,
+            link.revision_id,
+            link.expires_at,
+            user.id,
+            principal
+        );
+        Ok(link)
+    }
+
+    pub fn revoke_share_link(input: RevokeShareLinkInput) -> RevokeShareLinkResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Share link revocation attempt [principal={}, id={}]",
+            principal,
+            input.id
+        );
+
+        let user = match UserManager::authenticate(principal, "share link revocation") {
+            Ok(user) => user,
+            Err(e) => return RevokeShareLinkResult::Err(e),
+        };
+        let mut link = match SHARE_LINKS.with(|links| links.borrow().get(&input.id)) {
+            Some(link) => link,
+            None => {
+                log_warn!(
+                    "share_link_revocation: Share link not found [id={}, principal={}]",
+                    input.id,
+                    principal
+                );
+                return RevokeShareLinkResult::Err(AppError::EntityNotFound(
+                    "Share link not found".to_string(),
+                ));
+            }
+        };
+        let document = match DocumentManager::get_by_id(link.document_id) {
+            Some(document) => document,
+            None => {
+                log_warn!(
+                    "share_link_revocation: Document not found [id={}, document_id={}, principal={}]",
+                    link.id,
+                    link.document_id,
+                    principal
+                );
+                return RevokeShareLinkResult::Err(AppError::EntityNotFound(
+                    "Document not found".to_string(),
+                ));
+            }
+        };
+        if let Err(e) = Self::authorize_share(&user, &document, "share link revocation") {
+            return RevokeShareLinkResult::Err(e);
+        }
+
+        if link.revoked_at.is_some() {
+            log_warn!(
+                "share_link_revocation: Share link already revoked [id={}, principal={}]",
+                link.id,
+                principal
+            );
+            return RevokeShareLinkResult::Err(AppError::InvalidStateTransition(
+                "Share link is already revoked".to_string(),
+            ));
+        }
+        link.revoked_at = Some(ic_cdk::api::time());
+        link.revoked_by = Some(user.id);
+        Self::insert(link.id, link.clone());
+        log_info!(
+            "share_link_revocation: Revoked share link [id={}, document_id={}, user_id={}, principal={}]",
+            link.id,
+            link.document_id,
+            user.id,
+            principal
+        );
+        RevokeShareLinkResult::Ok
+    }
+
+    pub fn list_share_links(input: ListShareLinksInput) -> ListShareLinksResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "share_link_listing: Processing request [principal={}, document_id={}]",
+            principal,
+            input.document_id
+        );
+
+        let user = match UserManager::authenticate(principal, "share link listing") {
+            Ok(user) => user,
+            Err(e) => return ListShareLinksResult::Err(e),
+        };
+        let document = match DocumentManager::get_by_id(input.document_id) {
+            Some(document) => document,
+            None => {
+                return ListShareLinksResult::Err(AppError::EntityNotFound(
+                    "Document not found".to_string(),
+                ))
+            }
+        };
+        if let Err(e) = Self::authorize_share(&user, &document, "share link listing") {
+            return ListShareLinksResult::Err(e);
+        }
+        ListShareLinksResult::Ok(SHARE_LINKS.with(|links| {
+            links
+                .borrow()
+                .iter()
+                .filter(|(_, link)| link.document_id == input.document_id)
+                .map(|(_, link)| link)
+                .collect::<Vec<ShareLink>>()
+        }))
+    }
+
+    pub fn get_shared_revision(input: GetSharedRevisionInput) -> GetSharedRevisionResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "share_link_access: Shared revision requested [principal={}]",
+            principal
+        );
+
+        let link = match Self::get_valid_link(&input.token) {
+            Ok(link) => link,
+            Err(e) => return GetSharedRevisionResult::Err(e),
+        };
+        let revision = match RevisionsManager::get_by_id(link.revision_id) {
+            Some(revision) => revision,
+            None => {
+                log_warn!(
+                    "share_link_access: Shared revision not found [id={}, revision_id={}]",
+                    link.id,
+                    link.revision_id
+                );
+                return GetSharedRevisionResult::Err(AppError::EntityNotFound(
+                    "Revision not found".to_string(),
+                ));
+            }
+        };
+        let document = match DocumentManager::get_by_id(link.document_id) {
+            Some(document) => document,
+            None => {
+                log_warn!(
+                    "share_link_access: Shared document not found [id={}, document_id={}]",
+                    link.id,
+                    link.document_id
+                );
+                return GetSharedRevisionResult::Err(AppError::EntityNotFound(
+                    "Document not found".to_string(),
+                ));
+            }
+        };
+        let contents = RevisionsManager::list_revision_contents_by_revision_id(revision.id);
+        log_info!(
+            "share_link_access: Shared revision accessed [id={}, document_id={}, revision_id={}, principal={}]",
+            link.id,
+            link.document_id,
+            link.revision_id,
+            principal
+        );
+        GetSharedRevisionResult::Ok(Box::new(SharedRevision {
+            document_id: document.id,
+            document_title: document.title,
+            revision,
+            contents,
+            expires_at: link.expires_at,
+        }))
+    }
+}
@@ -0,0 +1,7 @@
+use crate::training::TrainingManager;
+use shared::types::training::{AcknowledgeRevisionInput, AcknowledgeRevisionResult};
+
+#[ic_cdk_macros::update]
+pub fn acknowledge_revision(input: AcknowledgeRevisionInput) -> AcknowledgeRevisionResult {
+    TrainingManager::acknowledge_revision(input)
+}
@@ -0,0 +1,7 @@
+use crate::training::TrainingManager;
+use shared::types::training::{GetTrainingPolicyInput, GetTrainingPolicyResult};
+
+#[ic_cdk_macros::query]
+pub fn get_training_policy(input: GetTrainingPolicyInput) -> GetTrainingPolicyResult {
+    TrainingManager::get_training_policy(input)
+}
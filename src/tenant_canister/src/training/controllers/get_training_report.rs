@@ -0,0 +1,7 @@
+use crate::training::TrainingManager;
+use shared::types::training::{GetTrainingReportInput, GetTrainingReportResult};
+
+#[ic_cdk_macros::query]
+pub fn get_training_report(input: GetTrainingReportInput) -> GetTrainingReportResult {
+    TrainingManager::get_training_report(input)
+}
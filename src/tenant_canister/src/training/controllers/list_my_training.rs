@@ -0,0 +1,7 @@
+use crate::training::TrainingManager;
+use shared::types::training::{ListMyTrainingInput, ListMyTrainingResult};
+
+#[ic_cdk_macros::query]
+pub fn list_my_training(input: ListMyTrainingInput) -> ListMyTrainingResult {
+    TrainingManager::list_my_training(input)
+}
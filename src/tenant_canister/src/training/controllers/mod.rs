@@ -0,0 +1,5 @@
+pub mod acknowledge_revision;
+pub mod get_training_policy;
+pub mod get_training_report;
+pub mod list_my_training;
+pub mod set_training_policy;
@@ -0,0 +1,7 @@
+use crate::training::TrainingManager;
+use shared::types::training::{SetTrainingPolicyInput, SetTrainingPolicyResult};
+
+#[ic_cdk_macros::update]
+pub fn set_training_policy(input: SetTrainingPolicyInput) -> SetTrainingPolicyResult {
+    TrainingManager::set_training_policy(input)
+}
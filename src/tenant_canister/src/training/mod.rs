@@ -0,0 +1,4 @@
+pub mod controllers;
+pub mod training_manager;
+
+pub use training_manager::TrainingManager;
@@ -0,0 +1,504 @@
+use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap};
+use shared::consts::memory_ids::tenant_canister::{
+    TRAINING_ASSIGNMENTS_MEMORY_ID, TRAINING_POLICIES_MEMORY_ID,
+};
+use shared::consts::training::MAX_TRAINING_DUE_DAYS;
+use shared::types::access_control::{DocumentPermission, Permission, ProjectPermission, RoleId};
+use shared::types::documents::Document;
+use shared::types::errors::AppError;
+use shared::types::projects::ProjectId;
+use shared::types::revisions::{Revision, RevisionId, RevisionStatus};
+use shared::types::training::{
+    AcknowledgeRevisionInput, AcknowledgeRevisionResult, GetTrainingPolicyInput,
+    GetTrainingPolicyResult, GetTrainingReportInput, GetTrainingReportResult, ListMyTrainingInput,
+    ListMyTrainingResult, SetTrainingPolicyInput, SetTrainingPolicyResult, TrainingAssignment,
+    TrainingPolicy, TrainingReport, TrainingReportEntry,
+};
+use shared::types::users::{User, UserId};
+use shared::utils::time::NANOS_PER_DAY;
+use shared::{log_debug, log_info, log_warn};
+use std::cell::RefCell;
+
+use crate::access_control::AccessControlManager;
+use crate::approvals::approvals_manager::ApprovalsManager;
+use crate::documents::DocumentManager;
+use crate::projects::projects_manager::ProjectsManager;
+use crate::revisions::revisions_manager::RevisionsManager;
+use crate::users::user_manager::UserManager;
+
+type Memory = VirtualMemory<DefaultMemoryImpl>;
+
+thread_local! {
+    static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
+        RefCell::new(MemoryManager::init(DefaultMemoryImpl::default()));
+
+    static TRAINING_POLICIES: RefCell<StableBTreeMap<ProjectId, TrainingPolicy, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(TRAINING_POLICIES_MEMORY_ID))),
+        )
+    );
+
+    static TRAINING_ASSIGNMENTS: RefCell<StableBTreeMap<(RevisionId, UserId), TrainingAssignment, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(TRAINING_ASSIGNMENTS_MEMORY_ID))),
+        )
+    );
+}
+
+/// Training on released revisions. When a revision is released, users holding one of
+/// the roles of the project's training policy are assigned to read and acknowledge it;
+/// open assignments on earlier revisions of the document are superseded.
+pub struct TrainingManager;
+
+impl TrainingManager {
+    fn authorize(
+        user: &User,
+        project_id: ProjectId,
+        permission: ProjectPermission,
+        operation: &str,
+    ) -> Result<(), AppError> {
+        if !AccessControlManager::has_project_permission(
+            user.id,
+            project_id,
+            &Permission::Project(permission),
+        ) {
+            log_warn!(
+                "access_control: Permission denied for {} [user_id={}, project_id={}]",
+                operation,
+                user.id,
+                project_id
+            );
+            return Err(AppError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn get_policy(project_id: ProjectId) -> Option<TrainingPolicy> {
+        TRAINING_POLICIES.with(|policies| policies.borrow().get(&project_id))
+    }
+
+    fn insert_assignment(assignment: TrainingAssignment) {
+        TRAINING_ASSIGNMENTS.with(|assignments| {
+            assignments
+                .borrow_mut()
+                .insert((assignment.revision_id, assignment.user_id), assignment);
+        });
+    }
+
+    fn is_open(assignment: &TrainingAssignment) -> bool {
+        assignment.acknowledged_at.is_none() && assignment.superseded_at.is_none()
+    }
+
+    fn is_overdue(assignment: &TrainingAssignment, now: u64) -> bool {
+        Self::is_open(assignment) && assignment.due_at <= now
+    }
+
+    /// Assigns training on a revision that was just released. Called by
+    /// `ApprovalsManager` after the release.
+    pub fn assign_training(revision: &Revision, document: &Document) {
+        let now = ic_cdk::api::time();
+        let superseded: Vec<TrainingAssignment> = TRAINING_ASSIGNMENTS.with(|assignments| {
+            let assignments = assignments.borrow();
+            document
+                .revisions
+                .iter()
+                .filter(|revision_id| **revision_id != revision.id)
+                .flat_map(|revision_id| {
+                    assignments
+                        .range((*revision_id, UserId::MIN)..=(*revision_id, UserId::MAX))
+                        .map(|(_, assignment)| assignment)
+                        .filter(Self::is_open)
+                        .collect::<Vec<_>>()
+                })
+                .collect()
+        });
+        let superseded_count = superseded.len();
+        for mut assignment in superseded {
+            assignment.superseded_at = Some(now);
+            Self::insert_assignment(assignment);
+        }
+
+        let Some(policy) = Self::get_policy(revision.project_id) else {
+            return;
+        };
+        let due_at = now.saturating_add(u64::from(policy.due_days) * NANOS_PER_DAY);
+        let read = Permission::Document(DocumentPermission::Read);
+        let mut assigned = 0;
+        for (user_id, role_ids) in AccessControlManager::get_role_holders(&policy.role_ids) {
+            if !DocumentManager::has_permission(user_id, document, &read) {
+                log_warn!(
+                    "training: Trainee cannot read the released document, not assigned [user_id={}, document_id={}, revision_id={}]",
+                    user_id,
+                    document.id,
+                    revision.id
+                );
+                continue;
+            }
+            Self::insert_assignment(TrainingAssignment {
+                revision_id: revision.id,
+                user_id,
+                document_id: document.id,
+                project_id: revision.project_id,
+                version: revision.version,
+                role_ids,
+                assigned_at: now,
+                due_at,
+                acknowledged_at: None,
+                signature_sequence: None,
+                superseded_at: None,
+            });
+            assigned += 1;
+        }
+        log_info!(
+            "training: Training assigned [revision_id={}, document_id={}, version={}, users={}, superseded={}, due_at={}]",
+            revision.id,
+            document.id,
+            revision.version,
+            assigned,
+            superseded_count,
+            due_at
+        );
+    }
+
+    /// Sets the roles assigned training on releases in a project. Applies to revisions
+    /// released from now on.
+    pub fn set_training_policy(input: SetTrainingPolicyInput) -> SetTrainingPolicyResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Training policy update attempt [principal={}, project_id={}, role_ids={:?}, due_days={}]",
+            principal,
+            input.project_id,
+            input.role_ids,
+            input.due_days
+        );
+
+        let user = match UserManager::authenticate(principal, "training policy update") {
+            Ok(user) => user,
+            Err(e) => return SetTrainingPolicyResult::Err(e),
+        };
+        if let Err(e) = Self::authorize(
+            &user,
+            input.project_id,
+            ProjectPermission::ConfigureSettings,
+            "training policy update",
+        ) {
+            return SetTrainingPolicyResult::Err(e);
+        }
+
+        if input.role_ids.is_empty() {
+            TRAINING_POLICIES.with(|policies| policies.borrow_mut().remove(&input.project_id));
+            log_info!(
+                "training: Training policy removed [project_id={}, user_id={}, principal={}]",
+                input.project_id,
+                user.id,
+                principal
+            );
+            return SetTrainingPolicyResult::Ok;
+        }
+        if input.due_days == 0 || input.due_days > MAX_TRAINING_DUE_DAYS {
+            log_warn!(
+                "training: Invalid training due days [project_id={}, due_days={}, principal={}]",
+                input.project_id,
+                input.due_days,
+                principal
+            );
+            return SetTrainingPolicyResult::Err(AppError::ValidationError(format!(
+                "Training must be due within 1 to {} days",
+                MAX_TRAINING_DUE_DAYS
+            )));
+        }
+        let mut role_ids: Vec<RoleId> = Vec::new();
+        for role_id in &input.role_ids {
+            match AccessControlManager::get_role(role_id) {
+                Some(role) if role.project_id == input.project_id => {}
+                _ => {
+                    log_warn!(
+                        "training: Training role not found in project [project_id={}, role_id={}, principal={}]",
+                        input.project_id,
+                        role_id,
+                        principal
+                    );
+                    return SetTrainingPolicyResult::Err(AppError::EntityNotFound(format!(
+                        "Role {} not found in project",
+                        role_id
+                    )));
+                }
+            }
+            if !role_ids.contains(role_id) {
+                role_ids.push(*role_id);
+            }
+        }
+
+        let policy = TrainingPolicy {
+            project_id: input.project_id,
+            role_ids,
+            due_days: input.due_days,
+            updated_at: ic_cdk::api::time(),
+            updated_by: user.id,
+        };
+        log_info!(
+            "training: Training policy updated [project_id={}, role_ids={:?}, due_days={}, user_id={}, principal={}]",
+            policy.project_id,
+            policy.role_ids,
+            policy.due_days,
+            user.id,
+            principal
+        );
+        TRAINING_POLICIES.with(|policies| {
+            policies.borrow_mut().insert(policy.project_id, policy);
+        });
+        SetTrainingPolicyResult::Ok
+    }
+
+    pub fn get_training_policy(input: GetTrainingPolicyInput) -> GetTrainingPolicyResult {
+        let principal = ic_cdk::api::msg_caller();
+        let user = match UserManager::authenticate(principal, "training policy retrieval") {
+            Ok(user) => user,
+            Err(e) => return GetTrainingPolicyResult::Err(e),
+        };
+        if !ProjectsManager::is_member(input.project_id, user.id) {
+            log_warn!(
+                "access_control: Permission denied for training policy retrieval [user_id={}, project_id={}]",
+                user.id,
+                input.project_id
+            );
+            return GetTrainingPolicyResult::Err(AppError::Unauthorized);
+        }
+        GetTrainingPolicyResult::Ok(Self::get_policy(input.project_id))
+    }
+
+    /// Acknowledges the caller's training on a released revision, optionally signed
+    /// with the `Acknowledged` meaning.
+    pub fn acknowledge_revision(input: AcknowledgeRevisionInput) -> AcknowledgeRevisionResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Training acknowledgement attempt [principal={}, revision_id={}, signed={}]",
+            principal,
+            input.revision_id,
+            input.attestation.is_some()
+        );
+
+        let user = match UserManager::authenticate(principal, "training acknowledgement") {
+            Ok(user) => user,
+            Err(e) => return AcknowledgeRevisionResult::Err(e),
+        };
+        let mut assignment = match TRAINING_ASSIGNMENTS
+            .with(|assignments| assignments.borrow().get(&(input.revision_id, user.id)))
+        {
+            Some(assignment) => assignment,
+            None => {
+                log_warn!(
+                    "training: No training assigned to acknowledge [revision_id={}, user_id={}, principal={}]",
+                    input.revision_id,
+                    user.id,
+                    principal
+                );
+                return AcknowledgeRevisionResult::Err(AppError::EntityNotFound(
+                    "No training is assigned on this revision".to_string(),
+                ));
+            }
+        };
+        if assignment.acknowledged_at.is_some() {
+            log_warn!(
+                "training: Revision already acknowledged [revision_id={}, user_id={}, principal={}]",
+                input.revision_id,
+                user.id,
+                principal
+            );
+            return AcknowledgeRevisionResult::Err(AppError::InvalidStateTransition(
+                "Revision has already been acknowledged".to_string(),
+            ));
+        }
+        if assignment.superseded_at.is_some() {
+            log_warn!(
+                "training: Cannot acknowledge a superseded assignment [revision_id={}, user_id={}, principal={}]",
+                input.revision_id,
+                user.id,
+                principal
+            );
+            return AcknowledgeRevisionResult::Err(AppError::InvalidStateTransition(
+                "A newer revision has been released, acknowledge that one instead".to_string(),
+            ));
+        }
+        let revision = match RevisionsManager::get_by_id(input.revision_id) {
+            Some(revision) => revision,
+            None => {
+                log_warn!(
+                    "training: Revision not found for acknowledgement [revision_id={}, principal={}]",
+                    input.revision_id,
+                    principal
+                );
+                return AcknowledgeRevisionResult::Err(AppError::EntityNotFound(
+                    "Revision not found".to_string(),
+                ));
+            }
+        };
+        if ApprovalsManager::get_status(&revision) != RevisionStatus::Released {
+            log_warn!(
+                "training: Cannot acknowledge an unreleased revision [revision_id={}, principal={}]",
+                revision.id,
+                principal
+            );
+            return AcknowledgeRevisionResult::Err(AppError::InvalidStateTransition(
+                "Only released revisions can be acknowledged".to_string(),
+            ));
+        }
+        let document = match DocumentManager::get_by_id(revision.document_id) {
+            Some(document) => document,
+            None => {
+                log_warn!(
+                    "training: Document not found for acknowledgement [document_id={}, revision_id={}, principal={}]",
+                    revision.document_id,
+                    revision.id,
+                    principal
+                );
+                return AcknowledgeRevisionResult::Err(AppError::EntityNotFound(
+                    "Document not found".to_string(),
+                ));
+            }
+        };
+        if !DocumentManager::has_permission(
+            user.id,
+            &document,
+            &Permission::Document(DocumentPermission::Read),
+        ) {
+            log_warn!(
+                "access_control: Permission denied for training acknowledgement [user_id={}, document_id={}]",
+                user.id,
+                document.id
+            );
+            return AcknowledgeRevisionResult::Err(AppError::Unauthorized);
+        }
+
+        if let Some(attestation) = &input.attestation {
+            match ApprovalsManager::sign_acknowledgement(principal, &user, &revision, attestation) {
+                Ok(signature) => assignment.signature_sequence = Some(signature.sequence),
+                Err(e) => {
+                    log_warn!(
+                        "training: Failed to sign acknowledgement [revision_id={}, principal={}] - {:?}",
+                        revision.id,
+                        principal,
+                        e
+                    );
+                    return AcknowledgeRevisionResult::Err(e);
+                }
+            }
+        }
+        assignment.acknowledged_at = Some(ic_cdk::api::time());
+        Self::insert_assignment(assignment.clone());
+        log_info!(
+            "training: Revision acknowledged [revision_id={}, document_id={}, user_id={}, signature_sequence={:?}, overdue={}, principal={}]",
+            assignment.revision_id,
+            assignment.document_id,
+            user.id,
+            assignment.signature_sequence,
+            assignment.acknowledged_at > Some(assignment.due_at),
+            principal
+        );
+        AcknowledgeRevisionResult::Ok(assignment)
+    }
+
+    /// The caller's training assignments, earliest due first.
+    pub fn list_my_training(input: ListMyTrainingInput) -> ListMyTrainingResult {
+        let principal = ic_cdk::api::msg_caller();
+        let user = match UserManager::authenticate(principal, "training listing") {
+            Ok(user) => user,
+            Err(e) => return ListMyTrainingResult::Err(e),
+        };
+        let mut assignments: Vec<TrainingAssignment> = TRAINING_ASSIGNMENTS.with(|assignments| {
+            assignments
+                .borrow()
+                .values()
+                .filter(|assignment| assignment.user_id == user.id)
+                .filter(|assignment| input.include_completed || Self::is_open(assignment))
+                .collect()
+        });
+        assignments.sort_by_key(|assignment| (assignment.due_at, assignment.revision_id));
+        ListMyTrainingResult::Ok(assignments)
+    }
+
+    /// Training status of a project's staff on its released revisions. Superseded
+    /// assignments are left out. Requires permission to view the project's metrics.
+    pub fn get_training_report(input: GetTrainingReportInput) -> GetTrainingReportResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Training report attempt [principal={}, project_id={}, document_id={:?}, user_id={:?}, overdue_only={}]",
+            principal,
+            input.project_id,
+            input.document_id,
+            input.user_id,
+            input.overdue_only
+        );
+
+        let user = match UserManager::authenticate(principal, "training report") {
+            Ok(user) => user,
+            Err(e) => return GetTrainingReportResult::Err(e),
+        };
+        if let Err(e) = Self::authorize(
+            &user,
+            input.project_id,
+            ProjectPermission::ViewMetrics,
+            "training report",
+        ) {
+            return GetTrainingReportResult::Err(e);
+        }
+
+        let now = ic_cdk::api::time();
+        let assignments: Vec<TrainingAssignment> = TRAINING_ASSIGNMENTS.with(|assignments| {
+            assignments
+                .borrow()
+                .values()
+                .filter(|assignment| assignment.project_id == input.project_id)
+                .filter(|assignment| assignment.superseded_at.is_none())
+                .filter(|assignment| {
+                    input
+                        .document_id
+                        .is_none_or(|document_id| assignment.document_id == document_id)
+                        && input
+                            .user_id
+                            .is_none_or(|user_id| assignment.user_id == user_id)
+                })
+                .collect()
+        });
+        let acknowledged = assignments
+            .iter()
+            .filter(|assignment| assignment.acknowledged_at.is_some())
+            .count() as u32;
+        let overdue = assignments
+            .iter()
+            .filter(|assignment| Self::is_overdue(assignment, now))
+            .count() as u32;
+
+        let mut entries: Vec<TrainingReportEntry> = assignments
+            .iter()
+            .filter(|assignment| !input.overdue_only || Self::is_overdue(assignment, now))
+            .map(|assignment| TrainingReportEntry {
+                user_name: UserManager::get_by_id(assignment.user_id)
+                    .map(|user| format!("{} {}", user.first_name, user.last_name))
+                    .unwrap_or_default(),
+                document_title: DocumentManager::get_by_id(assignment.document_id)
+                    .map(|document| document.title)
+                    .unwrap_or_default(),
+                overdue: Self::is_overdue(assignment, now),
+                assignment: assignment.clone(),
+            })
+            .collect();
+        entries.sort_by_key(|entry| {
+            (
+                !entry.overdue,
+                entry.assignment.due_at,
+                entry.assignment.user_id,
+            )
+        });
+
+        GetTrainingReportResult::Ok(TrainingReport {
+            project_id: input.project_id,
+            generated_at: now,
+            assigned: assignments.len() as u32,
+            acknowledged,
+            overdue,
+            entries,
+        })
+    }
+}
@@ -0,0 +1,7 @@
+use crate::uploads::uploads_manager::UploadsManager;
+use shared::types::uploads::{BeginUploadInput, BeginUploadResult};
+
+#[ic_cdk_macros::update]
+pub fn begin_upload(input: BeginUploadInput) -> BeginUploadResult {
+    UploadsManager::begin_upload(input)
+}
@@ -0,0 +1,7 @@
+use crate::uploads::uploads_manager::UploadsManager;
+use shared::types::uploads::{FinishUploadInput, FinishUploadResult};
+
+#[ic_cdk_macros::update]
+pub fn finish_upload(input: FinishUploadInput) -> FinishUploadResult {
+    UploadsManager::finish_upload(input)
+}
@@ -0,0 +1,7 @@
+use crate::uploads::uploads_manager::UploadsManager;
+use shared::types::uploads::GetStorageReportResult;
+
+#[ic_cdk_macros::query]
+pub fn get_storage_report() -> GetStorageReportResult {
+    UploadsManager::get_storage_report()
+}
@@ -0,0 +1,5 @@
+pub mod begin_upload;
+pub mod finish_upload;
+pub mod get_storage_report;
+pub mod set_upload_session_ttl;
+pub mod upload_chunk;
@@ -0,0 +1,7 @@
+use crate::uploads::uploads_manager::UploadsManager;
+use shared::types::uploads::{SetUploadSessionTtlInput, SetUploadSessionTtlResult};
+
+#[ic_cdk_macros::update]
+pub fn set_upload_session_ttl(input: SetUploadSessionTtlInput) -> SetUploadSessionTtlResult {
+    UploadsManager::set_upload_session_ttl(input)
+}
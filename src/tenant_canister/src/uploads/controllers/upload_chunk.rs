@@ -0,0 +1,7 @@
+use crate::uploads::uploads_manager::UploadsManager;
+use shared::types::uploads::{UploadChunkInput, UploadChunkResult};
+
+#[ic_cdk_macros::update]
+pub fn upload_chunk(input: UploadChunkInput) -> UploadChunkResult {
+    UploadsManager::upload_chunk(input)
+}
@@ -0,0 +1,701 @@
+use ic_stable_structures::memory_manager::{MemoryId, MemoryManager, VirtualMemory};
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap, StableCell};
+use sha2::{Digest, Sha256};
+use shared::consts::memory_ids::tenant_canister::{
+    UPLOAD_CHUNK_REFS_MEMORY_ID, UPLOAD_CONTENT_INDEX_MEMORY_ID, UPLOAD_SESSIONS_MEMORY_ID,
+    UPLOAD_SETTINGS_MEMORY_ID,
+};
+use shared::consts::revisions::MAX_DOCUMENT_SIZE;
+use shared::consts::uploads::{
+    MAX_HASHED_CHUNKS_PER_CALL, MAX_UPLOAD_SESSION_TTL, MAX_UPLOAD_SIZE, MIN_UPLOAD_SESSION_TTL,
+    STORAGE_CLEANUP_INTERVAL_SECONDS, UPLOAD_SESSION_TTL,
+};
+use shared::types::access_control::{OrganizationPermission, Permission};
+use shared::types::encryption::ContentEncryption;
+use shared::types::errors::AppError;
+use shared::types::revisions::{ChunkDigest, RevisionContentId, RevisionId};
+use shared::types::uploads::{
+    BeginUploadInput, BeginUploadResult, FinishUploadInput, FinishUploadResult,
+    GetStorageReportResult, SetUploadSessionTtlInput, SetUploadSessionTtlResult, StorageReport,
+    UploadChunkInput, UploadChunkResult, UploadId, UploadSession,
+};
+use shared::types::users::{User, UserId};
+use shared::utils::sha256::Sha256State;
+use shared::{log_debug, log_info, log_warn};
+use std::cell::RefCell;
+use std::time::Duration;
+
+use crate::access_control::AccessControlManager;
+use crate::exports::ExportsManager;
+use crate::quotas::quotas_manager::QuotasManager;
+use crate::revisions::revisions_manager::RevisionsManager;
+use crate::users::user_manager::UserManager;
+
+type Memory = VirtualMemory<DefaultMemoryImpl>;
+
+thread_local! {
+    static MEMORY_MANAGER: RefCell<MemoryManager<DefaultMemoryImpl>> =
+        RefCell::new(MemoryManager::init(DefaultMemoryImpl::default()));
+
+    static UPLOAD_SESSIONS: RefCell<StableBTreeMap<UploadId, UploadSession, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(UPLOAD_SESSIONS_MEMORY_ID))),
+        )
+    );
+
+    // (upload, chunk) -> digest of the chunk data in the revision chunk store
+    static UPLOAD_CHUNK_REFS: RefCell<StableBTreeMap<(UploadId, u32), ChunkDigest, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(UPLOAD_CHUNK_REFS_MEMORY_ID))),
+        )
+    );
+
+    // Reserved content ID -> finished upload
+    static UPLOAD_CONTENT_INDEX: RefCell<StableBTreeMap<RevisionContentId, UploadId, Memory>> = RefCell::new(
+        StableBTreeMap::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(UPLOAD_CONTENT_INDEX_MEMORY_ID))),
+        )
+    );
+
+    // Time after which uploads that were not attached to a revision are removed
+    static UPLOAD_SESSION_TTL_SETTING: RefCell<StableCell<u64, Memory>> = RefCell::new(
+        StableCell::init(
+            MEMORY_MANAGER.with(|m| m.borrow().get(MemoryId::new(UPLOAD_SETTINGS_MEMORY_ID))),
+            UPLOAD_SESSION_TTL,
+        ).expect("Failed to initialize upload settings stable cell")
+    );
+}
+
+pub struct UploadsManager;
+
+impl UploadsManager {
+    fn get_next_id() -> UploadId {
+        UPLOAD_SESSIONS.with(|sessions| {
+            sessions
+                .borrow()
+                .last_key_value()
+                .map(|(id, _)| id + 1)
+                .unwrap_or_default()
+        })
+    }
+
+    fn insert(session: UploadSession) {
+        UPLOAD_SESSIONS.with(|sessions| {
+            sessions.borrow_mut().insert(session.id, session);
+        });
+    }
+
+    fn get_by_content_id(content_id: RevisionContentId) -> Option<UploadSession> {
+        let upload_id = UPLOAD_CONTENT_INDEX.with(|index| index.borrow().get(&content_id))?;
+        UPLOAD_SESSIONS.with(|sessions| sessions.borrow().get(&upload_id))
+    }
+
+    fn remove(session: &UploadSession) {
+        Self::remove_chunk_refs(session.id);
+        if let Some(content_id) = session.content_id {
+            UPLOAD_CONTENT_INDEX.with(|index| index.borrow_mut().remove(&content_id));
+        }
+        UPLOAD_SESSIONS.with(|sessions| sessions.borrow_mut().remove(&session.id));
+    }
+
+    fn get_chunk_digests(upload_id: UploadId) -> Vec<(u32, ChunkDigest)> {
+        UPLOAD_CHUNK_REFS.with(|refs| {
+            refs.borrow()
+                .range((upload_id, 0)..=(upload_id, u32::MAX))
+                .map(|((_, chunk_id), digest)| (chunk_id, digest))
+                .collect()
+        })
+    }
+
+    /// Removes the chunk references of an upload and releases the chunks they held.
+    fn remove_chunk_refs(upload_id: UploadId) {
+        let digests = Self::get_chunk_digests(upload_id);
+        UPLOAD_CHUNK_REFS.with(|refs| {
+            let mut refs = refs.borrow_mut();
+            for (chunk_id, _) in &digests {
+                refs.remove(&(upload_id, *chunk_id));
+            }
+        });
+        for (_, digest) in &digests {
+            RevisionsManager::release_chunk_data(digest);
+        }
+    }
+
+    /// Feeds the uploaded chunks that follow the hashed ones into the upload hash, at
+    /// most `MAX_HASHED_CHUNKS_PER_CALL` per call. Returns whether any chunk was hashed.
+    fn hash_chunks(session: &mut UploadSession) -> bool {
+        let start = session.hashed_chunks;
+        let end = start
+            .saturating_add(MAX_HASHED_CHUNKS_PER_CALL)
+            .min(session.total_chunks);
+        for chunk_id in start..end {
+            let Some(data) = UPLOAD_CHUNK_REFS
+                .with(|refs| refs.borrow().get(&(session.id, chunk_id)))
+                .and_then(|digest| RevisionsManager::get_chunk_data(&digest))
+            else {
+                break;
+            };
+            session.hash.update(&data);
+            session.hashed_chunks += 1;
+        }
+        session.hashed_chunks > start
+    }
+
+    pub fn get_session_ttl() -> u64 {
+        UPLOAD_SESSION_TTL_SETTING.with(|cell| *cell.borrow().get())
+    }
+
+    /// Declared size of the uploads that may still be attached to a revision.
+    pub fn get_pending_upload_bytes() -> u64 {
+        let ttl = Self::get_session_ttl();
+        let now = ic_cdk::api::time();
+        UPLOAD_SESSIONS.with(|sessions| {
+            sessions
+                .borrow()
+                .values()
+                .filter(|session| !Self::is_expired(session, ttl, now))
+                .map(|session| session.total_size)
+                .sum()
+        })
+    }
+
+    fn is_expired(session: &UploadSession, ttl: u64, now: u64) -> bool {
+        session.expires_at <= now || session.created_at.saturating_add(ttl) <= now
+    }
+
+    /// Returns the caller's upload session if it can still receive chunks.
+    fn get_open_session(user: &User, upload_id: UploadId) -> Result<UploadSession, AppError> {
+        let session = UPLOAD_SESSIONS
+            .with(|sessions| sessions.borrow().get(&upload_id))
+            .ok_or_else(|| AppError::EntityNotFound("Upload not found".to_string()))?;
+        if session.created_by != user.id {
+            log_warn!(
+                "access_control: Upload accessed by another user [upload_id={}, owner={}, user_id={}]",
+                upload_id,
+                session.created_by,
+                user.id
+            );
+            return Err(AppError::Unauthorized);
+        }
+        if session.content_id.is_some() {
+            return Err(AppError::InvalidStateTransition(
+                "Upload is already finished".to_string(),
+            ));
+        }
+        if session.expires_at <= ic_cdk::api::time() {
+            return Err(AppError::InvalidStateTransition(
+                "Upload has expired".to_string(),
+            ));
+        }
+        Ok(session)
+    }
+
+    fn validate_begin_input(input: &BeginUploadInput) -> Result<(), AppError> {
+        if input.total_size == 0 || input.total_size > MAX_UPLOAD_SIZE {
+            return Err(AppError::ValidationError(format!(
+                "Upload size must be between 1 and {} bytes",
+                MAX_UPLOAD_SIZE
+            )));
+        }
+        if input.total_chunks == 0
+            || input.total_chunks as u64 > input.total_size
+            || input.total_size > input.total_chunks as u64 * MAX_DOCUMENT_SIZE as u64
+        {
+            return Err(AppError::ValidationError(format!(
+                "Chunk count does not fit the upload size with chunks of at most {} bytes",
+                MAX_DOCUMENT_SIZE
+            )));
+        }
+        if input.sha256.len() != 64 || !input.sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(AppError::ValidationError(
+                "Expected checksum must be a hex encoded SHA-256 hash".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn begin_upload(input: BeginUploadInput) -> BeginUploadResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Upload start attempt [principal={}, total_size={}, total_chunks={}]",
+            principal,
+            input.total_size,
+            input.total_chunks
+        );
+
+        let user = match UserManager::authenticate(principal, "upload start") {
+            Ok(user) => user,
+            Err(e) => return BeginUploadResult::Err(e),
+        };
+        if let Err(e) = Self::validate_begin_input(&input) {
+            log_warn!(
+                "content_upload: Invalid upload [total_size={}, total_chunks={}, principal={}] - {:?}",
+                input.total_size,
+                input.total_chunks,
+                principal,
+                e
+            );
+            return BeginUploadResult::Err(e);
+        }
+        let mime_type =
+            match RevisionsManager::validate_mime_type(&input.content_type, &input.mime_type) {
+                Ok(mime_type) => mime_type,
+                Err(e) => {
+                    log_warn!(
+                    "content_upload: Invalid content type [mime_type={:?}, principal={}] - {:?}",
+                    input.mime_type,
+                    principal,
+                    e
+                );
+                    return BeginUploadResult::Err(e);
+                }
+            };
+        if let Err(e) = QuotasManager::check_quota(input.total_size) {
+            log_warn!(
+                "content_upload: Upload exceeds the storage quota [total_size={}, principal={}] - {:?}",
+                input.total_size,
+                principal,
+                e
+            );
+            return BeginUploadResult::Err(e);
+        }
+
+        let now = ic_cdk::api::time();
+        let session = UploadSession {
+            id: Self::get_next_id(),
+            created_by: user.id,
+            created_at: now,
+            expires_at: now + Self::get_session_ttl(),
+            content_type: input.content_type,
+            file_name: input.file_name,
+            total_size: input.total_size,
+            total_chunks: input.total_chunks,
+            mime_type: Some(mime_type),
+            encryption: input.encryption,
+            expected_sha256: input.sha256.to_lowercase(),
+            hash: Sha256State::new(),
+            hashed_chunks: 0,
+            content_id: None,
+        };
+        Self::insert(session.clone());
+        log_info!(
+            "content_upload: Upload started [upload_id={}, total_size={}, total_chunks={}, user_id={}, principal={}]",
+            session.id,
+            session.total_size,
+            session.total_chunks,
+            user.id,
+            principal
+        );
+        BeginUploadResult::Ok(session.id)
+    }
+
+    pub fn upload_chunk(input: UploadChunkInput) -> UploadChunkResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "content_upload: Storing chunk [principal={}, upload_id={}, chunk_id={}, chunk_size={}]",
+            principal,
+            input.upload_id,
+            input.chunk_id,
+            input.data.len()
+        );
+
+        let user = match UserManager::authenticate(principal, "chunk upload") {
+            Ok(user) => user,
+            Err(e) => return UploadChunkResult::Err(e),
+        };
+        let mut session = match Self::get_open_session(&user, input.upload_id) {
+            Ok(session) => session,
+            Err(e) => {
+                log_warn!(
+                    "content_upload: Failed to store chunk [upload_id={}, chunk_id={}, principal={}] - {:?}",
+                    input.upload_id,
+                    input.chunk_id,
+                    principal,
+                    e
+                );
+                return UploadChunkResult::Err(e);
+            }
+        };
+        if input.chunk_id >= session.total_chunks {
+            log_warn!(
+                "content_upload: Chunk ID out of range [upload_id={}, chunk_id={}, total_chunks={}, principal={}]",
+                session.id,
+                input.chunk_id,
+                session.total_chunks,
+                principal
+            );
+            return UploadChunkResult::Err(AppError::InvalidInput(format!(
+                "Chunk ID {} is invalid for an upload with {} chunks",
+                input.chunk_id, session.total_chunks
+            )));
+        }
+        if input.data.is_empty() || input.data.len() > MAX_DOCUMENT_SIZE as usize {
+            log_warn!(
+                "content_upload: Invalid chunk size [upload_id={}, chunk_id={}, chunk_size={}, principal={}]",
+                session.id,
+                input.chunk_id,
+                input.data.len(),
+                principal
+            );
+            return UploadChunkResult::Err(AppError::ValidationError(format!(
+                "Chunk size must be between 1 and {} bytes",
+                MAX_DOCUMENT_SIZE
+            )));
+        }
+
+        if let Some(checksum) = &input.checksum {
+            let actual = hex::encode(Sha256::digest(&input.data));
+            if !checksum.eq_ignore_ascii_case(&actual) {
+                log_warn!(
+                    "content_upload: Chunk checksum mismatch [upload_id={}, chunk_id={}, supplied={}, actual={}, principal={}]",
+                    session.id,
+                    input.chunk_id,
+                    checksum,
+                    actual,
+                    principal
+                );
+                return UploadChunkResult::Err(AppError::ValidationError(format!(
+                    "Checksum of chunk {} does not match its data",
+                    input.chunk_id
+                )));
+            }
+        }
+
+        // Encrypted content cannot be checked against its declared type
+        if let (0, Some(mime_type), None) =
+            (input.chunk_id, &session.mime_type, &session.encryption)
+        {
+            if let Err(e) = RevisionsManager::verify_mime_type(mime_type, &input.data) {
+                log_warn!(
+                    "content_upload: Content does not match its declared type [upload_id={}, mime_type={}, principal={}] - {:?}",
+                    session.id,
+                    mime_type,
+                    principal,
+                    e
+                );
+                return UploadChunkResult::Err(e);
+            }
+        }
+
+        if input.chunk_id < session.hashed_chunks {
+            // Already part of the upload hash, so it cannot be replaced
+            let stored =
+                UPLOAD_CHUNK_REFS.with(|refs| refs.borrow().get(&(session.id, input.chunk_id)));
+            if stored != Some(Sha256::digest(&input.data).into()) {
+                log_warn!(
+                    "content_upload: Verified chunk cannot be replaced [upload_id={}, chunk_id={}, principal={}]",
+                    session.id,
+                    input.chunk_id,
+                    principal
+                );
+                return UploadChunkResult::Err(AppError::InvalidStateTransition(format!(
+                    "Chunk {} has already been uploaded",
+                    input.chunk_id
+                )));
+            }
+            return UploadChunkResult::Ok;
+        }
+
+        let digest = RevisionsManager::store_chunk_data(input.data);
+        let replaced = UPLOAD_CHUNK_REFS.with(|refs| {
+            refs.borrow_mut()
+                .insert((session.id, input.chunk_id), digest)
+        });
+        if let Some(replaced) = replaced {
+            RevisionsManager::release_chunk_data(&replaced);
+        }
+        if Self::hash_chunks(&mut session) {
+            Self::insert(session);
+        }
+        UploadChunkResult::Ok
+    }
+
+    /// Verifies that all chunks are present and match the declared size and checksum,
+    /// then reserves the content ID the upload is referenced by.
+    pub fn finish_upload(input: FinishUploadInput) -> FinishUploadResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "content_upload: Finishing upload [principal={}, upload_id={}]",
+            principal,
+            input.upload_id
+        );
+
+        let user = match UserManager::authenticate(principal, "upload completion") {
+            Ok(user) => user,
+            Err(e) => return FinishUploadResult::Err(e),
+        };
+        let mut session = match Self::get_open_session(&user, input.upload_id) {
+            Ok(session) => session,
+            Err(e) => {
+                log_warn!(
+                    "content_upload: Failed to finish upload [upload_id={}, principal={}] - {:?}",
+                    input.upload_id,
+                    principal,
+                    e
+                );
+                return FinishUploadResult::Err(e);
+            }
+        };
+
+        if Self::hash_chunks(&mut session) {
+            Self::insert(session.clone());
+        }
+        if session.hashed_chunks < session.total_chunks {
+            let missing = session.hashed_chunks;
+            let uploaded =
+                UPLOAD_CHUNK_REFS.with(|refs| refs.borrow().contains_key(&(session.id, missing)));
+            log_warn!(
+                "content_upload: Upload is not complete [upload_id={}, next_chunk={}, uploaded={}, principal={}]",
+                session.id,
+                missing,
+                uploaded,
+                principal
+            );
+            return FinishUploadResult::Err(if uploaded {
+                AppError::InvalidStateTransition(format!(
+                    "Upload is still being verified up to chunk {}, finish it again",
+                    missing
+                ))
+            } else {
+                AppError::ValidationError(format!("Chunk {} has not been uploaded", missing))
+            });
+        }
+        let size = session.hash.len();
+        if size != session.total_size {
+            log_warn!(
+                "content_upload: Upload size mismatch [upload_id={}, expected={}, actual={}, principal={}]",
+                session.id,
+                session.total_size,
+                size,
+                principal
+            );
+            return FinishUploadResult::Err(AppError::ValidationError(format!(
+                "Uploaded {} bytes, expected {}",
+                size, session.total_size
+            )));
+        }
+        let sha256 = hex::encode(session.hash.clone().finalize());
+        if sha256 != session.expected_sha256 {
+            log_warn!(
+                "content_upload: Checksum mismatch [upload_id={}, expected={}, actual={}, principal={}]",
+                session.id,
+                session.expected_sha256,
+                sha256,
+                principal
+            );
+            return FinishUploadResult::Err(AppError::ValidationError(
+                "Uploaded content does not match the expected SHA-256 checksum".to_string(),
+            ));
+        }
+
+        let content_id = RevisionsManager::get_next_content_id();
+        session.content_id = Some(content_id);
+        Self::insert(session.clone());
+        UPLOAD_CONTENT_INDEX.with(|index| index.borrow_mut().insert(content_id, session.id));
+        log_info!(
+            "content_upload: Upload finished [upload_id={}, content_id={}, total_size={}, user_id={}, principal={}]",
+            session.id,
+            content_id,
+            session.total_size,
+            user.id,
+            principal
+        );
+        FinishUploadResult::Ok(content_id)
+    }
+
+    /// Checks that `content_id` belongs to a finished upload of the user that has not
+    /// been attached to a revision yet.
+    pub fn authorize_attachment(
+        user_id: UserId,
+        content_id: RevisionContentId,
+    ) -> Result<(), AppError> {
+        let session = Self::get_by_content_id(content_id)
+            .ok_or_else(|| AppError::EntityNotFound(format!("Content {} not found", content_id)))?;
+        if session.created_by != user_id {
+            return Err(AppError::Unauthorized);
+        }
+        if session.expires_at <= ic_cdk::api::time() {
+            return Err(AppError::InvalidStateTransition(
+                "Upload has expired".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Session of a finished upload that has not been attached yet.
+    pub fn get_unattached_upload(content_id: RevisionContentId) -> Option<UploadSession> {
+        Self::get_by_content_id(content_id)
+    }
+
+    /// Encryption declared for a finished upload that has not been attached yet.
+    pub fn get_upload_encryption(content_id: RevisionContentId) -> Option<ContentEncryption> {
+        Self::get_unattached_upload(content_id).and_then(|session| session.encryption)
+    }
+
+    /// Stores a finished upload as content of the revision. Callers must have checked
+    /// `authorize_attachment` first.
+    pub fn attach_upload(
+        content_id: RevisionContentId,
+        revision_id: RevisionId,
+        content_index: u32,
+    ) -> RevisionContentId {
+        let Some(session) = Self::get_by_content_id(content_id) else {
+            return content_id;
+        };
+        let digests = Self::get_chunk_digests(session.id)
+            .into_iter()
+            .map(|(_, digest)| digest)
+            .collect();
+        RevisionsManager::insert_uploaded_content(
+            &session,
+            content_id,
+            revision_id,
+            content_index,
+            digests,
+        );
+        Self::remove(&session);
+        log_info!(
+            "content_upload: Upload attached to revision [upload_id={}, content_id={}, revision_id={}, content_index={}]",
+            session.id,
+            content_id,
+            revision_id,
+            content_index
+        );
+        content_id
+    }
+
+    /// Removes uploads that expired before being attached to a revision, incomplete
+    /// chunked contents and chunks nothing refers to anymore.
+    pub fn cleanup_storage() {
+        let ttl = Self::get_session_ttl();
+        let now = ic_cdk::api::time();
+        let expired: Vec<UploadSession> = UPLOAD_SESSIONS.with(|sessions| {
+            sessions
+                .borrow()
+                .values()
+                .filter(|session| Self::is_expired(session, ttl, now))
+                .collect()
+        });
+        for session in &expired {
+            Self::remove(session);
+        }
+
+        // Chunks are not fully reference counted until the storage migration is done
+        let (incomplete_contents, orphan_chunks, orphan_bytes) = if RevisionsManager::is_migrating()
+        {
+            (0, 0, 0)
+        } else {
+            let incomplete_contents = RevisionsManager::remove_incomplete_contents(ttl, now);
+            let (orphan_chunks, orphan_bytes) = RevisionsManager::remove_orphan_chunks();
+            (incomplete_contents, orphan_chunks, orphan_bytes)
+        };
+        let expired_exports = ExportsManager::remove_expired_exports(now);
+        if !expired.is_empty()
+            || incomplete_contents > 0
+            || orphan_chunks > 0
+            || expired_exports > 0
+        {
+            log_info!(
+                "chunk_cleanup: Storage cleanup completed [expired_uploads={}, incomplete_contents={}, orphan_chunks={}, orphan_bytes={}, expired_exports={}]",
+                expired.len(),
+                incomplete_contents,
+                orphan_chunks,
+                orphan_bytes,
+                expired_exports
+            );
+        }
+    }
+
+    /// Schedules the periodic storage cleanup. Timers do not survive upgrades, so this
+    /// runs from both `init` and `post_upgrade`.
+    pub fn start_cleanup_timer() {
+        ic_cdk_timers::set_timer_interval(
+            Duration::from_secs(STORAGE_CLEANUP_INTERVAL_SECONDS),
+            Self::cleanup_storage,
+        );
+    }
+
+    fn authorize_storage_settings(user: &User, operation: &str) -> Result<(), AppError> {
+        if !AccessControlManager::has_organization_permission(
+            user.id,
+            &Permission::Organization(OrganizationPermission::ConfigureSettings),
+        ) {
+            log_warn!(
+                "access_control: Permission denied for {} [user_id={}]",
+                operation,
+                user.id
+            );
+            return Err(AppError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    pub fn set_upload_session_ttl(input: SetUploadSessionTtlInput) -> SetUploadSessionTtlResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Upload session TTL update attempt [principal={}, ttl={}]",
+            principal,
+            input.ttl
+        );
+
+        let user = match UserManager::authenticate(principal, "upload session TTL update") {
+            Ok(user) => user,
+            Err(e) => return SetUploadSessionTtlResult::Err(e),
+        };
+        if let Err(e) = Self::authorize_storage_settings(&user, "upload session TTL update") {
+            return SetUploadSessionTtlResult::Err(e);
+        }
+        if !(MIN_UPLOAD_SESSION_TTL..=MAX_UPLOAD_SESSION_TTL).contains(&input.ttl) {
+            log_warn!(
+                "content_upload: Upload session TTL out of range [ttl={}, principal={}]",
+                input.ttl,
+                principal
+            );
+            return SetUploadSessionTtlResult::Err(AppError::ValidationError(format!(
+                "Upload session TTL must be between {} and {} nanoseconds",
+                MIN_UPLOAD_SESSION_TTL, MAX_UPLOAD_SESSION_TTL
+            )));
+        }
+        UPLOAD_SESSION_TTL_SETTING.with(|cell| {
+            cell.borrow_mut()
+                .set(input.ttl)
+                .expect("Failed to set upload session TTL in stable memory");
+        });
+        log_info!(
+            "content_upload: Upload session TTL updated [ttl={}, user_id={}, principal={}]",
+            input.ttl,
+            user.id,
+            principal
+        );
+        SetUploadSessionTtlResult::Ok
+    }
+
+    pub fn get_storage_report() -> GetStorageReportResult {
+        let principal = ic_cdk::api::msg_caller();
+        log_debug!(
+            "auth_check: Storage report attempt [principal={}]",
+            principal
+        );
+
+        let user = match UserManager::authenticate(principal, "storage report") {
+            Ok(user) => user,
+            Err(e) => return GetStorageReportResult::Err(e),
+        };
+        if let Err(e) = Self::authorize_storage_settings(&user, "storage report") {
+            return GetStorageReportResult::Err(e);
+        }
+
+        let mut report = StorageReport {
+            chunk_count: 0,
+            bytes_stored: 0,
+            bytes_referenced: 0,
+            bytes_saved_by_dedup: 0,
+            orphan_count: 0,
+            orphan_bytes: 0,
+            pending_upload_count: UPLOAD_SESSIONS.with(|sessions| sessions.borrow().len()),
+            upload_session_ttl: Self::get_session_ttl(),
+        };
+        RevisionsManager::fill_storage_report(&mut report);
+        GetStorageReportResult::Ok(report)
+    }
+}
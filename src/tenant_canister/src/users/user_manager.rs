@@ -52,6 +52,10 @@ impl UserManager {
         })
     }
 
+    pub fn get_by_id(id: UserId) -> Option<User> {
+        USERS.with(|users| users.borrow().get(&id))
+    }
+
     pub fn get_by_principal(principal: Principal) -> Option<User> {
         USERS.with(|users| {
             users
@@ -253,4 +257,21 @@ impl UserManager {
             }
         }
     }
+
+    /// Resolves the caller of an endpoint to a user, logging failures against
+    /// `operation`.
+    pub fn authenticate(principal: Principal, operation: &str) -> Result<User, AppError> {
+        match Self::get_user_by_principal(principal) {
+            GetUserResult::Ok(user) => Ok(user),
+            GetUserResult::Err(e) => {
+                log_warn!(
+                    "auth_check: Authentication failed for {} [principal={}] - {:?}",
+                    operation,
+                    principal,
+                    e
+                );
+                Err(e)
+            }
+        }
+    }
 }
@@ -3,6 +3,7 @@ use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap};
 use shared::consts::memory_ids::tenant_canister::WORKFLOWS_MEMORY_ID;
 use shared::traits::workflows::WorkflowGraphExt;
 use shared::types::errors::AppError;
+use shared::types::notifications::NotificationEvent;
 use shared::types::workflows::{
     CreateWorkflowInput, Edge, EventId, StateId, Workflow, WorkflowGraph, WorkflowId,
 };
@@ -10,6 +11,9 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU32, Ordering};
 
+use crate::notifications::NotificationsManager;
+use crate::users::user_manager::UserManager;
+
 type Memory = VirtualMemory<DefaultMemoryImpl>;
 
 thread_local! {
@@ -136,7 +140,19 @@ impl WorkflowsManager {
             Ok(_) => {
                 let mut updated_workflow = workflow.clone();
                 updated_workflow.current_state = state_machine.current_state().clone();
-                Self::update_workflow(workflow_id, updated_workflow);
+                Self::update_workflow(workflow_id, updated_workflow.clone());
+                let actor =
+                    UserManager::get_by_principal(ic_cdk::api::msg_caller()).map(|user| user.id);
+                NotificationsManager::notify_workflow(
+                    &updated_workflow,
+                    actor,
+                    NotificationEvent::WorkflowTransitioned {
+                        workflow_id,
+                        event_id,
+                        from_state: workflow.current_state,
+                        to_state: updated_workflow.current_state.clone(),
+                    },
+                );
                 Ok(())
             }
             Err(e) => Err(AppError::InvalidStateTransition(e)),